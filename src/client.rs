@@ -2,11 +2,16 @@
 //! =========
 //! This file contains the Paystack API client, and it associated endpoints.
 use crate::{
-    ApplePayEndpoints, CustomersEndpoints, DedicatedVirtualAccountEndpoints, HttpClient,
-    PlansEndpoints, RefundEndpoints, SubaccountEndpoints, SubscriptionEndpoints, TerminalEndpoints,
-    TransactionEndpoints, TransactionSplitEndpoints, VirtualTerminalEndpoints,
+    ApplePayEndpoints, Currency, CustomersEndpoints, DedicatedVirtualAccountEndpoints, HttpClient,
+    PaystackAPIError, PlansEndpoints, RefundEndpoints, SubaccountEndpoints, SubscriptionEndpoints,
+    TerminalEndpoints, TransactionEndpoints, TransactionSplitEndpoints, VirtualTerminalEndpoints,
 };
-use std::sync::Arc;
+use std::{env, sync::Arc};
+
+/// Environment variable read by `PaystackClient::from_env`
+pub const PAYSTACK_SECRET_KEY_VAR: &str = "PAYSTACK_SECRET_KEY";
+/// Fallback environment variable read by `PaystackClient::from_env`
+pub const PAYSTACK_API_KEY_VAR: &str = "PAYSTACK_API_KEY";
 
 /// This is the entry level struct for the paystack API.
 /// it allows for authentication of the client
@@ -36,8 +41,27 @@ pub struct PaystackClient<T: HttpClient + Default> {
 }
 
 impl<T: HttpClient + Default> PaystackClient<T> {
+    /// Creates a new client with the provided API key.
+    ///
+    /// A warning is logged if the key does not look like a Paystack secret key,
+    /// but the client is still created so new key formats keep working.
+    /// Use `try_new` to reject such keys instead.
     pub fn new(api_key: String) -> PaystackClient<T> {
-        let http = Arc::new(T::default());
+        PaystackClient::with_http(api_key, T::default())
+    }
+
+    /// Creates a new client with the provided API key that sends its requests with the given HTTP client.
+    ///
+    /// Use this to configure the HTTP client, or to pass a `DryRunClient` to inspect requests without sending them.
+    /// A warning is logged if the key does not look like a Paystack secret key.
+    pub fn with_http(api_key: String, http: T) -> PaystackClient<T> {
+        if let Err(e) = validate_secret_key(&api_key) {
+            log::warn!(
+                "{e}. Requests made with this client will likely fail with 401 Unauthorized"
+            );
+        }
+
+        let http = Arc::new(http);
         let key = Arc::new(api_key);
         PaystackClient {
             transactions: TransactionEndpoints::new(Arc::clone(&key), Arc::clone(&http)),
@@ -56,4 +80,160 @@ impl<T: HttpClient + Default> PaystackClient<T> {
             refund: RefundEndpoints::new(Arc::clone(&key), Arc::clone(&http)),
         }
     }
+
+    /// Keeps the raw JSON body of each response in `Response::raw` on every route of the client,
+    /// alongside the typed data.
+    ///
+    /// This gives access to fields the typed models do not cover yet, and lets `Response::data_as`
+    /// read fields dropped by the typed data. It is off by default, since the body is then held
+    /// twice in memory. Each endpoint group can also be configured on its own with its `with_keep_raw`.
+    ///
+    /// # Arguments
+    /// * `keep_raw` - Whether the raw body is kept on each response
+    pub fn with_keep_raw(self, keep_raw: bool) -> PaystackClient<T> {
+        PaystackClient {
+            transactions: self.transactions.with_keep_raw(keep_raw),
+            transaction_split: self.transaction_split.with_keep_raw(keep_raw),
+            subaccount: self.subaccount.with_keep_raw(keep_raw),
+            terminal: self.terminal.with_keep_raw(keep_raw),
+            virutal_terminal: self.virutal_terminal.with_keep_raw(keep_raw),
+            customers: self.customers.with_keep_raw(keep_raw),
+            dedicated_virtual_account: self.dedicated_virtual_account.with_keep_raw(keep_raw),
+            apple_pay: self.apple_pay.with_keep_raw(keep_raw),
+            plans: self.plans.with_keep_raw(keep_raw),
+            subscription: self.subscription.with_keep_raw(keep_raw),
+            refund: self.refund.with_keep_raw(keep_raw),
+        }
+    }
+
+    /// Generates transaction references starting with the prefix when a create call leaves `reference` unset.
+    /// See `TransactionEndpoints::with_reference_prefix`.
+    ///
+    /// # Arguments
+    /// * `prefix` - The start of the generated references, e.g. `ORD-`
+    ///
+    /// # Returns
+    /// A Result containing the client, or a validation error if the prefix is not valid in a reference
+    pub fn with_reference_prefix(
+        mut self,
+        prefix: &str,
+    ) -> Result<PaystackClient<T>, PaystackAPIError> {
+        self.transactions = self.transactions.with_reference_prefix(prefix)?;
+        Ok(self)
+    }
+
+    /// Sets the currency of refunds, transactions and authorization charges created without one,
+    /// for integrations that only operate in one currency. A currency set on a request is sent unchanged.
+    /// See `TransactionEndpoints::with_default_currency` and `RefundEndpoints::with_default_currency`.
+    ///
+    /// # Arguments
+    /// * `currency` - The currency of the integration
+    pub fn with_default_currency(mut self, currency: Currency) -> PaystackClient<T> {
+        self.transactions = self.transactions.with_default_currency(currency.clone());
+        self.refund = self.refund.with_default_currency(currency);
+        self
+    }
+
+    /// Creates a new client, checking that the API key is a Paystack secret key (`sk_...`).
+    ///
+    /// # Returns
+    /// A Result containing the client, or a validation error if the key is empty or not a secret key
+    pub fn try_new(api_key: String) -> Result<PaystackClient<T>, PaystackAPIError> {
+        validate_secret_key(&api_key)?;
+
+        Ok(PaystackClient::new(api_key))
+    }
+
+    /// Creates a client with the secret key stored in the `PAYSTACK_SECRET_KEY` environment variable.
+    /// `PAYSTACK_API_KEY` is used when `PAYSTACK_SECRET_KEY` is not set.
+    ///
+    /// # Returns
+    /// A Result containing the client, or a validation error if the key is missing
+    /// or is not a secret key (`sk_...`)
+    pub fn from_env() -> Result<PaystackClient<T>, PaystackAPIError> {
+        let api_key = env::var(PAYSTACK_SECRET_KEY_VAR)
+            .or_else(|_| env::var(PAYSTACK_API_KEY_VAR))
+            .map_err(|_| {
+                PaystackAPIError::Validation(format!(
+                    "{PAYSTACK_SECRET_KEY_VAR} environment variable is not set"
+                ))
+            })?;
+
+        PaystackClient::try_new(api_key)
+    }
+}
+
+/// Checks that the key is a Paystack secret key.
+fn validate_secret_key(api_key: &str) -> Result<(), PaystackAPIError> {
+    if api_key.is_empty() {
+        Err(PaystackAPIError::Validation(String::from(
+            "the API key is empty",
+        )))
+    } else if api_key.starts_with("sk_") {
+        Ok(())
+    } else if api_key.starts_with("pk_") {
+        Err(PaystackAPIError::Validation(String::from(
+            "a public key (pk_...) was provided, use your secret key (sk_...) instead",
+        )))
+    } else {
+        Err(PaystackAPIError::Validation(String::from(
+            "the API key is not a Paystack secret key (sk_...)",
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ReqwestClient;
+
+    #[cfg(any(feature = "default-tls", feature = "rustls-tls"))]
+    #[test]
+    fn try_new_accepts_secret_keys() {
+        let test_key = PaystackClient::<ReqwestClient>::try_new("sk_test_xxxxxxxxxxxx".to_string());
+        let live_key = PaystackClient::<ReqwestClient>::try_new("sk_live_xxxxxxxxxxxx".to_string());
+
+        assert!(test_key.is_ok());
+        assert!(live_key.is_ok());
+    }
+
+    #[test]
+    fn try_new_rejects_public_key() {
+        let client = PaystackClient::<ReqwestClient>::try_new("pk_test_xxxxxxxxxxxx".to_string());
+
+        assert!(
+            matches!(client, Err(PaystackAPIError::Validation(msg)) if msg.contains("public key"))
+        );
+    }
+
+    #[test]
+    fn try_new_rejects_empty_key() {
+        let client = PaystackClient::<ReqwestClient>::try_new(String::new());
+
+        assert!(matches!(client, Err(PaystackAPIError::Validation(msg)) if msg.contains("empty")));
+    }
+
+    // Kept in a single test since the environment is shared by all the tests in the process.
+    #[cfg(any(feature = "default-tls", feature = "rustls-tls"))]
+    #[test]
+    fn from_env_validates_the_secret_key() {
+        env::remove_var(PAYSTACK_SECRET_KEY_VAR);
+        env::remove_var(PAYSTACK_API_KEY_VAR);
+        let missing = PaystackClient::<ReqwestClient>::from_env();
+        assert!(
+            matches!(missing, Err(PaystackAPIError::Validation(msg)) if msg.contains("not set"))
+        );
+
+        env::set_var(PAYSTACK_SECRET_KEY_VAR, "pk_test_xxxxxxxxxxxx");
+        let public_key = PaystackClient::<ReqwestClient>::from_env();
+        assert!(
+            matches!(public_key, Err(PaystackAPIError::Validation(msg)) if msg.contains("public key"))
+        );
+
+        env::set_var(PAYSTACK_SECRET_KEY_VAR, "sk_test_xxxxxxxxxxxx");
+        let present = PaystackClient::<ReqwestClient>::from_env();
+        assert!(present.is_ok());
+
+        env::remove_var(PAYSTACK_SECRET_KEY_VAR);
+    }
 }
@@ -2,7 +2,7 @@
 //! THe Apple Pay API allows you register your application's top-level domain or subdomain.
 
 use super::PAYSTACK_BASE_URL;
-use crate::{ApplePayResponseData, HttpClient, PaystackAPIError, PaystackResult};
+use crate::{ApplePayResponseData, HttpClient, PaystackAPIError, PaystackResult, Response};
 use serde_json::json;
 use std::{marker::PhantomData, sync::Arc};
 
@@ -14,6 +14,8 @@ pub struct ApplePayEndpoints<T: HttpClient + Default> {
     base_url: String,
     /// Http client for the route
     http: Arc<T>,
+    /// Whether the raw JSON body is kept on each response
+    keep_raw: bool,
 }
 
 impl<T: HttpClient + Default> ApplePayEndpoints<T> {
@@ -32,9 +34,16 @@ impl<T: HttpClient + Default> ApplePayEndpoints<T> {
             key: key.to_string(),
             base_url,
             http,
+            keep_raw: false,
         }
     }
 
+    /// Keeps the raw JSON body of each response in `Response::raw`, see `PaystackClient::with_keep_raw`.
+    pub fn with_keep_raw(mut self, keep_raw: bool) -> Self {
+        self.keep_raw = keep_raw;
+        self
+    }
+
     /// Register a top-level domain or subdomain for your Apple Pay integration.
     ///
     /// # Arguments
@@ -55,9 +64,9 @@ impl<T: HttpClient + Default> ApplePayEndpoints<T> {
             .http
             .post(url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::ApplePay(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::ApplePay))?;
 
-        let parsed_response = serde_json::from_str(&response)
+        let parsed_response = Response::parse(&response, self.keep_raw)
             .map_err(|e| PaystackAPIError::ApplePay(e.to_string()))?;
 
         Ok(parsed_response)
@@ -74,9 +83,9 @@ impl<T: HttpClient + Default> ApplePayEndpoints<T> {
             .http
             .get(url, &self.key, None)
             .await
-            .map_err(|e| PaystackAPIError::ApplePay(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::ApplePay))?;
 
-        let parsed_response = serde_json::from_str(&response)
+        let parsed_response = Response::parse(&response, self.keep_raw)
             .map_err(|e| PaystackAPIError::ApplePay(e.to_string()))?;
 
         Ok(parsed_response)
@@ -102,9 +111,9 @@ impl<T: HttpClient + Default> ApplePayEndpoints<T> {
             .http
             .delete(url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::ApplePay(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::ApplePay))?;
 
-        let parsed_response = serde_json::from_str(&response)
+        let parsed_response = Response::parse(&response, self.keep_raw)
             .map_err(|e| PaystackAPIError::ApplePay(e.to_string()))?;
 
         Ok(parsed_response)
@@ -2,10 +2,12 @@
 //! =========
 //! Thse Customers API allows you to create and maange customers on your integration
 
+use super::pager::{for_each_page, PAGE_SIZE};
 use super::PAYSTACK_BASE_URL;
 use crate::{
-    CreateCustomerRequest, CustomerResponseData, HttpClient, PaystackAPIError, PaystackResult,
-    Response, RiskAction, UpdateCustomerRequest, ValidateCustomerRequest,
+    CountResponse, CreateCustomerRequest, CustomerResponseData, CustomerSummary, HttpClient,
+    PaystackAPIError, PaystackResult, Response, RiskAction, TransactionStatus,
+    TransactionStatusData, UpdateCustomerRequest, ValidateCustomerRequest,
 };
 use serde_json::json;
 use std::{marker::PhantomData, sync::Arc};
@@ -19,6 +21,8 @@ pub struct CustomersEndpoints<T: HttpClient + Default> {
     base_url: String,
     /// Http client for the route
     http: Arc<T>,
+    /// Whether the raw JSON body is kept on each response
+    keep_raw: bool,
 }
 
 impl<T: HttpClient + Default> CustomersEndpoints<T> {
@@ -36,9 +40,16 @@ impl<T: HttpClient + Default> CustomersEndpoints<T> {
             key: key.to_string(),
             base_url,
             http,
+            keep_raw: false,
         }
     }
 
+    /// Keeps the raw JSON body of each response in `Response::raw`, see `PaystackClient::with_keep_raw`.
+    pub fn with_keep_raw(mut self, keep_raw: bool) -> Self {
+        self.keep_raw = keep_raw;
+        self
+    }
+
     /// Create customer on your integration
     ///
     /// # Arguments
@@ -59,10 +70,11 @@ impl<T: HttpClient + Default> CustomersEndpoints<T> {
             .http
             .post(url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::Customer(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Customer))?;
 
-        let parsed_response: Response<CustomerResponseData> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::Customer(e.to_string()))?;
+        let parsed_response: Response<CustomerResponseData> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Customer(e.to_string()))?;
 
         Ok(parsed_response)
     }
@@ -90,14 +102,32 @@ impl<T: HttpClient + Default> CustomersEndpoints<T> {
             .http
             .get(url, &self.key, Some(&query))
             .await
-            .map_err(|e| PaystackAPIError::Customer(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Customer))?;
 
-        let parsed_response: Response<Vec<CustomerResponseData>> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::Customer(e.to_string()))?;
+        let parsed_response: Response<Vec<CustomerResponseData>> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Customer(e.to_string()))?;
 
         Ok(parsed_response)
     }
 
+    /// Counts the customers available on your integration, without fetching them
+    ///
+    /// # Returns
+    /// A Result containing the total number of customers or an error
+    pub async fn count_customers(&self) -> PaystackResult<u64> {
+        let url = &self.base_url;
+        let query = vec![("perPage", "1")];
+
+        let response = self
+            .http
+            .get(url, &self.key, Some(&query))
+            .await
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Customer))?;
+
+        CountResponse::parse(&response).map_err(PaystackAPIError::Customer)
+    }
+
     /// Gets details of a customer on your integration
     ///
     /// # Arguments
@@ -115,14 +145,140 @@ impl<T: HttpClient + Default> CustomersEndpoints<T> {
             .http
             .get(&url, &self.key, None)
             .await
-            .map_err(|e| PaystackAPIError::Customer(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Customer))?;
 
-        let parsed_response: Response<CustomerResponseData> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::Customer(e.to_string()))?;
+        let parsed_response: Response<CustomerResponseData> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Customer(e.to_string()))?;
 
         Ok(parsed_response)
     }
 
+    /// Computes the number of successful transactions of a customer and their total spend in each currency.
+    ///
+    /// Paystack has no endpoint for this, so every successful transaction of the customer is fetched,
+    /// 100 at a time. Use the date range to limit the scan.
+    /// If Paystack returns a transaction of another customer or one that was not successful,
+    /// the `customer` or `status` filter was not applied and a `FilterNotHonoured` error is returned
+    /// instead of a wrong total.
+    ///
+    /// # Arguments
+    /// * `customer` - ID of the customer
+    /// * `from` - Optional start date of the range e.g. `2024-01-01T00:00:00.000Z`
+    /// * `to` - Optional end date of the range e.g. `2024-12-31T23:59:59.000Z`
+    ///
+    /// # Returns
+    /// A Result containing the summary of the customer's transactions or an error
+    pub async fn summary(
+        &self,
+        customer: u64,
+        from: Option<&str>,
+        to: Option<&str>,
+    ) -> PaystackResult<CustomerSummary> {
+        let url = &format!("{PAYSTACK_BASE_URL}/transaction");
+        let customer_id = customer;
+        let customer = &customer.to_string();
+        let per_page = &PAGE_SIZE.to_string();
+        let mut summary = CustomerSummary::default();
+
+        let fetch_page = move |page: u32| async move {
+            let page = page.to_string();
+            let mut query = vec![
+                ("customer", customer.as_str()),
+                ("status", "success"),
+                ("perPage", per_page.as_str()),
+                ("page", page.as_str()),
+            ];
+            if let Some(from) = from {
+                query.push(("from", from));
+            }
+            if let Some(to) = to {
+                query.push(("to", to));
+            }
+
+            let response = self
+                .http
+                .get(url, &self.key, Some(&query))
+                .await
+                .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Customer))?;
+
+            let parsed_response: Response<Vec<TransactionStatusData>> =
+                Response::parse(&response, self.keep_raw)
+                    .map_err(|e| PaystackAPIError::Customer(e.to_string()))?;
+            Ok(parsed_response)
+        };
+
+        let last_page = for_each_page(fetch_page, |transactions| {
+            for transaction in transactions {
+                if transaction.customer.id != customer_id
+                    || transaction.status != TransactionStatus::Success
+                {
+                    return Err(PaystackAPIError::FilterNotHonoured(format!(
+                        "listed successful transactions of customer {customer_id} but got \
+                         transaction {} of customer {} with status {}",
+                        transaction.id, transaction.customer.id, transaction.status
+                    )));
+                }
+                summary.transaction_count += 1;
+                *summary.total_spend.entry(transaction.currency).or_default() +=
+                    u64::from(transaction.amount);
+            }
+            Ok(true)
+        })
+        .await?;
+
+        Ok(last_page.with_data(summary))
+    }
+
+    /// Finds the customers whose email address is on the given domain, e.g. every `@acme.com` customer.
+    ///
+    /// Paystack cannot filter customers by email, so this is a client-side scan:
+    /// every customer of the integration is fetched, 100 at a time, and matched locally.
+    /// The domain is compared without regard to case, and may be given with or without the `@`.
+    ///
+    /// # Arguments
+    /// * `domain` - The email domain to match e.g. `acme.com`
+    ///
+    /// # Returns
+    /// A Result containing the customers on the domain or an error
+    pub async fn find_by_email_domain(
+        &self,
+        domain: &str,
+    ) -> PaystackResult<Vec<CustomerResponseData>> {
+        let url = &self.base_url;
+        let suffix = format!("@{}", domain.trim_start_matches('@').to_lowercase());
+        let per_page = &PAGE_SIZE.to_string();
+        let mut customers = Vec::new();
+
+        let fetch_page = move |page: u32| async move {
+            let page = page.to_string();
+            let query = vec![("perPage", per_page.as_str()), ("page", page.as_str())];
+
+            let response = self
+                .http
+                .get(url, &self.key, Some(&query))
+                .await
+                .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Customer))?;
+
+            let parsed_response: Response<Vec<CustomerResponseData>> =
+                Response::parse(&response, self.keep_raw)
+                    .map_err(|e| PaystackAPIError::Customer(e.to_string()))?;
+            Ok(parsed_response)
+        };
+
+        let last_page = for_each_page(fetch_page, |page_customers| {
+            customers.extend(
+                page_customers
+                    .into_iter()
+                    .filter(|customer| customer.email.to_lowercase().ends_with(&suffix)),
+            );
+            Ok(true)
+        })
+        .await?;
+
+        Ok(last_page.with_data(customers))
+    }
+
     /// Updates a customer's details on your integration
     ///
     /// # Arguments
@@ -145,16 +301,22 @@ impl<T: HttpClient + Default> CustomersEndpoints<T> {
             .http
             .put(&url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::Customer(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Customer))?;
 
-        let parsed_response: Response<CustomerResponseData> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::Customer(e.to_string()))?;
+        let parsed_response: Response<CustomerResponseData> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Customer(e.to_string()))?;
 
         Ok(parsed_response)
     }
 
     /// Validates a customer's identity
     ///
+    /// Paystack identifies the customer in the background. The result is sent in a
+    /// `customeridentification.success` or `customeridentification.failed` webhook event,
+    /// read with `WebhookEvent::customer_identification` and matched by `customer_code`.
+    /// Use `is_identified` to check the customer without waiting for the event.
+    ///
     /// # Arguments
     /// * `customer_code` - Email or customer code of customer to be identified
     /// * `customer_validation_request` - The data to validate the customer with.
@@ -175,14 +337,41 @@ impl<T: HttpClient + Default> CustomersEndpoints<T> {
             .http
             .post(&url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::Customer(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Customer))?;
 
-        let parsed_response: Response<PhantomData<String>> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::Customer(e.to_string()))?;
+        let parsed_response: Response<PhantomData<String>> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Customer(e.to_string()))?;
 
         Ok(parsed_response)
     }
 
+    /// Checks whether a customer has been identified, e.g. after `validate_customer`.
+    ///
+    /// # Arguments
+    /// * `customer_code` - Email or customer code of the customer
+    ///
+    /// # Returns
+    /// A Result containing `true` if the customer has been identified, or an error
+    pub async fn is_identified(&self, customer_code: &str) -> PaystackResult<bool> {
+        let response = self.fetch_customer(customer_code.to_string()).await?;
+        let identified = response
+            .data
+            .as_ref()
+            .and_then(|customer| customer.identified)
+            .unwrap_or(false);
+
+        Ok(Response {
+            status: response.status,
+            message: response.message,
+            data: Some(identified),
+            meta: response.meta,
+            response_type: response.response_type,
+            code: response.code,
+            raw: response.raw,
+        })
+    }
+
     /// Whitelists or blacklists a customer on your integration
     ///
     /// # Arguments
@@ -206,10 +395,11 @@ impl<T: HttpClient + Default> CustomersEndpoints<T> {
             .http
             .post(&url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::Customer(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Customer))?;
 
-        let parsed_response: Response<CustomerResponseData> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::Customer(e.to_string()))?;
+        let parsed_response: Response<CustomerResponseData> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Customer(e.to_string()))?;
 
         Ok(parsed_response)
     }
@@ -234,10 +424,11 @@ impl<T: HttpClient + Default> CustomersEndpoints<T> {
             .http
             .post(&url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::Customer(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Customer))?;
 
-        let parsed_response: Response<PhantomData<String>> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::Customer(e.to_string()))?;
+        let parsed_response: Response<PhantomData<String>> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Customer(e.to_string()))?;
 
         Ok(parsed_response)
     }
@@ -9,13 +9,29 @@ use crate::{
     SplitDedicatedAccountTransactionRequest,
 };
 use serde_json::json;
-use std::{marker::PhantomData, sync::Arc};
+use std::{
+    marker::PhantomData,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+/// How long the bank providers are cached by default, one hour.
+pub const DEFAULT_PROVIDER_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// The bank providers and when they were fetched
+type CachedProviders = Option<(Instant, Vec<BankProviderData>)>;
 
 #[derive(Debug, Clone)]
 pub struct DedicatedVirtualAccountEndpoints<T: HttpClient + Default> {
     key: String,
     base_url: String,
     http: Arc<T>,
+    /// Whether the raw JSON body is kept on each response
+    keep_raw: bool,
+    /// The supported bank providers, fetched the first time a preferred bank is validated
+    providers: Arc<RwLock<CachedProviders>>,
+    /// How long the bank providers are cached before they are fetched again
+    provider_cache_ttl: Duration,
 }
 
 /// Handles operations related to dedicated virtual accounts in the Paystack API
@@ -34,11 +50,33 @@ impl<T: HttpClient + Default> DedicatedVirtualAccountEndpoints<T> {
             key: key.to_string(),
             base_url,
             http,
+            keep_raw: false,
+            providers: Arc::new(RwLock::new(None)),
+            provider_cache_ttl: DEFAULT_PROVIDER_CACHE_TTL,
         }
     }
 
+    /// Keeps the raw JSON body of each response in `Response::raw`, see `PaystackClient::with_keep_raw`.
+    pub fn with_keep_raw(mut self, keep_raw: bool) -> Self {
+        self.keep_raw = keep_raw;
+        self
+    }
+
+    /// Sets how long the bank providers used to validate preferred banks are cached.
+    /// Defaults to `DEFAULT_PROVIDER_CACHE_TTL`.
+    ///
+    /// # Arguments
+    /// * `ttl` - How long the providers are reused before they are fetched again
+    pub fn with_provider_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.provider_cache_ttl = ttl;
+        self
+    }
+
     /// Create a dedicated virtual account for an existing customer.
     ///
+    /// If a `preferred_bank` is set, it is checked against the supported bank providers first
+    /// and a `Validation` error listing the valid slugs is returned if it is not one of them.
+    ///
     /// # Arguments
     /// * `create_dedicated_virtual_account_request` - The request data to create the dedicated virtual account for the customer.
     ///   It should be created with the `DedicatedVirtualAccountRequstBuilder` struct.
@@ -49,18 +87,19 @@ impl<T: HttpClient + Default> DedicatedVirtualAccountEndpoints<T> {
         &self,
         create_dedicated_virtual_account_request: DedicatedVirtualAccountRequest,
     ) -> PaystackResult<DedicatedVirtualAccountResponseData> {
+        self.validate_preferred_bank(&create_dedicated_virtual_account_request.preferred_bank)
+            .await?;
+
         let url = &self.base_url;
         let body = serde_json::to_value(create_dedicated_virtual_account_request)
             .map_err(|e| PaystackAPIError::DedicatedVirtualAccount(e.to_string()))?;
 
-        let response = self
-            .http
-            .post(url, &self.key, &body)
-            .await
-            .map_err(|e| PaystackAPIError::DedicatedVirtualAccount(e.to_string()))?;
+        let response = self.http.post(url, &self.key, &body).await.map_err(|e| {
+            PaystackAPIError::from_http(e, PaystackAPIError::DedicatedVirtualAccount)
+        })?;
 
         let parsed_response: Response<DedicatedVirtualAccountResponseData> =
-            serde_json::from_str(&response)
+            Response::parse(&response, self.keep_raw)
                 .map_err(|e| PaystackAPIError::DedicatedVirtualAccount(e.to_string()))?;
 
         Ok(parsed_response)
@@ -68,6 +107,9 @@ impl<T: HttpClient + Default> DedicatedVirtualAccountEndpoints<T> {
 
     /// Creates a customer, validates them and assigns a dedicated virtual account.
     ///
+    /// If a `preferred_bank` is set, it is checked against the supported bank providers first
+    /// and a `Validation` error listing the valid slugs is returned if it is not one of them.
+    ///
     /// # Arguments
     /// * `assign_dedicated_virtual_account_request` - The request data to assign the dedicated virtual account.
     ///   It should be created with the `DedicatedVirtualAccountRequestBuilder`
@@ -78,18 +120,20 @@ impl<T: HttpClient + Default> DedicatedVirtualAccountEndpoints<T> {
         &self,
         assign_dedicated_virtual_account_request: DedicatedVirtualAccountRequest,
     ) -> PaystackResult<PhantomData<String>> {
+        self.validate_preferred_bank(&assign_dedicated_virtual_account_request.preferred_bank)
+            .await?;
+
         let url = &self.base_url;
         let body = serde_json::to_value(assign_dedicated_virtual_account_request)
             .map_err(|e| PaystackAPIError::DedicatedVirtualAccount(e.to_string()))?;
 
-        let response = self
-            .http
-            .post(url, &self.key, &body)
-            .await
-            .map_err(|e| PaystackAPIError::DedicatedVirtualAccount(e.to_string()))?;
+        let response = self.http.post(url, &self.key, &body).await.map_err(|e| {
+            PaystackAPIError::from_http(e, PaystackAPIError::DedicatedVirtualAccount)
+        })?;
 
-        let parsed_response: Response<PhantomData<String>> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::DedicatedVirtualAccount(e.to_string()))?;
+        let parsed_response: Response<PhantomData<String>> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::DedicatedVirtualAccount(e.to_string()))?;
 
         Ok(parsed_response)
     }
@@ -133,10 +177,12 @@ impl<T: HttpClient + Default> DedicatedVirtualAccountEndpoints<T> {
             .http
             .get(url, &self.key, Some(&query))
             .await
-            .map_err(|e| PaystackAPIError::DedicatedVirtualAccount(e.to_string()))?;
+            .map_err(|e| {
+                PaystackAPIError::from_http(e, PaystackAPIError::DedicatedVirtualAccount)
+            })?;
 
         let parsed_response: Response<Vec<DedicatedVirtualAccountResponseData>> =
-            serde_json::from_str(&response)
+            Response::parse(&response, self.keep_raw)
                 .map_err(|e| PaystackAPIError::DedicatedVirtualAccount(e.to_string()))?;
 
         Ok(parsed_response)
@@ -155,14 +201,12 @@ impl<T: HttpClient + Default> DedicatedVirtualAccountEndpoints<T> {
     ) -> PaystackResult<DedicatedVirtualAccountResponseData> {
         let url = format!("{}/{}", self.base_url, dedicated_account_id);
 
-        let response = self
-            .http
-            .get(&url, &self.key, None)
-            .await
-            .map_err(|e| PaystackAPIError::DedicatedVirtualAccount(e.to_string()))?;
+        let response = self.http.get(&url, &self.key, None).await.map_err(|e| {
+            PaystackAPIError::from_http(e, PaystackAPIError::DedicatedVirtualAccount)
+        })?;
 
         let parsed_response: Response<DedicatedVirtualAccountResponseData> =
-            serde_json::from_str(&response)
+            Response::parse(&response, self.keep_raw)
                 .map_err(|e| PaystackAPIError::DedicatedVirtualAccount(e.to_string()))?;
 
         Ok(parsed_response)
@@ -199,10 +243,13 @@ impl<T: HttpClient + Default> DedicatedVirtualAccountEndpoints<T> {
             .http
             .get(&url, &self.key, Some(&query))
             .await
-            .map_err(|e| PaystackAPIError::DedicatedVirtualAccount(e.to_string()))?;
+            .map_err(|e| {
+                PaystackAPIError::from_http(e, PaystackAPIError::DedicatedVirtualAccount)
+            })?;
 
-        let parsed_response: Response<PhantomData<String>> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::DedicatedVirtualAccount(e.to_string()))?;
+        let parsed_response: Response<PhantomData<String>> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::DedicatedVirtualAccount(e.to_string()))?;
 
         Ok(parsed_response)
     }
@@ -225,10 +272,12 @@ impl<T: HttpClient + Default> DedicatedVirtualAccountEndpoints<T> {
             .http
             .delete(&url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::DedicatedVirtualAccount(e.to_string()))?;
+            .map_err(|e| {
+                PaystackAPIError::from_http(e, PaystackAPIError::DedicatedVirtualAccount)
+            })?;
 
         let parsed_response: Response<DedicatedVirtualAccountResponseData> =
-            serde_json::from_str(&response)
+            Response::parse(&response, self.keep_raw)
                 .map_err(|e| PaystackAPIError::DedicatedVirtualAccount(e.to_string()))?;
 
         Ok(parsed_response)
@@ -246,18 +295,16 @@ impl<T: HttpClient + Default> DedicatedVirtualAccountEndpoints<T> {
         &self,
         split_dedocated_account_transaction_request: SplitDedicatedAccountTransactionRequest,
     ) -> PaystackResult<DedicatedVirtualAccountResponseData> {
-        let url = &self.base_url;
+        let url = format!("{}/split", self.base_url);
         let body = serde_json::to_value(split_dedocated_account_transaction_request)
             .map_err(|e| PaystackAPIError::DedicatedVirtualAccount(e.to_string()))?;
 
-        let response = self
-            .http
-            .post(url, &self.key, &body)
-            .await
-            .map_err(|e| PaystackAPIError::DedicatedVirtualAccount(e.to_string()))?;
+        let response = self.http.post(&url, &self.key, &body).await.map_err(|e| {
+            PaystackAPIError::from_http(e, PaystackAPIError::DedicatedVirtualAccount)
+        })?;
 
         let parsed_response: Response<DedicatedVirtualAccountResponseData> =
-            serde_json::from_str(&response)
+            Response::parse(&response, self.keep_raw)
                 .map_err(|e| PaystackAPIError::DedicatedVirtualAccount(e.to_string()))?;
 
         Ok(parsed_response)
@@ -274,19 +321,21 @@ impl<T: HttpClient + Default> DedicatedVirtualAccountEndpoints<T> {
         &self,
         account_number: String,
     ) -> PaystackResult<DedicatedVirtualAccountResponseData> {
-        let url = &self.base_url;
+        let url = format!("{}/split", self.base_url);
         let body = json!({
             "account_number": account_number
         });
 
         let response = self
             .http
-            .delete(url, &self.key, &body)
+            .delete(&url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::DedicatedVirtualAccount(e.to_string()))?;
+            .map_err(|e| {
+                PaystackAPIError::from_http(e, PaystackAPIError::DedicatedVirtualAccount)
+            })?;
 
         let parsed_response: Response<DedicatedVirtualAccountResponseData> =
-            serde_json::from_str(&response)
+            Response::parse(&response, self.keep_raw)
                 .map_err(|e| PaystackAPIError::DedicatedVirtualAccount(e.to_string()))?;
 
         Ok(parsed_response)
@@ -302,15 +351,95 @@ impl<T: HttpClient + Default> DedicatedVirtualAccountEndpoints<T> {
     pub async fn fetch_bank_providers(&self) -> PaystackResult<Vec<BankProviderData>> {
         let url = format!("{}/available_providers", self.base_url);
 
-        let response = self
-            .http
-            .get(&url, &self.key, None)
-            .await
-            .map_err(|e| PaystackAPIError::DedicatedVirtualAccount(e.to_string()))?;
+        let response = self.http.get(&url, &self.key, None).await.map_err(|e| {
+            PaystackAPIError::from_http(e, PaystackAPIError::DedicatedVirtualAccount)
+        })?;
 
-        let parsed_response: Response<Vec<BankProviderData>> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::DedicatedVirtualAccount(e.to_string()))?;
+        let parsed_response: Response<Vec<BankProviderData>> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::DedicatedVirtualAccount(e.to_string()))?;
 
         Ok(parsed_response)
     }
+
+    /// Gets the supported bank providers, from the cache if they were fetched within the cache TTL.
+    /// Use it e.g. to build a list of preferred banks to choose from.
+    ///
+    /// # Returns
+    /// A Result containing the bank providers or an error
+    pub async fn providers(&self) -> Result<Vec<BankProviderData>, PaystackAPIError> {
+        match self.cached_providers() {
+            Some(providers) => Ok(providers),
+            None => self.refresh_providers().await,
+        }
+    }
+
+    /// The cached bank providers, if they were fetched within the cache TTL.
+    pub fn cached_providers(&self) -> Option<Vec<BankProviderData>> {
+        match &*self.providers.read().unwrap() {
+            Some((fetched_at, providers)) if fetched_at.elapsed() < self.provider_cache_ttl => {
+                Some(providers.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Fetches the supported bank providers and replaces the cached ones, e.g. after Paystack adds a provider.
+    ///
+    /// An unsuccessful response or one without providers is not cached, so it does not
+    /// make every preferred bank invalid until the cache expires.
+    ///
+    /// # Returns
+    /// A Result containing the bank providers, or an error if they could not be fetched
+    /// or Paystack returned none
+    pub async fn refresh_providers(&self) -> Result<Vec<BankProviderData>, PaystackAPIError> {
+        let response = self.fetch_bank_providers().await?;
+        let providers = match response.data {
+            Some(providers) if response.status && !providers.is_empty() => providers,
+            _ => {
+                return Err(PaystackAPIError::DedicatedVirtualAccount(format!(
+                    "no bank providers returned: {}",
+                    response.message
+                )))
+            }
+        };
+        *self.providers.write().unwrap() = Some((Instant::now(), providers.clone()));
+
+        Ok(providers)
+    }
+
+    /// Checks that the preferred bank is one of the supported bank providers.
+    /// The providers are cached, see `providers`. If the preferred bank is not in the cache,
+    /// the providers are fetched again once in case Paystack added it since.
+    async fn validate_preferred_bank(
+        &self,
+        preferred_bank: &Option<String>,
+    ) -> Result<(), PaystackAPIError> {
+        let Some(preferred_bank) = preferred_bank else {
+            return Ok(());
+        };
+        let is_supported = |providers: &[BankProviderData]| {
+            providers
+                .iter()
+                .any(|provider| &provider.provider_slug == preferred_bank)
+        };
+
+        let providers = match self.cached_providers() {
+            Some(cached) if is_supported(&cached) => return Ok(()),
+            _ => self.refresh_providers().await?,
+        };
+
+        if is_supported(&providers) {
+            Ok(())
+        } else {
+            let slugs: Vec<String> = providers
+                .into_iter()
+                .map(|provider| provider.provider_slug)
+                .collect();
+            Err(PaystackAPIError::Validation(format!(
+                "{preferred_bank} is not a supported preferred bank, use one of: {}",
+                slugs.join(", ")
+            )))
+        }
+    }
 }
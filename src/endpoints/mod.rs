@@ -10,6 +10,8 @@ pub mod transaction;
 pub mod transaction_split;
 pub mod virtual_terminal;
 
+mod pager;
+
 // public re-export
 pub use apple_pay::*;
 pub use customers::*;
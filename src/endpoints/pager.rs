@@ -0,0 +1,135 @@
+//! Pager
+//! =====
+//! Goes through every page of a list, for the methods that scan a whole list, e.g. `CustomersEndpoints::summary`.
+
+use crate::{PaystackAPIError, PaystackResult, Response};
+use std::future::Future;
+
+/// The number of records requested per page when scanning a list.
+pub(crate) const PAGE_SIZE: u32 = 100;
+
+/// Fetches the pages of a list one after the other, starting from the first.
+///
+/// `fetch_page` is called with the number of the page to fetch, and `on_page` with its records.
+/// `on_page` returns `false` to stop early, e.g. once a match is found. Paging stops after the
+/// last page reported by `meta.page_count`, or at the first empty page if the response has no
+/// page count, so a page size capped by Paystack does not end the scan early.
+///
+/// # Returns
+/// A Result containing the response of the last page fetched, without its data and meta,
+/// or the first error of `fetch_page` or `on_page`
+pub(crate) async fn for_each_page<R, F, Fut, P>(
+    mut fetch_page: F,
+    mut on_page: P,
+) -> PaystackResult<()>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = PaystackResult<Vec<R>>>,
+    P: FnMut(Vec<R>) -> Result<bool, PaystackAPIError>,
+{
+    let mut page = 1;
+
+    loop {
+        let response = fetch_page(page).await?;
+        let page_count = response.meta.as_ref().and_then(|meta| meta.page_count);
+        let records = response.data.unwrap_or_default();
+        let is_empty = records.is_empty();
+        let keep_going = on_page(records)?;
+        let is_last = match page_count {
+            Some(page_count) => page >= u32::from(page_count),
+            None => is_empty,
+        };
+
+        if !keep_going || is_last {
+            return Ok(Response {
+                status: response.status,
+                message: response.message,
+                data: None,
+                meta: None,
+                response_type: response.response_type,
+                code: response.code,
+                raw: response.raw,
+            });
+        }
+        page += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Meta;
+
+    fn page(records: Vec<u32>, page_count: Option<u16>) -> PaystackResult<Vec<u32>> {
+        Ok(Response {
+            status: true,
+            message: "Records retrieved".to_string(),
+            data: Some(records),
+            meta: page_count.map(|page_count| Meta {
+                page_count: Some(page_count),
+                ..Meta::default()
+            }),
+            response_type: None,
+            code: None,
+            raw: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn stops_after_the_last_page_count_even_with_short_pages() {
+        let mut requested = vec![];
+        let mut records = vec![];
+
+        for_each_page(
+            |n| {
+                requested.push(n);
+                // Paystack capped the page size at 2, below `PAGE_SIZE`
+                std::future::ready(page(vec![n * 10, n * 10 + 1], Some(3)))
+            },
+            |page| {
+                records.extend(page);
+                Ok(true)
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(requested, vec![1, 2, 3]);
+        assert_eq!(records, vec![10, 11, 20, 21, 30, 31]);
+    }
+
+    #[tokio::test]
+    async fn stops_at_the_first_empty_page_without_page_count() {
+        let mut requested = vec![];
+
+        for_each_page(
+            |n| {
+                requested.push(n);
+                let records = if n < 3 { vec![n] } else { vec![] };
+                std::future::ready(page(records, None))
+            },
+            |_| Ok(true),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(requested, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn stops_when_on_page_returns_false() {
+        let mut requested = vec![];
+
+        for_each_page(
+            |n| {
+                requested.push(n);
+                std::future::ready(page(vec![n], Some(5)))
+            },
+            |page| Ok(!page.contains(&2)),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(requested, vec![1, 2]);
+    }
+}
@@ -13,6 +13,8 @@ pub struct PlansEndpoints<T: HttpClient + Default> {
     base_url: String,
     /// Http client for the route
     http: Arc<T>,
+    /// Whether the raw JSON body is kept on each response
+    keep_raw: bool,
 }
 
 /// Create a new `PlansEndpoints<T>` instance
@@ -30,9 +32,16 @@ impl<T: HttpClient + Default> PlansEndpoints<T> {
             key: key.to_string(),
             base_url,
             http,
+            keep_raw: false,
         }
     }
 
+    /// Keeps the raw JSON body of each response in `Response::raw`, see `PaystackClient::with_keep_raw`.
+    pub fn with_keep_raw(mut self, keep_raw: bool) -> Self {
+        self.keep_raw = keep_raw;
+        self
+    }
+
     /// Create a plan on your integration
     ///
     /// # Arguments
@@ -50,10 +59,10 @@ impl<T: HttpClient + Default> PlansEndpoints<T> {
             .http
             .post(url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::Plan(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Plan))?;
 
-        let parsed_response: Response<PlanResponseData> =
-            serde_json::from_str(&response).map_err(|e| PaystackAPIError::Plan(e.to_string()))?;
+        let parsed_response: Response<PlanResponseData> = Response::parse(&response, self.keep_raw)
+            .map_err(|e| PaystackAPIError::Plan(e.to_string()))?;
 
         Ok(parsed_response)
     }
@@ -105,10 +114,11 @@ impl<T: HttpClient + Default> PlansEndpoints<T> {
             .http
             .get(url, &self.key, Some(&query))
             .await
-            .map_err(|e| PaystackAPIError::Plan(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Plan))?;
 
         let parsed_response: Response<Vec<PlanResponseData>> =
-            serde_json::from_str(&response).map_err(|e| PaystackAPIError::Plan(e.to_string()))?;
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Plan(e.to_string()))?;
 
         Ok(parsed_response)
     }
@@ -127,10 +137,10 @@ impl<T: HttpClient + Default> PlansEndpoints<T> {
             .http
             .get(&url, &self.key, None)
             .await
-            .map_err(|e| PaystackAPIError::Plan(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Plan))?;
 
-        let parsed_response: Response<PlanResponseData> =
-            serde_json::from_str(&response).map_err(|e| PaystackAPIError::Plan(e.to_string()))?;
+        let parsed_response: Response<PlanResponseData> = Response::parse(&response, self.keep_raw)
+            .map_err(|e| PaystackAPIError::Plan(e.to_string()))?;
 
         Ok(parsed_response)
     }
@@ -157,10 +167,11 @@ impl<T: HttpClient + Default> PlansEndpoints<T> {
             .http
             .put(&url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::Plan(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Plan))?;
 
         let parsed_response: Response<PhantomData<String>> =
-            serde_json::from_str(&response).map_err(|e| PaystackAPIError::Plan(e.to_string()))?;
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Plan(e.to_string()))?;
 
         Ok(parsed_response)
     }
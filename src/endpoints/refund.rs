@@ -2,10 +2,11 @@
 //! =======
 //! The Refund route allows you to create and manage transaction refunds on your integration.
 
+use super::pager::{for_each_page, PAGE_SIZE};
 use super::PAYSTACK_BASE_URL;
 use crate::{
-    CreateRefundRequest, HttpClient, PaystackAPIError, PaystackResult, RefundData,
-    RetryRefundRequest, Response,
+    CountResponse, CreateRefundRequest, Currency, HttpClient, PaystackAPIError, PaystackResult,
+    RefundData, RefundTransactionCheck, RetryRefundRequest, Response,
 };
 use std::sync::Arc;
 
@@ -18,6 +19,10 @@ pub struct RefundEndpoints<T: HttpClient + Default> {
     base_url: String,
     /// Http client for the route
     http: Arc<T>,
+    /// Whether the raw JSON body is kept on each response
+    keep_raw: bool,
+    /// Currency of the refunds created without one
+    default_currency: Option<Currency>,
 }
 
 impl<T: HttpClient + Default> RefundEndpoints<T> {
@@ -35,11 +40,38 @@ impl<T: HttpClient + Default> RefundEndpoints<T> {
             key: key.to_string(),
             base_url,
             http,
+            keep_raw: false,
+            default_currency: None,
         }
     }
 
+    /// Keeps the raw JSON body of each response in `Response::raw`, see `PaystackClient::with_keep_raw`.
+    pub fn with_keep_raw(mut self, keep_raw: bool) -> Self {
+        self.keep_raw = keep_raw;
+        self
+    }
+
+    /// Sets the currency of the refunds created without a `currency`.
+    /// A currency set on a request is sent unchanged.
+    ///
+    /// # Arguments
+    /// * `currency` - The currency the refunds are made in, e.g. the only currency of the integration
+    pub fn with_default_currency(mut self, currency: Currency) -> Self {
+        self.default_currency = Some(currency);
+        self
+    }
+
     /// Initiate a refund on your integration
     ///
+    /// Paystack does not deduplicate refund requests, so retrying a call that timed out can refund
+    /// the customer twice. Check with `refund_exists` before retrying.
+    ///
+    /// Refunds must be made in the currency of the original transaction. If both the refund
+    /// `currency` and the `transaction_currency` are set and differ, a `Validation` error is
+    /// returned without sending the request. A refund without a `currency` is made in the
+    /// `transaction_currency` if it is set, or else in the currency set with
+    /// `with_default_currency`, if any.
+    ///
     /// # Arguments
     /// * `request` - The refund request body. Build with `CreateRefundRequestBuilder`.
     ///
@@ -47,8 +79,25 @@ impl<T: HttpClient + Default> RefundEndpoints<T> {
     /// A Result containing the refund data or an error
     pub async fn create_refund(
         &self,
-        request: CreateRefundRequest,
+        mut request: CreateRefundRequest,
     ) -> PaystackResult<RefundData> {
+        if request.currency.is_none() {
+            request.currency = match &request.transaction_currency {
+                Some(transaction_currency) => Some(transaction_currency.clone()),
+                None => self.default_currency.as_ref().map(Currency::to_string),
+            };
+        }
+
+        if let (Some(currency), Some(transaction_currency)) =
+            (&request.currency, &request.transaction_currency)
+        {
+            if !currency.eq_ignore_ascii_case(transaction_currency) {
+                return Err(PaystackAPIError::Validation(format!(
+                    "refund currency {currency} does not match the transaction currency {transaction_currency}"
+                )));
+            }
+        }
+
         let url = &self.base_url;
         let body = serde_json::to_value(request)
             .map_err(|e| PaystackAPIError::Refund(e.to_string()))?;
@@ -57,9 +106,9 @@ impl<T: HttpClient + Default> RefundEndpoints<T> {
             .http
             .post(url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::Refund(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Refund))?;
 
-        let parsed_response: Response<RefundData> = serde_json::from_str(&response)
+        let parsed_response: Response<RefundData> = Response::parse(&response, self.keep_raw)
             .map_err(|e| PaystackAPIError::Refund(e.to_string()))?;
 
         Ok(parsed_response)
@@ -86,9 +135,9 @@ impl<T: HttpClient + Default> RefundEndpoints<T> {
             .http
             .post(&url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::Refund(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Refund))?;
 
-        let parsed_response: Response<RefundData> = serde_json::from_str(&response)
+        let parsed_response: Response<RefundData> = Response::parse(&response, self.keep_raw)
             .map_err(|e| PaystackAPIError::Refund(e.to_string()))?;
 
         Ok(parsed_response)
@@ -146,14 +195,83 @@ impl<T: HttpClient + Default> RefundEndpoints<T> {
             .http
             .get(url, &self.key, if query.is_empty() { None } else { Some(&query) })
             .await
-            .map_err(|e| PaystackAPIError::Refund(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Refund))?;
 
-        let parsed_response: Response<Vec<RefundData>> = serde_json::from_str(&response)
+        let parsed_response: Response<Vec<RefundData>> = Response::parse(&response, self.keep_raw)
             .map_err(|e| PaystackAPIError::Refund(e.to_string()))?;
 
         Ok(parsed_response)
     }
 
+    /// Count the refunds available on your integration, without fetching them
+    ///
+    /// # Arguments
+    /// * `transaction` - Optional transaction ID or reference to count the refunds of
+    /// * `currency` - Optional currency to filter by
+    ///
+    /// # Returns
+    /// A Result containing the total number of matching refunds or an error
+    pub async fn count_refunds(
+        &self,
+        transaction: Option<&str>,
+        currency: Option<&str>,
+    ) -> PaystackResult<u64> {
+        let url = &self.base_url;
+
+        let mut query: Vec<(&str, &str)> = vec![("perPage", "1")];
+        if let Some(t) = transaction {
+            query.push(("transaction", t));
+        }
+        if let Some(c) = currency {
+            query.push(("currency", c));
+        }
+
+        let response = self
+            .http
+            .get(url, &self.key, Some(&query))
+            .await
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Refund))?;
+
+        CountResponse::parse(&response).map_err(PaystackAPIError::Refund)
+    }
+
+    /// Check if a refund of the given amount already exists for a transaction.
+    ///
+    /// Use this before retrying a failed or timed out `create_refund` call to avoid refunding twice.
+    /// A refund matches if its amount is exactly `amount` and its status is not `failed`,
+    /// since a failed refund did not return any money.
+    ///
+    /// If the list has refunds of another transaction, the `transaction` filter was not applied
+    /// and a `FilterNotHonoured` error is returned rather than matching them. Listed refunds only
+    /// carry the ID of their transaction, so the check is only complete for a transaction ID.
+    ///
+    /// # Arguments
+    /// * `transaction` - The transaction ID or reference the refund was made for
+    /// * `amount` - The refund amount in the subunit of the currency
+    ///
+    /// # Returns
+    /// A Result containing `true` if a matching refund exists, or an error
+    pub async fn refund_exists(&self, transaction: &str, amount: u64) -> PaystackResult<bool> {
+        let mut check = RefundTransactionCheck::new(transaction);
+        let mut exists = false;
+
+        let fetch_page = |page| {
+            self.list_refunds(Some(transaction), None, None, None, Some(PAGE_SIZE), Some(page))
+        };
+        let last_page = for_each_page(fetch_page, |refunds| {
+            for refund in &refunds {
+                check.check(refund)?;
+            }
+            exists = refunds
+                .iter()
+                .any(|refund| refund.amount == amount && refund.status != "failed");
+            Ok(!exists)
+        })
+        .await?;
+
+        Ok(last_page.with_data(exists))
+    }
+
     /// Get details of a refund on your integration
     ///
     /// # Arguments
@@ -168,9 +286,9 @@ impl<T: HttpClient + Default> RefundEndpoints<T> {
             .http
             .get(&url, &self.key, None)
             .await
-            .map_err(|e| PaystackAPIError::Refund(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Refund))?;
 
-        let parsed_response: Response<RefundData> = serde_json::from_str(&response)
+        let parsed_response: Response<RefundData> = Response::parse(&response, self.keep_raw)
             .map_err(|e| PaystackAPIError::Refund(e.to_string()))?;
 
         Ok(parsed_response)
@@ -5,8 +5,8 @@
 
 use super::PAYSTACK_BASE_URL;
 use crate::{
-    CreateSubaccountRequest, HttpClient, PaystackAPIError, PaystackResult, Response,
-    SubaccountsResponseData,
+    CreateSubaccountRequest, HttpClient, ListSubaccountsRequest, PaystackAPIError, PaystackResult,
+    Response, SubaccountsResponseData,
 };
 use std::sync::Arc;
 
@@ -19,6 +19,8 @@ pub struct SubaccountEndpoints<T: HttpClient + Default> {
     base_url: String,
     /// Http client for the route
     http: Arc<T>,
+    /// Whether the raw JSON body is kept on each response
+    keep_raw: bool,
 }
 
 impl<T: HttpClient + Default> SubaccountEndpoints<T> {
@@ -36,21 +38,34 @@ impl<T: HttpClient + Default> SubaccountEndpoints<T> {
             key: key.to_string(),
             base_url,
             http,
+            keep_raw: false,
         }
     }
 
+    /// Keeps the raw JSON body of each response in `Response::raw`, see `PaystackClient::with_keep_raw`.
+    pub fn with_keep_raw(mut self, keep_raw: bool) -> Self {
+        self.keep_raw = keep_raw;
+        self
+    }
+
     /// Create a subaccount on your integration
     ///
     /// # Arguments
     /// * `subaccount_request` - The request data to create the subaccount.
     ///   It should be created with the `CreateSubaccountRequestBuilder` struct.
     ///
+    /// The business name, settlement bank, account number and percentage charge
+    /// are required, and are checked before the request is sent.
+    ///
     /// # Returns
-    /// A Result containing the subaccount response data or an error
+    /// A Result containing the subaccount response data, with its `subaccount_code`,
+    /// or a validation error if a required field is not set
     pub async fn create_subaccount(
         &self,
         subaccount_request: CreateSubaccountRequest,
     ) -> PaystackResult<SubaccountsResponseData> {
+        subaccount_request.validate_for_create()?;
+
         let url = &self.base_url;
         let body = serde_json::to_value(subaccount_request)
             .map_err(|e| PaystackAPIError::Subaccount(e.to_string()))?;
@@ -59,10 +74,11 @@ impl<T: HttpClient + Default> SubaccountEndpoints<T> {
             .http
             .post(url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::Subaccount(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Subaccount))?;
 
-        let parsed_response: Response<SubaccountsResponseData> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::Subaccount(e.to_string()))?;
+        let parsed_response: Response<SubaccountsResponseData> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Subaccount(e.to_string()))?;
         Ok(parsed_response)
     }
 
@@ -89,10 +105,44 @@ impl<T: HttpClient + Default> SubaccountEndpoints<T> {
             .http
             .get(&url, &self.key, Some(&query))
             .await
-            .map_err(|e| PaystackAPIError::Subaccount(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Subaccount))?;
 
         let parsed_response: Response<Vec<SubaccountsResponseData>> =
-            serde_json::from_str(&response)
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Subaccount(e.to_string()))?;
+
+        Ok(parsed_response)
+    }
+
+    /// List subaccounts available on your integration, filtered by creation date and paged as requested.
+    ///
+    /// Only the filters that are set are sent, and the page is read from `Response::meta`,
+    /// e.g. to sync a vendor directory page by page.
+    ///
+    /// # Arguments
+    /// * `request` - The filters of the subaccounts to list.
+    ///   It should be created with the `ListSubaccountsRequestBuilder` struct.
+    ///
+    /// # Returns
+    /// A Result containing a vector of subaccount data or an error.
+    pub async fn list_subaccounts_with_filter(
+        &self,
+        request: ListSubaccountsRequest,
+    ) -> PaystackResult<Vec<SubaccountsResponseData>> {
+        let url = &self.base_url;
+
+        // Transform String to &str using iter
+        let query = request.to_query();
+        let query: Vec<(&str, &str)> = query.iter().map(|(k, v)| (*k, v.as_str())).collect();
+
+        let response = self
+            .http
+            .get(url, &self.key, Some(&query))
+            .await
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Subaccount))?;
+
+        let parsed_response: Response<Vec<SubaccountsResponseData>> =
+            Response::parse(&response, self.keep_raw)
                 .map_err(|e| PaystackAPIError::Subaccount(e.to_string()))?;
 
         Ok(parsed_response)
@@ -104,7 +154,8 @@ impl<T: HttpClient + Default> SubaccountEndpoints<T> {
     /// * `id_or_code` - The subaccount ID or code you want to fetch
     ///
     /// # Returns
-    /// A Result containing the details of the subaccount or an error.
+    /// A Result containing the details of the subaccount, including its settlement schedule,
+    /// settlement bank and whether it is active, or an error.
     pub async fn fetch_subaccount(
         &self,
         id_or_code: String,
@@ -115,10 +166,11 @@ impl<T: HttpClient + Default> SubaccountEndpoints<T> {
             .http
             .get(&url, &self.key, None)
             .await
-            .map_err(|e| PaystackAPIError::Subaccount(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Subaccount))?;
 
-        let parsed_response: Response<SubaccountsResponseData> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::Subaccount(e.to_string()))?;
+        let parsed_response: Response<SubaccountsResponseData> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Subaccount(e.to_string()))?;
 
         Ok(parsed_response)
     }
@@ -129,6 +181,8 @@ impl<T: HttpClient + Default> SubaccountEndpoints<T> {
     /// * `id_or_code` - Subaccount's ID or code
     /// * `update_request` - The request data to update the subaccount.
     ///   It should be created with the `CreateSubaccountRequestBuilder` struct.
+    ///   Only the fields that are set are changed, e.g. the settlement bank and account number
+    ///   of a vendor that changed their payout account, or `active` to deactivate the subaccount.
     ///
     /// # Returns
     /// A Result containing the updated subaccount response data or an error
@@ -145,10 +199,11 @@ impl<T: HttpClient + Default> SubaccountEndpoints<T> {
             .http
             .put(&url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::Subaccount(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Subaccount))?;
 
-        let parsed_response: Response<SubaccountsResponseData> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::Subaccount(e.to_string()))?;
+        let parsed_response: Response<SubaccountsResponseData> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Subaccount(e.to_string()))?;
 
         Ok(parsed_response)
     }
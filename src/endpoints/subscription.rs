@@ -4,10 +4,12 @@
 
 use serde_json::json;
 
+use super::pager::{for_each_page, PAGE_SIZE};
 use super::PAYSTACK_BASE_URL;
 use crate::{
-    CreateSubscriptionRequest, FetchSubscriptionRequest, HttpClient, PaystackAPIError,
-    PaystackResult, Response, Subscription, UpdateSubscriptionRequest,
+    CountResponse, CreateSubscriptionRequest, FetchSubscriptionRequest, HttpClient, ManageLinks,
+    ManagementLink, PaystackAPIError, PaystackResult, Response, Subscription, SubscriptionStatus,
+    UpdateSubscriptionRequest,
 };
 use std::sync::Arc;
 
@@ -20,6 +22,8 @@ pub struct SubscriptionEndpoints<T: HttpClient + Default> {
     base_url: String,
     /// Http client for the route
     http: Arc<T>,
+    /// Whether the raw JSON body is kept on each response
+    keep_raw: bool,
 }
 
 impl<T: HttpClient + Default> SubscriptionEndpoints<T> {
@@ -37,9 +41,16 @@ impl<T: HttpClient + Default> SubscriptionEndpoints<T> {
             key: key.to_string(),
             base_url,
             http,
+            keep_raw: false,
         }
     }
 
+    /// Keeps the raw JSON body of each response in `Response::raw`, see `PaystackClient::with_keep_raw`.
+    pub fn with_keep_raw(mut self, keep_raw: bool) -> Self {
+        self.keep_raw = keep_raw;
+        self
+    }
+
     /// Create a subscription in your integration
     ///
     /// # Arguments
@@ -60,9 +71,9 @@ impl<T: HttpClient + Default> SubscriptionEndpoints<T> {
             .http
             .post(&url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::Subscription(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Subscription))?;
 
-        let parsed_response: Response<Subscription> = serde_json::from_str(&response)
+        let parsed_response: Response<Subscription> = Response::parse(&response, self.keep_raw)
             .map_err(|e| PaystackAPIError::Subscription(e.to_string()))?;
         Ok(parsed_response)
     }
@@ -98,14 +109,45 @@ impl<T: HttpClient + Default> SubscriptionEndpoints<T> {
             .http
             .get(&url, &self.key, None)
             .await
-            .map_err(|e| PaystackAPIError::Subscription(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Subscription))?;
 
-        let parsed_response: Response<Vec<Subscription>> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::Subscription(e.to_string()))?;
+        let parsed_response: Response<Vec<Subscription>> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Subscription(e.to_string()))?;
 
         Ok(parsed_response)
     }
 
+    /// Count subscriptions, without fetching them
+    ///
+    /// # Arguments
+    /// * `customer` - Optional customer ID to count the subscriptions of
+    /// * `plan` - Optional plan ID to count the subscriptions of
+    ///
+    /// # Returns
+    /// A Result containing the total number of matching subscriptions or an error
+    pub async fn count_subscriptions(
+        &self,
+        customer: Option<u32>,
+        plan: Option<String>,
+    ) -> PaystackResult<u64> {
+        let mut url = format!("{}?perPage=1", self.base_url);
+        if let Some(customer) = customer {
+            url.push_str(&format!("&customer={}", customer));
+        }
+        if let Some(plan) = plan {
+            url.push_str(&format!("&plan={}", plan));
+        }
+
+        let response = self
+            .http
+            .get(&url, &self.key, None)
+            .await
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Subscription))?;
+
+        CountResponse::parse(&response).map_err(PaystackAPIError::Subscription)
+    }
+
     /// Gets details of a specific subscription
     ///
     /// # Arguments
@@ -123,9 +165,9 @@ impl<T: HttpClient + Default> SubscriptionEndpoints<T> {
             .http
             .get(&url, &self.key, None)
             .await
-            .map_err(|e| PaystackAPIError::Subscription(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Subscription))?;
 
-        let parsed_response: Response<Subscription> = serde_json::from_str(&response)
+        let parsed_response: Response<Subscription> = Response::parse(&response, self.keep_raw)
             .map_err(|e| PaystackAPIError::Subscription(e.to_string()))?;
 
         Ok(parsed_response)
@@ -150,10 +192,10 @@ impl<T: HttpClient + Default> SubscriptionEndpoints<T> {
             .http
             .post(&url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::Subscription(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Subscription))?;
 
-        let parsed_response: Response<()> =
-            serde_json::from_str(&response).map_err(|e| PaystackAPIError::Charge(e.to_string()))?;
+        let parsed_response: Response<()> = Response::parse(&response, self.keep_raw)
+            .map_err(|e| PaystackAPIError::Charge(e.to_string()))?;
 
         Ok(parsed_response)
     }
@@ -177,30 +219,221 @@ impl<T: HttpClient + Default> SubscriptionEndpoints<T> {
             .http
             .post(&url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::Subscription(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Subscription))?;
 
-        let parsed_response: Response<()> =
-            serde_json::from_str(&response).map_err(|e| PaystackAPIError::Charge(e.to_string()))?;
+        let parsed_response: Response<()> = Response::parse(&response, self.keep_raw)
+            .map_err(|e| PaystackAPIError::Charge(e.to_string()))?;
 
         Ok(parsed_response)
     }
 
+    /// Enable a subscription using only its code.
+    ///
+    /// The subscription is fetched first to get the `email_token` required to enable it.
+    /// Use `enable_subscription` if you already have the token.
+    ///
+    /// # Arguments
+    /// * `subscription_code` - The code of the subscription to enable
+    ///
+    /// # Returns
+    /// A Result containing the response or an error
+    pub async fn enable(&self, subscription_code: String) -> PaystackResult<()> {
+        let request = self.update_subscription_request(subscription_code).await?;
+        self.enable_subscription(request).await
+    }
+
+    /// Disable a subscription using only its code.
+    ///
+    /// The subscription is fetched first to get the `email_token` required to disable it.
+    /// Use `disable_subscription` if you already have the token.
+    ///
+    /// # Arguments
+    /// * `subscription_code` - The code of the subscription to disable
+    ///
+    /// # Returns
+    /// A Result containing the response or an error
+    pub async fn disable(&self, subscription_code: String) -> PaystackResult<()> {
+        let request = self.update_subscription_request(subscription_code).await?;
+        self.disable_subscription(request).await
+    }
+
+    /// Pause a subscription so it is not charged at the next billing cycle.
+    ///
+    /// Paystack has no separate pause action; this disables the subscription using its code.
+    /// The subscription status becomes `SubscriptionStatus::NonRenewing`: it stays active until the
+    /// end of the current cycle and is not renewed after that, unless it is resumed.
+    ///
+    /// # Arguments
+    /// * `subscription_code` - The code of the subscription to pause
+    ///
+    /// # Returns
+    /// A Result containing the response or an error
+    pub async fn pause_subscription(&self, subscription_code: String) -> PaystackResult<()> {
+        self.disable(subscription_code).await
+    }
+
+    /// Resume a paused subscription so it renews at the next billing cycle.
+    ///
+    /// This enables the subscription using its code, and its status returns to
+    /// `SubscriptionStatus::Active`.
+    ///
+    /// # Arguments
+    /// * `subscription_code` - The code of the subscription to resume
+    ///
+    /// # Returns
+    /// A Result containing the response or an error
+    pub async fn resume_subscription(&self, subscription_code: String) -> PaystackResult<()> {
+        self.enable(subscription_code).await
+    }
+
+    /// Cancel a subscription so it is not renewed anymore.
+    ///
+    /// Paystack has no separate cancel action: this disables the subscription using its code,
+    /// fetching the `email_token` as needed, which stops all future renewals. The subscription is
+    /// then fetched again to return its new status, usually `SubscriptionStatus::NonRenewing`
+    /// until the end of the current cycle, or `SubscriptionStatus::Cancelled`.
+    /// Use `disable_subscription` if you already have the token.
+    ///
+    /// # Arguments
+    /// * `subscription_code` - The code of the subscription to cancel
+    ///
+    /// # Returns
+    /// A Result containing the status of the subscription after it was cancelled, or an error
+    pub async fn cancel_subscription(
+        &self,
+        subscription_code: String,
+    ) -> PaystackResult<SubscriptionStatus> {
+        let disabled = self.disable(subscription_code.clone()).await?;
+        let subscription = self.fetch_subscription(subscription_code).await?;
+
+        Ok(Response {
+            status: disabled.status,
+            message: disabled.message,
+            data: subscription.data.map(|subscription| subscription.status),
+            meta: None,
+            response_type: disabled.response_type,
+            code: disabled.code,
+            raw: disabled.raw,
+        })
+    }
+
+    /// Fetches a subscription to build the request needed to enable or disable it.
+    async fn update_subscription_request(
+        &self,
+        subscription_code: String,
+    ) -> Result<UpdateSubscriptionRequest, PaystackAPIError> {
+        let subscription = self
+            .fetch_subscription(subscription_code.clone())
+            .await?
+            .data
+            .ok_or_else(|| {
+                PaystackAPIError::Subscription(format!(
+                    "no subscription data returned for {subscription_code}"
+                ))
+            })?;
+
+        Ok(UpdateSubscriptionRequest {
+            code: subscription.subscription_code,
+            token: subscription.email_token,
+        })
+    }
+
+    /// Disable every subscription of a customer that can still be charged, e.g. when offboarding them.
+    ///
+    /// This covers `Active` subscriptions and `Attention` subscriptions, whose last charge failed
+    /// but which are still retried. Subscriptions in any other status are left as they are.
+    /// Failing to disable one subscription does not stop the others from being disabled.
+    /// The response status is `false` if any of them could not be disabled.
+    ///
+    /// # Arguments
+    /// * `customer` - The ID of the customer whose subscriptions should be disabled
+    ///
+    /// # Returns
+    /// A Result containing the codes of the subscriptions that were disabled or an error
+    pub async fn disable_all_for_customer(&self, customer: u32) -> PaystackResult<Vec<String>> {
+        let mut chargeable = vec![];
+
+        let fetch_page = |page| {
+            self.list_subscriptions(FetchSubscriptionRequest {
+                page: Some(page),
+                per_page: Some(PAGE_SIZE),
+                customer: Some(customer),
+                plan: None,
+            })
+        };
+        for_each_page(fetch_page, |subscriptions| {
+            chargeable.extend(subscriptions.into_iter().filter(|s| {
+                matches!(
+                    s.status,
+                    SubscriptionStatus::Active | SubscriptionStatus::Attention
+                )
+            }));
+            Ok(true)
+        })
+        .await?;
+
+        let mut disabled = vec![];
+        let mut failed = vec![];
+        for subscription in &chargeable {
+            let request = UpdateSubscriptionRequest {
+                code: subscription.subscription_code.clone(),
+                token: subscription.email_token.clone(),
+            };
+            match self.disable_subscription(request).await {
+                Ok(res) if res.status => disabled.push(subscription.subscription_code.clone()),
+                Ok(res) => failed.push(format!(
+                    "{}: {}",
+                    subscription.subscription_code, res.message
+                )),
+                Err(e) => failed.push(format!("{}: {}", subscription.subscription_code, e)),
+            }
+        }
+
+        let message = if failed.is_empty() {
+            format!("Disabled {} subscriptions", disabled.len())
+        } else {
+            format!(
+                "Disabled {} of {} subscriptions. Failed: {}",
+                disabled.len(),
+                chargeable.len(),
+                failed.join(", ")
+            )
+        };
+
+        Ok(Response {
+            status: failed.is_empty(),
+            message,
+            data: Some(disabled),
+            meta: None,
+            response_type: None,
+            code: None,
+            raw: None,
+        })
+    }
+
     /// Generate Update Subscription Link
     ///
     /// # Arguments
-    /// * `code` - the code of the subscription to enable
+    /// * `code` - the code of the subscription to manage
     ///
-    pub async fn generate_update_subscription_link(&self, code: String) -> PaystackResult<String> {
+    /// # Returns
+    /// A Result containing the link to the subscription management page or an error.
+    /// A link that is not an absolute HTTPS URL is returned as an error.
+    pub async fn generate_update_subscription_link(
+        &self,
+        code: String,
+    ) -> PaystackResult<ManagementLink> {
         let url = format!("{}/{}/manage/link", self.base_url, code);
 
         let response = self
             .http
-            .post(&url, &self.key, &serde_json::Value::Null)
+            .get(&url, &self.key, None)
             .await
-            .map_err(|e| PaystackAPIError::Subscription(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Subscription))?;
 
-        let parsed_response: Response<String> =
-            serde_json::from_str(&response).map_err(|e| PaystackAPIError::Charge(e.to_string()))?;
+        let parsed_response: Response<ManagementLink> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Subscription(e.to_string()))?;
 
         Ok(parsed_response)
     }
@@ -208,8 +441,10 @@ impl<T: HttpClient + Default> SubscriptionEndpoints<T> {
     /// Send Update Subscription Link
     ///
     /// # Arguments
-    /// * `code` - the code of the subscription to enable
+    /// * `code` - the code of the subscription to manage
     ///
+    /// # Returns
+    /// A Result containing the response or an error. The response has no data.
     pub async fn send_update_subscription_link(&self, code: String) -> PaystackResult<String> {
         let url = format!("{}/{}/manage/email", self.base_url, code);
 
@@ -217,11 +452,70 @@ impl<T: HttpClient + Default> SubscriptionEndpoints<T> {
             .http
             .post(&url, &self.key, &serde_json::Value::Null)
             .await
-            .map_err(|e| PaystackAPIError::Subscription(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Subscription))?;
 
-        let parsed_response: Response<String> =
-            serde_json::from_str(&response).map_err(|e| PaystackAPIError::Charge(e.to_string()))?;
+        let parsed_response: Response<String> = Response::parse(&response, self.keep_raw)
+            .map_err(|e| PaystackAPIError::Subscription(e.to_string()))?;
 
         Ok(parsed_response)
     }
+
+    /// Email the customer the link to manage their subscription, e.g. to update their card.
+    ///
+    /// # Arguments
+    /// * `code` - the code of the subscription to manage
+    ///
+    /// # Returns
+    /// A Result containing the response or an error
+    pub async fn send_management_email(&self, code: String) -> PaystackResult<()> {
+        let response = self.send_update_subscription_link(code).await?;
+
+        Ok(Response {
+            status: response.status,
+            message: response.message,
+            data: None,
+            meta: response.meta,
+            response_type: response.response_type,
+            code: response.code,
+            raw: response.raw,
+        })
+    }
+
+    /// Generate the link to manage a subscription and email it to the customer.
+    ///
+    /// The link is still returned if the email could not be sent, with `emailed` set to `false`,
+    /// so it can be shown to the customer instead.
+    ///
+    /// # Arguments
+    /// * `code` - the code of the subscription to manage
+    ///
+    /// # Returns
+    /// A Result containing the link and whether it was emailed, or an error if the link could not be generated
+    pub async fn manage(&self, code: String) -> PaystackResult<ManageLinks> {
+        let response = self.generate_update_subscription_link(code.clone()).await?;
+        let link = response
+            .data
+            .ok_or_else(|| {
+                PaystackAPIError::Subscription(format!("no management link returned for {code}"))
+            })?
+            .link;
+
+        let emailed = match self.send_management_email(code.clone()).await {
+            Ok(email) => email.status,
+            Err(e) => {
+                log::warn!("unable to email the management link of subscription {code}: {e}");
+                false
+            }
+        };
+
+        Ok(Response {
+            status: response.status,
+            message: response.message,
+            data: Some(ManageLinks { link, emailed }),
+            meta: None,
+            response_type: response.response_type,
+            code: response.code,
+            raw: response.raw,
+        })
+    }
 }
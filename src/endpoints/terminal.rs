@@ -4,13 +4,16 @@
 
 use crate::{
     EventRequest, FetchEventStatusResponseData, FetchTerminalStatusResponseData, HttpClient,
-    PaystackAPIError, PaystackResult, Response, SendEventResponseData, TerminalData,
-    UpdateTerminalRequest,
+    ListTerminalsRequest, PaystackAPIError, PaystackResult, Response, SendEventResponseData,
+    TerminalData, UpdateTerminalRequest,
 };
-use std::{marker::PhantomData, sync::Arc};
+use std::{marker::PhantomData, sync::Arc, time::Duration};
 
 use super::PAYSTACK_BASE_URL;
 
+/// How long `await_event` waits between two checks of an event, by default
+pub const DEFAULT_EVENT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
 /// A struct to hold all the functions of the terminal API endpoint
 #[derive(Debug, Clone)]
 pub struct TerminalEndpoints<T: HttpClient + Default> {
@@ -20,6 +23,10 @@ pub struct TerminalEndpoints<T: HttpClient + Default> {
     base_url: String,
     /// Http client for the route
     http: Arc<T>,
+    /// Whether the raw JSON body is kept on each response
+    keep_raw: bool,
+    /// How long `await_event` waits between two checks of an event
+    event_poll_interval: Duration,
 }
 
 impl<T: HttpClient + Default> TerminalEndpoints<T> {
@@ -37,9 +44,17 @@ impl<T: HttpClient + Default> TerminalEndpoints<T> {
             key: key.to_string(),
             base_url,
             http,
+            keep_raw: false,
+            event_poll_interval: DEFAULT_EVENT_POLL_INTERVAL,
         }
     }
 
+    /// Keeps the raw JSON body of each response in `Response::raw`, see `PaystackClient::with_keep_raw`.
+    pub fn with_keep_raw(mut self, keep_raw: bool) -> Self {
+        self.keep_raw = keep_raw;
+        self
+    }
+
     /// Send an event from your application to the Paystack Terminal
     ///
     /// # Arguments
@@ -47,12 +62,17 @@ impl<T: HttpClient + Default> TerminalEndpoints<T> {
     /// * `event_request` - Struct containing the information of the event to send to the terminal. Created with EventRequestBuilder
     ///
     /// # Returns
-    /// A Result containing the send event response data or an error
+    /// A Result containing the ID of the event, to poll its status with `fetch_event_status`,
+    /// or a validation error if the action or data is not valid for the type of event
     pub async fn send_event(
         &self,
         terminal_id: String,
         event_request: EventRequest,
     ) -> PaystackResult<SendEventResponseData> {
+        event_request
+            .validate()
+            .map_err(PaystackAPIError::Validation)?;
+
         let url = format!("{}/{}/event", self.base_url, terminal_id);
         let body = serde_json::to_value(event_request)
             .map_err(|e| PaystackAPIError::Terminal(e.to_string()))?;
@@ -61,10 +81,11 @@ impl<T: HttpClient + Default> TerminalEndpoints<T> {
             .http
             .post(&url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::Terminal(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Terminal))?;
 
-        let parsed_response: Response<SendEventResponseData> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::Terminal(e.to_string()))?;
+        let parsed_response: Response<SendEventResponseData> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Terminal(e.to_string()))?;
 
         Ok(parsed_response)
     }
@@ -76,7 +97,9 @@ impl<T: HttpClient + Default> TerminalEndpoints<T> {
     /// * `event_id` - The ID of the event that was sent to the Terminal
     ///
     /// # Returns
-    /// A Result containing the event status response data or an error
+    /// A Result containing the event status response data or an error.
+    /// `delivered` stays `false` until the Terminal receives the event, so an event that is not
+    /// delivered in time can be sent again with `send_event`. See `await_event` to wait for it instead.
     pub async fn fetch_event_status(
         &self,
         terminal_id: String,
@@ -88,15 +111,76 @@ impl<T: HttpClient + Default> TerminalEndpoints<T> {
             .http
             .get(&url, &self.key, None)
             .await
-            .map_err(|e| PaystackAPIError::Terminal(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Terminal))?;
 
         let parsed_response: Response<FetchEventStatusResponseData> =
-            serde_json::from_str(&response)
+            Response::parse(&response, self.keep_raw)
                 .map_err(|e| PaystackAPIError::Terminal(e.to_string()))?;
 
         Ok(parsed_response)
     }
 
+    /// Sets how long `await_event` waits between two checks of an event.
+    /// Defaults to `DEFAULT_EVENT_POLL_INTERVAL`.
+    ///
+    /// # Arguments
+    /// * `interval` - The time between two checks
+    pub fn with_event_poll_interval(mut self, interval: Duration) -> Self {
+        self.event_poll_interval = interval;
+        self
+    }
+
+    /// Waits until an event sent to the Paystack Terminal is delivered.
+    ///
+    /// The status of the event is checked every poll interval, see `with_event_poll_interval`.
+    /// While it is not delivered, the presence of the Terminal is checked too,
+    /// so an offline Terminal is reported right away instead of waiting for the timeout.
+    ///
+    /// # Arguments
+    /// * `terminal_id` - The ID of the Terminal the event was sent to
+    /// * `event_id` - The ID of the event, as returned by `send_event`
+    /// * `timeout` - How long to wait for the event to be delivered, including requests still in flight
+    ///
+    /// # Returns
+    /// A Result containing the final event status, a terminal error if the Terminal is offline,
+    /// or `PaystackAPIError::Timeout` if the event is not delivered before the timeout
+    pub async fn await_event(
+        &self,
+        terminal_id: String,
+        event_id: String,
+        timeout: Duration,
+    ) -> PaystackResult<FetchEventStatusResponseData> {
+        let poll = async {
+            loop {
+                let status = self
+                    .fetch_event_status(terminal_id.clone(), event_id.clone())
+                    .await?;
+                if status.data.as_ref().is_some_and(|event| event.delivered) {
+                    return Ok(status);
+                }
+
+                let presence = self.fetch_terminal_status(terminal_id.clone()).await?;
+                if presence
+                    .data
+                    .as_ref()
+                    .is_some_and(|terminal| !terminal.online)
+                {
+                    return Err(PaystackAPIError::Terminal(format!(
+                        "terminal {terminal_id} is offline, event {event_id} was not delivered"
+                    )));
+                }
+
+                tokio::time::sleep(self.event_poll_interval).await;
+            }
+        };
+
+        tokio::time::timeout(timeout, poll).await.map_err(|_| {
+            PaystackAPIError::Timeout(format!(
+                "event {event_id} was not delivered to terminal {terminal_id} within {timeout:?}"
+            ))
+        })?
+    }
+
     /// Check the availiability of a Terminal before sending an event to it
     ///
     /// # Arguments
@@ -114,10 +198,10 @@ impl<T: HttpClient + Default> TerminalEndpoints<T> {
             .http
             .get(&url, &self.key, None)
             .await
-            .map_err(|e| PaystackAPIError::Terminal(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Terminal))?;
 
         let parsed_response: Response<FetchTerminalStatusResponseData> =
-            serde_json::from_str(&response)
+            Response::parse(&response, self.keep_raw)
                 .map_err(|e| PaystackAPIError::Terminal(e.to_string()))?;
 
         Ok(parsed_response)
@@ -130,6 +214,7 @@ impl<T: HttpClient + Default> TerminalEndpoints<T> {
     ///
     /// # Returns
     /// A Result containing a vector of terminal data or an error
+    #[deprecated(note = "use `list_terminals_with_cursor` instead")]
     pub async fn list_terminals(&self, per_page: Option<i32>) -> PaystackResult<Vec<TerminalData>> {
         let url = &self.base_url;
         let per_page = per_page.unwrap_or(50).to_string();
@@ -139,10 +224,45 @@ impl<T: HttpClient + Default> TerminalEndpoints<T> {
             .http
             .get(url, &self.key, Some(&query))
             .await
-            .map_err(|e| PaystackAPIError::Terminal(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Terminal))?;
 
-        let parsed_response: Response<Vec<TerminalData>> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::Terminal(e.to_string()))?;
+        let parsed_response: Response<Vec<TerminalData>> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Terminal(e.to_string()))?;
+
+        Ok(parsed_response)
+    }
+
+    /// List the Terminals available on your integration, one page at a time.
+    ///
+    /// Pass the cursor in `Meta::next` or `Meta::previous` of a response
+    /// to get the next or previous page.
+    ///
+    /// # Arguments
+    /// * `request` - The page of Terminals to list.
+    ///   It should be created with the `ListTerminalsRequestBuilder` struct.
+    ///
+    /// # Returns
+    /// A Result containing a vector of terminal data, with the cursors in `Response::meta`, or an error
+    pub async fn list_terminals_with_cursor(
+        &self,
+        request: ListTerminalsRequest,
+    ) -> PaystackResult<Vec<TerminalData>> {
+        let url = &self.base_url;
+
+        // Transform String to &str using iter
+        let query = request.to_query();
+        let query: Vec<(&str, &str)> = query.iter().map(|(k, v)| (*k, v.as_str())).collect();
+
+        let response = self
+            .http
+            .get(url, &self.key, Some(&query))
+            .await
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Terminal))?;
+
+        let parsed_response: Response<Vec<TerminalData>> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Terminal(e.to_string()))?;
 
         Ok(parsed_response)
     }
@@ -161,9 +281,9 @@ impl<T: HttpClient + Default> TerminalEndpoints<T> {
             .http
             .get(&url, &self.key, None)
             .await
-            .map_err(|e| PaystackAPIError::Terminal(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Terminal))?;
 
-        let parsed_response: Response<TerminalData> = serde_json::from_str(&response)
+        let parsed_response: Response<TerminalData> = Response::parse(&response, self.keep_raw)
             .map_err(|e| PaystackAPIError::Terminal(e.to_string()))?;
 
         Ok(parsed_response)
@@ -190,10 +310,11 @@ impl<T: HttpClient + Default> TerminalEndpoints<T> {
             .http
             .put(&url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::Terminal(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Terminal))?;
 
-        let parsed_response: Response<PhantomData<String>> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::Terminal(e.to_string()))?;
+        let parsed_response: Response<PhantomData<String>> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Terminal(e.to_string()))?;
 
         Ok(parsed_response)
     }
@@ -218,10 +339,11 @@ impl<T: HttpClient + Default> TerminalEndpoints<T> {
             .http
             .post(&url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::Terminal(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Terminal))?;
 
-        let parsed_response: Response<PhantomData<String>> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::Terminal(e.to_string()))?;
+        let parsed_response: Response<PhantomData<String>> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Terminal(e.to_string()))?;
 
         Ok(parsed_response)
     }
@@ -246,10 +368,11 @@ impl<T: HttpClient + Default> TerminalEndpoints<T> {
             .http
             .post(&url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::Terminal(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Terminal))?;
 
-        let parsed_response: Response<PhantomData<String>> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::Terminal(e.to_string()))?;
+        let parsed_response: Response<PhantomData<String>> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Terminal(e.to_string()))?;
 
         Ok(parsed_response)
     }
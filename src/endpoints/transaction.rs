@@ -2,13 +2,21 @@
 //! =============
 //! The Transaction route allows to create and manage payments on your integration.
 
+use super::pager::{for_each_page, PAGE_SIZE};
 use super::PAYSTACK_BASE_URL;
 use crate::{
-    ChargeRequest, ChargeResponseData, Currency, ExportTransactionData, HttpClient,
-    PartialDebitTransactionRequest, PaystackAPIError, PaystackResult, Response, Status,
-    TransactionIdentifier, TransactionRequest, TransactionResponseData, TransactionStatusData,
-    TransactionTimelineData, TransactionTotalData,
+    generate_reference, validate_reference_prefix, ChargeRequest, ChargeResponseData,
+    CountResponse, Currency, ExportTransactionData, ExportTransactionsRequest, HttpClient,
+    ListTransactionsRequest, PartialDebitTransactionRequest, PartialList, PaystackAPIError,
+    PaystackResult, RefundData, RefundEndpoints, RefundTransactionCheck, Response, Status,
+    TransactionContext, TransactionIdentifier, TransactionRequest, TransactionResponseData,
+    TransactionStatusData, TransactionTimelineData, TransactionTotalData,
 };
+#[cfg(feature = "csv")]
+use crate::{parse_transaction_export, ExportedTransaction};
+use futures_util::{future, stream, StreamExt};
+use serde_json::Value;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 /// A struct to hold all the functions of the transaction API endpoint
@@ -20,6 +28,12 @@ pub struct TransactionEndpoints<T: HttpClient + Default> {
     base_url: String,
     /// Http client for the route
     http: Arc<T>,
+    /// Whether the raw JSON body is kept on each response
+    keep_raw: bool,
+    /// Prefix of the references generated for create calls that leave `reference` unset
+    reference_prefix: Option<String>,
+    /// Currency of the create calls that leave `currency` unset
+    default_currency: Option<Currency>,
 }
 
 impl<T: HttpClient + Default> TransactionEndpoints<T> {
@@ -37,6 +51,65 @@ impl<T: HttpClient + Default> TransactionEndpoints<T> {
             key: key.to_string(),
             base_url,
             http,
+            keep_raw: false,
+            reference_prefix: None,
+            default_currency: None,
+        }
+    }
+
+    /// Keeps the raw JSON body of each response in `Response::raw`, see `PaystackClient::with_keep_raw`.
+    pub fn with_keep_raw(mut self, keep_raw: bool) -> Self {
+        self.keep_raw = keep_raw;
+        self
+    }
+
+    /// Generates a reference starting with the prefix whenever `initialize_transaction`,
+    /// `charge_authorization` or `partial_debit` is called without a reference,
+    /// e.g. `ORD-` to tell the transactions of orders apart in logs.
+    /// References set on a request are sent unchanged.
+    ///
+    /// # Arguments
+    /// * `prefix` - The start of the generated references.
+    ///   Only `-`, `.`, `=` and alphanumeric characters are allowed.
+    ///
+    /// # Returns
+    /// A Result containing the endpoints, or a validation error if the prefix is empty,
+    /// uses characters not allowed in a reference or is too long
+    pub fn with_reference_prefix(mut self, prefix: &str) -> Result<Self, PaystackAPIError> {
+        validate_reference_prefix(prefix).map_err(PaystackAPIError::Validation)?;
+        self.reference_prefix = Some(prefix.to_string());
+        Ok(self)
+    }
+
+    /// Sets the currency of `initialize_transaction` and `charge_authorization` calls made without one.
+    /// A currency set on a request is sent unchanged.
+    ///
+    /// # Arguments
+    /// * `currency` - The currency the payments are made in, e.g. the only currency of the integration
+    pub fn with_default_currency(mut self, currency: Currency) -> Self {
+        self.default_currency = Some(currency);
+        self
+    }
+
+    /// Sets the default currency on the request body if it has none and a default is configured.
+    fn fill_currency(&self, body: &mut Value) {
+        let Some(currency) = &self.default_currency else {
+            return;
+        };
+
+        if body.get("currency").is_none_or(Value::is_null) {
+            body["currency"] = Value::String(currency.to_string());
+        }
+    }
+
+    /// Sets a generated reference on the request body if it has none and a prefix is configured.
+    fn fill_reference(&self, body: &mut Value) {
+        let Some(prefix) = &self.reference_prefix else {
+            return;
+        };
+
+        if body.get("reference").is_none_or(Value::is_null) {
+            body["reference"] = Value::String(generate_reference(prefix));
         }
     }
 
@@ -53,17 +126,20 @@ impl<T: HttpClient + Default> TransactionEndpoints<T> {
         transaction_request: TransactionRequest,
     ) -> PaystackResult<TransactionResponseData> {
         let url = format!("{}/initialize", self.base_url);
-        let body = serde_json::to_value(transaction_request)
+        let mut body = serde_json::to_value(transaction_request)
             .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
+        self.fill_reference(&mut body);
+        self.fill_currency(&mut body);
 
         let response = self
             .http
             .post(&url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Transaction))?;
 
-        let parsed_response: Response<TransactionResponseData> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
+        let parsed_response: Response<TransactionResponseData> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
         Ok(parsed_response)
     }
 
@@ -84,14 +160,45 @@ impl<T: HttpClient + Default> TransactionEndpoints<T> {
             .http
             .get(&url, &self.key, None)
             .await
-            .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Transaction))?;
 
-        let parsed_response: Response<TransactionStatusData> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
+        let parsed_response: Response<TransactionStatusData> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
 
         Ok(parsed_response)
     }
 
+    /// Confirm the status of several transactions at once, e.g. when reconciling a day's callbacks.
+    ///
+    /// The transactions are verified concurrently, with at most `concurrency` requests in flight
+    /// at a time to stay within Paystack's rate limits. Duplicate references are verified once.
+    ///
+    /// # Arguments
+    /// * `references` - The transaction references to verify
+    /// * `concurrency` - The maximum number of requests to send at the same time. `0` is treated as `1`.
+    ///
+    /// # Returns
+    /// A map from each reference to the result of verifying it, so failures are visible per reference
+    pub async fn verify_transactions(
+        &self,
+        references: Vec<String>,
+        concurrency: usize,
+    ) -> HashMap<String, PaystackResult<TransactionStatusData>> {
+        let mut references = references;
+        references.sort();
+        references.dedup();
+
+        stream::iter(references)
+            .map(|reference| async move {
+                let result = self.verify_transaction(&reference).await;
+                (reference, result)
+            })
+            .buffer_unordered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
     /// Lists transactions carried out on your integration
     ///
     /// # Arguments
@@ -115,14 +222,170 @@ impl<T: HttpClient + Default> TransactionEndpoints<T> {
             .http
             .get(url, &self.key, Some(&query))
             .await
-            .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Transaction))?;
 
-        let parsed_response: Response<Vec<TransactionStatusData>> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
+        let parsed_response: Response<Vec<TransactionStatusData>> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
 
         Ok(parsed_response)
     }
 
+    /// Lists transactions carried out on your integration, filtered and paged as requested.
+    ///
+    /// Unlike `list_transactions`, no status filter is applied unless one is set,
+    /// and the page is read from `Response::meta`.
+    ///
+    /// # Arguments
+    /// * `request` - The filters of the transactions to list.
+    ///   It should be created with the `ListTransactionsRequestBuilder` struct.
+    ///
+    /// # Returns
+    /// A Result containing a vector of transaction status data or an error
+    pub async fn list_transactions_with_filter(
+        &self,
+        request: ListTransactionsRequest,
+    ) -> PaystackResult<Vec<TransactionStatusData>> {
+        let url = &self.base_url;
+
+        // Transform String to &str using iter
+        let query = request.to_query();
+        let query: Vec<(&str, &str)> = query.iter().map(|(k, v)| (*k, v.as_str())).collect();
+
+        let response = self
+            .http
+            .get(url, &self.key, Some(&query))
+            .await
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Transaction))?;
+
+        let parsed_response: Response<Vec<TransactionStatusData>> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
+
+        Ok(parsed_response)
+    }
+
+    /// Lists transactions carried out on your integration, skipping the records that cannot be deserialized.
+    ///
+    /// Use it instead of `list_transactions` for large pulls, e.g. for reconciliation,
+    /// where one malformed record should not fail the whole page.
+    ///
+    /// # Arguments
+    /// * `per_page` - Optional number of transactions to return per page. Defaults to 10 if None
+    /// * `status` - Optional filter for transaction status. Defaults to Success if None
+    ///
+    /// # Returns
+    /// A Result containing the deserialized transactions and the errors of the skipped ones, or an error
+    pub async fn list_transactions_lenient(
+        &self,
+        per_page: Option<u32>,
+        status: Option<Status>,
+    ) -> PaystackResult<PartialList<TransactionStatusData>> {
+        let url = &self.base_url;
+
+        let per_page = per_page.unwrap_or(10).to_string();
+        let status = status.unwrap_or(Status::Success).to_string();
+        let query = vec![("perPage", per_page.as_str()), ("status", status.as_str())];
+
+        let response = self
+            .http
+            .get(url, &self.key, Some(&query))
+            .await
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Transaction))?;
+
+        let parsed_response: Response<PartialList<TransactionStatusData>> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
+
+        Ok(parsed_response)
+    }
+
+    /// Counts the transactions carried out on your integration, without fetching them
+    ///
+    /// # Arguments
+    /// * `status` - Optional filter for transaction status. All transactions are counted if None
+    ///
+    /// # Returns
+    /// A Result containing the total number of matching transactions or an error
+    pub async fn count_transactions(&self, status: Option<Status>) -> PaystackResult<u64> {
+        let url = &self.base_url;
+
+        let status = status.map(|status| status.to_string());
+        let mut query = vec![("perPage", "1")];
+        if let Some(status) = &status {
+            query.push(("status", status.as_str()));
+        }
+
+        let response = self
+            .http
+            .get(url, &self.key, Some(&query))
+            .await
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Transaction))?;
+
+        CountResponse::parse(&response).map_err(PaystackAPIError::Transaction)
+    }
+
+    /// Finds the transactions whose metadata has the given value for a key,
+    /// e.g. to find the payment of an internal order ID stored in the metadata.
+    ///
+    /// Paystack cannot filter transactions by metadata, so this is a client-side scan:
+    /// every transaction created in the date range is fetched, 100 at a time, and filtered here.
+    /// Keep the range as narrow as possible.
+    ///
+    /// # Arguments
+    /// * `key` - The metadata key e.g. `order_id`
+    /// * `value` - The value of the key. Numbers and booleans are compared by their text e.g. `"1234"`
+    /// * `from` - Start date of the range e.g. `2024-08-01T00:00:00.000Z`
+    /// * `to` - End date of the range e.g. `2024-08-31T23:59:59.000Z`
+    ///
+    /// # Returns
+    /// A Result containing the matching transactions or an error
+    pub async fn find_by_metadata(
+        &self,
+        key: &str,
+        value: &str,
+        from: &str,
+        to: &str,
+    ) -> PaystackResult<Vec<TransactionStatusData>> {
+        let url = &self.base_url;
+        let per_page = &PAGE_SIZE.to_string();
+        let mut matches = Vec::new();
+
+        let fetch_page = move |page: u32| async move {
+            let page = page.to_string();
+            let query = vec![
+                ("perPage", per_page.as_str()),
+                ("page", page.as_str()),
+                ("from", from),
+                ("to", to),
+            ];
+
+            let response = self
+                .http
+                .get(url, &self.key, Some(&query))
+                .await
+                .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Transaction))?;
+
+            let parsed_response: Response<Vec<TransactionStatusData>> =
+                Response::parse(&response, self.keep_raw)
+                    .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
+            Ok(parsed_response)
+        };
+
+        let last_page = for_each_page(fetch_page, |transactions| {
+            matches.extend(transactions.into_iter().filter(|transaction| {
+                transaction
+                    .metadata
+                    .as_ref()
+                    .is_some_and(|metadata| metadata_matches(metadata, key, value))
+            }));
+            Ok(true)
+        })
+        .await?;
+
+        Ok(last_page.with_data(matches))
+    }
+
     /// Gets details of a specific transaction
     ///
     /// # Arguments
@@ -130,9 +393,24 @@ impl<T: HttpClient + Default> TransactionEndpoints<T> {
     ///
     /// # Returns
     /// A Result containing the transaction status data or an error
+    #[deprecated(note = "use `fetch_transaction` instead")]
     pub async fn fetch_transactions(
         &self,
         transaction_id: u64,
+    ) -> PaystackResult<TransactionStatusData> {
+        self.fetch_transaction(transaction_id).await
+    }
+
+    /// Gets details of a specific transaction, including its customer, authorization and plan
+    ///
+    /// # Arguments
+    /// * `transaction_id` - The ID of the transaction to fetch
+    ///
+    /// # Returns
+    /// A Result containing the transaction status data or an error
+    pub async fn fetch_transaction(
+        &self,
+        transaction_id: u64,
     ) -> PaystackResult<TransactionStatusData> {
         let url = format!("{}/{}", self.base_url, transaction_id);
 
@@ -140,14 +418,152 @@ impl<T: HttpClient + Default> TransactionEndpoints<T> {
             .http
             .get(&url, &self.key, None)
             .await
-            .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Transaction))?;
 
-        let parsed_response: Response<TransactionStatusData> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
+        let parsed_response: Response<TransactionStatusData> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
 
         Ok(parsed_response)
     }
 
+    /// Gets details of a transaction using its reference
+    ///
+    /// This looks the reference up through the list route, so unlike `verify_transaction`
+    /// it does not requery the payment channel. `reference` is not a documented filter of
+    /// that route, so if Paystack returns transactions with another reference the call fails
+    /// with `FilterNotHonoured` instead of searching a page of unrelated transactions.
+    ///
+    /// # Arguments
+    /// * `reference` - The reference of the transaction to fetch
+    ///
+    /// # Returns
+    /// A Result containing the transaction status data, a `NotFound` error if no transaction
+    /// has the reference, a `FilterNotHonoured` error if the reference filter was ignored,
+    /// or an error if more than one transaction matches it
+    pub async fn fetch_transaction_by_reference(
+        &self,
+        reference: &str,
+    ) -> PaystackResult<TransactionStatusData> {
+        let url = &self.base_url;
+        let query = vec![("reference", reference)];
+
+        let response = self
+            .http
+            .get(url, &self.key, Some(&query))
+            .await
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Transaction))?;
+
+        let parsed_response: Response<Vec<TransactionStatusData>> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
+
+        let mut transactions = parsed_response.data.unwrap_or_default();
+        if let Some(transaction) = transactions.iter().find(|t| t.reference != reference) {
+            return Err(PaystackAPIError::FilterNotHonoured(format!(
+                "looked up reference {reference} but got transaction {} with reference {}",
+                transaction.id, transaction.reference
+            )));
+        }
+
+        match transactions.len() {
+            0 => Err(PaystackAPIError::NotFound(format!(
+                "no transaction with reference {reference}"
+            ))),
+            1 => Ok(Response {
+                status: parsed_response.status,
+                message: parsed_response.message,
+                data: transactions.pop(),
+                meta: None,
+                response_type: parsed_response.response_type,
+                code: parsed_response.code,
+                raw: parsed_response.raw,
+            }),
+            count => Err(PaystackAPIError::Transaction(format!(
+                "{count} transactions found with reference {reference}"
+            ))),
+        }
+    }
+
+    /// Fetches a transaction together with its refunds, e.g. for a support agent investigating a payment.
+    ///
+    /// The transaction and its refunds are fetched concurrently, going through every page of refunds.
+    /// If the refunds cannot be fetched, or Paystack lists refunds of other transactions,
+    /// a warning is logged and the refunds are left empty instead of failing the whole call.
+    ///
+    /// # Arguments
+    /// * `transaction_id` - ID of the transaction to fetch
+    ///
+    /// # Returns
+    /// A Result containing the transaction and its refunds, or an error if the transaction could not be fetched
+    pub async fn fetch_with_context(
+        &self,
+        transaction_id: u64,
+    ) -> PaystackResult<TransactionContext> {
+        let (transaction, refunds) = future::join(
+            self.fetch_transaction(transaction_id),
+            self.list_all_refunds(transaction_id),
+        )
+        .await;
+
+        let transaction = transaction?;
+        let refunds = match refunds {
+            Ok(refunds) => refunds,
+            Err(e) => {
+                log::warn!("unable to fetch the refunds of transaction {transaction_id}: {e}");
+                Vec::new()
+            }
+        };
+
+        Ok(Response {
+            status: transaction.status,
+            message: transaction.message,
+            data: transaction.data.map(|transaction| TransactionContext {
+                transaction,
+                refunds,
+            }),
+            meta: None,
+            response_type: transaction.response_type,
+            code: transaction.code,
+            raw: transaction.raw,
+        })
+    }
+
+    /// Lists every refund of a transaction, one page at a time.
+    /// Fails with `FilterNotHonoured` if refunds of another transaction are listed.
+    async fn list_all_refunds(
+        &self,
+        transaction_id: u64,
+    ) -> Result<Vec<RefundData>, PaystackAPIError> {
+        let refunds = &RefundEndpoints::new(Arc::new(self.key.clone()), Arc::clone(&self.http));
+        let transaction = &transaction_id.to_string();
+        let mut all = vec![];
+        let mut check = RefundTransactionCheck::new(transaction);
+
+        for_each_page(
+            move |page| {
+                refunds.list_refunds(
+                    Some(transaction),
+                    None,
+                    None,
+                    None,
+                    Some(PAGE_SIZE),
+                    Some(page),
+                )
+            },
+            |data| {
+                for refund in &data {
+                    check.check(refund)?;
+                }
+                all.extend(data);
+                Ok(true)
+            },
+        )
+        .await?;
+
+        Ok(all)
+    }
+
     /// Charges a reusable authorization
     ///
     /// # Arguments
@@ -161,17 +577,20 @@ impl<T: HttpClient + Default> TransactionEndpoints<T> {
         charge_request: ChargeRequest,
     ) -> PaystackResult<ChargeResponseData> {
         let url = format!("{}/charge_authorization", self.base_url);
-        let body = serde_json::to_value(charge_request)
+        let mut body = serde_json::to_value(charge_request)
             .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
+        self.fill_reference(&mut body);
+        self.fill_currency(&mut body);
 
         let response = self
             .http
             .post(&url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Transaction))?;
 
         let parsed_response: Response<ChargeResponseData> =
-            serde_json::from_str(&response).map_err(|e| PaystackAPIError::Charge(e.to_string()))?;
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Charge(e.to_string()))?;
 
         Ok(parsed_response)
     }
@@ -200,10 +619,11 @@ impl<T: HttpClient + Default> TransactionEndpoints<T> {
             .http
             .get(&url, &self.key, None)
             .await
-            .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Transaction))?;
 
-        let parsed_response: Response<TransactionTimelineData> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
+        let parsed_response: Response<TransactionTimelineData> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
 
         Ok(parsed_response)
     }
@@ -213,22 +633,57 @@ impl<T: HttpClient + Default> TransactionEndpoints<T> {
     /// # Returns
     /// A Result containing the transaction total data or an error
     pub async fn total_transactions(&self) -> PaystackResult<TransactionTotalData> {
+        self.transaction_totals(None, None).await
+    }
+
+    /// Gets the totals of the transactions on your integration, optionally within a date range
+    ///
+    /// # Arguments
+    /// * `from` - Optional start date of the range e.g. `2024-08-01T00:00:00.000Z`
+    /// * `to` - Optional end date of the range e.g. `2024-08-31T23:59:59.000Z`
+    ///
+    /// # Returns
+    /// A Result containing the total number and volume of transactions, overall and per currency,
+    /// and the pending transfers, or an error
+    pub async fn transaction_totals(
+        &self,
+        from: Option<&str>,
+        to: Option<&str>,
+    ) -> PaystackResult<TransactionTotalData> {
         let url = format!("{}/totals", self.base_url);
 
+        let mut query = Vec::new();
+        if let Some(from) = from {
+            query.push(("from", from));
+        }
+        if let Some(to) = to {
+            query.push(("to", to));
+        }
+
         let response = self
             .http
-            .get(&url, &self.key, None)
+            .get(
+                &url,
+                &self.key,
+                if query.is_empty() { None } else { Some(&query) },
+            )
             .await
-            .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Transaction))?;
 
-        let parsed_response: Response<TransactionTotalData> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
+        let parsed_response: Response<TransactionTotalData> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
 
         Ok(parsed_response)
     }
 
     /// Exports a list of transactions
     ///
+    /// The returned path is a CSV file to download. With the `csv` feature enabled,
+    /// its content can be read with `parse_transaction_export`, or `export_and_parse`
+    /// can export, download and parse the file in one call.
+    /// Use `export_transactions_with_filter` for the other filters.
+    ///
     /// # Arguments
     /// * `status` - Optional status filter for transactions to export. Defaults to Success
     /// * `currency` - Optional currency filter. Defaults to NGN
@@ -263,14 +718,89 @@ impl<T: HttpClient + Default> TransactionEndpoints<T> {
             .http
             .get(&url, &self.key, Some(&query))
             .await
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Transaction))?;
+
+        let parsed_response = Response::parse(&response, self.keep_raw)
             .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
 
-        let parsed_response = serde_json::from_str(&response)
+        Ok(parsed_response)
+    }
+
+    /// Exports the transactions matching the filters
+    ///
+    /// Unlike `export_transaction`, no status or currency filter is applied unless one is set.
+    ///
+    /// # Arguments
+    /// * `request` - The filters of the transactions to export.
+    ///   It should be created with the `ExportTransactionsRequestBuilder` struct.
+    ///
+    /// # Returns
+    /// A Result containing the path of the CSV file to download, or an error
+    pub async fn export_transactions_with_filter(
+        &self,
+        request: ExportTransactionsRequest,
+    ) -> PaystackResult<ExportTransactionData> {
+        let url = format!("{}/export", self.base_url);
+
+        // Transform String to &str using iter
+        let query = request.to_query();
+        let query: Vec<(&str, &str)> = query.iter().map(|(k, v)| (*k, v.as_str())).collect();
+
+        let response = self
+            .http
+            .get(&url, &self.key, Some(&query))
+            .await
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Transaction))?;
+
+        let parsed_response = Response::parse(&response, self.keep_raw)
             .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
 
         Ok(parsed_response)
     }
 
+    /// Exports the transactions matching the filters and reads the rows of the exported file.
+    ///
+    /// The file is downloaded from the pre-signed path returned by the export with `HttpClient::download`,
+    /// which does not send the API key, and parsed with `parse_transaction_export`.
+    /// The whole file is held in memory while it is parsed, so the size limit of the HTTP client
+    /// e.g. `ReqwestClient::with_max_response_bytes` applies to it.
+    ///
+    /// # Arguments
+    /// * `request` - The filters of the transactions to export.
+    ///   It should be created with the `ExportTransactionsRequestBuilder` struct.
+    ///
+    /// # Returns
+    /// A Result containing the exported transactions, or an error if the export, the download
+    /// or the parsing of the file fails
+    #[cfg(feature = "csv")]
+    pub async fn export_and_parse(
+        &self,
+        request: ExportTransactionsRequest,
+    ) -> Result<Vec<ExportedTransaction>, PaystackAPIError>
+    where
+        T: Sync,
+    {
+        let export = self.export_transactions_with_filter(request).await?;
+        let path = export
+            .data
+            .map(|data| data.path)
+            .filter(|path| !path.is_empty())
+            .ok_or_else(|| {
+                PaystackAPIError::Transaction(format!(
+                    "the export has no file to download: {}",
+                    export.message
+                ))
+            })?;
+
+        let file = self
+            .http
+            .download(&path)
+            .await
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Transaction))?;
+
+        parse_transaction_export(file.as_bytes())
+    }
+
     /// Performs a partial debit on a transaction
     ///
     /// # Arguments
@@ -284,18 +814,42 @@ impl<T: HttpClient + Default> TransactionEndpoints<T> {
         partial_debit_transaction_request: PartialDebitTransactionRequest,
     ) -> PaystackResult<TransactionStatusData> {
         let url = format!("{}/partial_debit", self.base_url);
-        let body = serde_json::to_value(partial_debit_transaction_request)
+        let mut body = serde_json::to_value(partial_debit_transaction_request)
             .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
+        self.fill_reference(&mut body);
 
         let response = self
             .http
             .post(&url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::Transaction))?;
 
-        let parsed_response: Response<TransactionStatusData> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
+        let parsed_response: Response<TransactionStatusData> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
 
         Ok(parsed_response)
     }
 }
+
+/// Whether the metadata of a transaction has the given value for the key.
+/// Paystack sometimes returns the metadata as a JSON encoded string, which is decoded first.
+fn metadata_matches(metadata: &Value, key: &str, value: &str) -> bool {
+    let decoded;
+    let metadata = match metadata {
+        Value::String(encoded) => match serde_json::from_str(encoded) {
+            Ok(parsed) => {
+                decoded = parsed;
+                &decoded
+            }
+            Err(_) => return false,
+        },
+        metadata => metadata,
+    };
+
+    match metadata.get(key) {
+        Some(Value::String(found)) => found == value,
+        Some(Value::Null) | None => false,
+        Some(found) => serde_json::from_str::<Value>(value).is_ok_and(|parsed| &parsed == found),
+    }
+}
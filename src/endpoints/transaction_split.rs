@@ -3,9 +3,11 @@
 //! The Transaction Splits API enables merchants split the settlement for a
 //! transaction across their payout account, and one or more subaccounts.
 
+use super::pager::{for_each_page, PAGE_SIZE};
 use super::PAYSTACK_BASE_URL;
 use crate::{
-    DeleteSubAccountBody, HttpClient, PaystackAPIError, PaystackResult, Response, SubaccountBody,
+    validate_split_shares, DeleteSubAccountBody, HttpClient, ListTransactionSplitsRequest,
+    PaystackAPIError, PaystackResult, RemovedSplitSubaccount, Response, SplitType, SubaccountBody,
     TransactionSplitRequest, TransactionSplitResponseData, UpdateTransactionSplitRequest,
 };
 use std::sync::Arc;
@@ -19,6 +21,8 @@ pub struct TransactionSplitEndpoints<T: HttpClient + Default> {
     base_url: String,
     /// Http client for the route
     http: Arc<T>,
+    /// Whether the raw JSON body is kept on each response
+    keep_raw: bool,
 }
 
 impl<T: HttpClient + Default> TransactionSplitEndpoints<T> {
@@ -36,21 +40,33 @@ impl<T: HttpClient + Default> TransactionSplitEndpoints<T> {
             key: key.to_string(),
             base_url,
             http,
+            keep_raw: false,
         }
     }
 
+    /// Keeps the raw JSON body of each response in `Response::raw`, see `PaystackClient::with_keep_raw`.
+    pub fn with_keep_raw(mut self, keep_raw: bool) -> Self {
+        self.keep_raw = keep_raw;
+        self
+    }
+
     /// Creates a split payment on your integration
     ///
     /// # Arguments
     /// * `split_body` - The request data to create the split payment.
     ///   It should be created with a `TransactionSplitRequest` struct.
     ///
+    /// The shares are checked before the request is sent: percentage shares must add up to at most 100,
+    /// and flat shares must be positive whole amounts.
+    ///
     /// # Returns
-    /// A Result containing the transaction split response data or an error
+    /// A Result containing the transaction split response data, or a validation error if the shares are not valid
     pub async fn create_transaction_split(
         &self,
         split_body: TransactionSplitRequest,
     ) -> PaystackResult<TransactionSplitResponseData> {
+        split_body.validate_shares()?;
+
         let url = &self.base_url;
         let body = serde_json::to_value(split_body)
             .map_err(|e| PaystackAPIError::TransactionSplit(e.to_string()))?;
@@ -59,10 +75,10 @@ impl<T: HttpClient + Default> TransactionSplitEndpoints<T> {
             .http
             .post(url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::TransactionSplit(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::TransactionSplit))?;
 
         let parsed_response: Response<TransactionSplitResponseData> =
-            serde_json::from_str(&response)
+            Response::parse(&response, self.keep_raw)
                 .map_err(|e| PaystackAPIError::TransactionSplit(e.to_string()))?;
         Ok(parsed_response)
     }
@@ -97,15 +113,97 @@ impl<T: HttpClient + Default> TransactionSplitEndpoints<T> {
             .http
             .get(url, &self.key, Some(&query))
             .await
-            .map_err(|e| PaystackAPIError::TransactionSplit(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::TransactionSplit))?;
+
+        let parsed_response: Response<Vec<TransactionSplitResponseData>> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::TransactionSplit(e.to_string()))?;
+
+        Ok(parsed_response)
+    }
+
+    /// Lists transaction splits available on your integration, filtered and paged as requested.
+    ///
+    /// Unlike `list_transaction_splits`, only the filters that are set are sent,
+    /// and the page is read from `Response::meta`.
+    ///
+    /// # Arguments
+    /// * `request` - The filters of the splits to list.
+    ///   It should be created with the `ListTransactionSplitsRequestBuilder` struct.
+    ///
+    /// # Returns
+    /// A Result containing a vector of transaction split response data or an error
+    pub async fn list_transaction_splits_with_filter(
+        &self,
+        request: ListTransactionSplitsRequest,
+    ) -> PaystackResult<Vec<TransactionSplitResponseData>> {
+        let url = &self.base_url;
+
+        // Transform String to &str using iter
+        let query = request.to_query();
+        let query: Vec<(&str, &str)> = query.iter().map(|(k, v)| (*k, v.as_str())).collect();
+
+        let response = self
+            .http
+            .get(url, &self.key, Some(&query))
+            .await
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::TransactionSplit))?;
 
         let parsed_response: Response<Vec<TransactionSplitResponseData>> =
-            serde_json::from_str(&response)
+            Response::parse(&response, self.keep_raw)
                 .map_err(|e| PaystackAPIError::TransactionSplit(e.to_string()))?;
 
         Ok(parsed_response)
     }
 
+    /// Lists the transaction splits a subaccount is part of, e.g. to audit the payout configuration of a vendor.
+    ///
+    /// Paystack cannot filter splits by subaccount, so every split on the integration is fetched,
+    /// 100 at a time, and the splits that include the subaccount are returned.
+    ///
+    /// # Arguments
+    /// * `subaccount_code` - Code of the subaccount e.g. `ACCT_4hl4xenwpjy5wb`
+    ///
+    /// # Returns
+    /// A Result containing the transaction splits that include the subaccount or an error
+    pub async fn list_splits_for_subaccount(
+        &self,
+        subaccount_code: &str,
+    ) -> PaystackResult<Vec<TransactionSplitResponseData>> {
+        let url = &self.base_url;
+        let per_page = &PAGE_SIZE.to_string();
+        let mut splits = Vec::new();
+
+        let fetch_page = move |page: u32| async move {
+            let page = page.to_string();
+            let query = vec![("perPage", per_page.as_str()), ("page", page.as_str())];
+
+            let response = self
+                .http
+                .get(url, &self.key, Some(&query))
+                .await
+                .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::TransactionSplit))?;
+
+            let parsed_response: Response<Vec<TransactionSplitResponseData>> =
+                Response::parse(&response, self.keep_raw)
+                    .map_err(|e| PaystackAPIError::TransactionSplit(e.to_string()))?;
+            Ok(parsed_response)
+        };
+
+        let last_page = for_each_page(fetch_page, |page_splits| {
+            splits.extend(page_splits.into_iter().filter(|split| {
+                split
+                    .subaccounts
+                    .iter()
+                    .any(|s| s.subaccount.subaccount_code == subaccount_code)
+            }));
+            Ok(true)
+        })
+        .await?;
+
+        Ok(last_page.with_data(splits))
+    }
+
     /// Gets details of a split on your integration
     ///
     /// # Arguments
@@ -123,10 +221,10 @@ impl<T: HttpClient + Default> TransactionSplitEndpoints<T> {
             .http
             .get(&url, &self.key, None)
             .await
-            .map_err(|e| PaystackAPIError::TransactionSplit(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::TransactionSplit))?;
 
         let parsed_response: Response<TransactionSplitResponseData> =
-            serde_json::from_str(&response)
+            Response::parse(&response, self.keep_raw)
                 .map_err(|e| PaystackAPIError::TransactionSplit(e.to_string()))?;
 
         Ok(parsed_response)
@@ -154,10 +252,10 @@ impl<T: HttpClient + Default> TransactionSplitEndpoints<T> {
             .http
             .put(&url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::TransactionSplit(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::TransactionSplit))?;
 
         let parsed_response: Response<TransactionSplitResponseData> =
-            serde_json::from_str(&response)
+            Response::parse(&response, self.keep_raw)
                 .map_err(|e| PaystackAPIError::TransactionSplit(e.to_string()))?;
         Ok(parsed_response)
     }
@@ -169,13 +267,44 @@ impl<T: HttpClient + Default> TransactionSplitEndpoints<T> {
     /// * `body` - The subaccount data to add or update.
     ///   It should be created with a `SubaccountBody` struct.
     ///
+    /// This makes two requests: the split is fetched first to check the new share against its
+    /// type and the shares of the other subaccounts, then the subaccount is posted to it.
+    /// Percentage shares must add up to at most 100, and flat shares must be positive whole amounts.
+    /// If the fetch fails, or the split has a type other than `percentage` or `flat`,
+    /// the subaccount is not added.
+    ///
     /// # Returns
-    /// A Result containing the transaction split response data or an error
+    /// A Result containing the transaction split response data, a validation error if the share is not valid,
+    /// or an error if the split could not be fetched
     pub async fn add_or_update_subaccount_split(
         &self,
         split_id: &str,
         body: SubaccountBody,
     ) -> PaystackResult<TransactionSplitResponseData> {
+        let split = self
+            .fetch_transaction_split(split_id)
+            .await?
+            .data
+            .ok_or_else(|| {
+                PaystackAPIError::TransactionSplit(format!("no split data returned for {split_id}"))
+            })?;
+        let split_type = match split.split_type.as_str() {
+            "percentage" => SplitType::Percentage,
+            "flat" => SplitType::Flat,
+            other => {
+                return Err(PaystackAPIError::TransactionSplit(format!(
+                    "split {split_id} has unknown type {other}"
+                )))
+            }
+        };
+        // The share of a subaccount already in the split is replaced by the new one
+        let other_shares = split
+            .subaccounts
+            .iter()
+            .filter(|s| s.subaccount.subaccount_code != body.subaccount)
+            .map(|s| s.share as f32);
+        validate_split_shares(&split_type, other_shares.chain([body.share]))?;
+
         let url = format!("{}/{}/subaccount/add", self.base_url, split_id);
         let body = serde_json::to_value(body)
             .map_err(|e| PaystackAPIError::TransactionSplit(e.to_string()))?;
@@ -184,10 +313,10 @@ impl<T: HttpClient + Default> TransactionSplitEndpoints<T> {
             .http
             .post(&url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::TransactionSplit(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::TransactionSplit))?;
 
         let parsed_response: Response<TransactionSplitResponseData> =
-            serde_json::from_str(&response)
+            Response::parse(&response, self.keep_raw)
                 .map_err(|e| PaystackAPIError::TransactionSplit(e.to_string()))?;
 
         Ok(parsed_response)
@@ -215,11 +344,48 @@ impl<T: HttpClient + Default> TransactionSplitEndpoints<T> {
             .http
             .post(&url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::TransactionSplit(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::TransactionSplit))?;
 
-        let parsed_response: Response<String> = serde_json::from_str(&response)
+        let parsed_response: Response<String> = Response::parse(&response, self.keep_raw)
             .map_err(|e| PaystackAPIError::TransactionSplit(e.to_string()))?;
 
         Ok(parsed_response)
     }
+
+    /// Removes a subaccount from a transaction split using only its code, e.g. when a vendor is offboarded.
+    ///
+    /// # Arguments
+    /// * `split_id` - ID of the transaction split
+    /// * `subaccount_code` - Code of the subaccount to remove e.g. `ACCT_4hl4xenwpjy5wb`
+    ///
+    /// # Returns
+    /// A Result containing the split and subaccount with whether the subaccount was removed, or an error
+    pub async fn remove_split_subaccount(
+        &self,
+        split_id: &str,
+        subaccount_code: &str,
+    ) -> PaystackResult<RemovedSplitSubaccount> {
+        let response = self
+            .remove_subaccount_from_transaction_split(
+                split_id,
+                DeleteSubAccountBody {
+                    subaccount: subaccount_code.to_string(),
+                },
+            )
+            .await?;
+
+        Ok(Response {
+            status: response.status,
+            message: response.message,
+            data: Some(RemovedSplitSubaccount {
+                split_id: split_id.to_string(),
+                subaccount: subaccount_code.to_string(),
+                removed: response.status,
+            }),
+            meta: response.meta,
+            response_type: response.response_type,
+            code: response.code,
+            raw: response.raw,
+        })
+    }
 }
@@ -19,6 +19,8 @@ pub struct VirtualTerminalEndpoints<T: HttpClient + Default> {
     base_url: String,
     /// Http client for the route
     http: Arc<T>,
+    /// Whether the raw JSON body is kept on each response
+    keep_raw: bool,
 }
 
 impl<T: HttpClient + Default> VirtualTerminalEndpoints<T> {
@@ -36,9 +38,16 @@ impl<T: HttpClient + Default> VirtualTerminalEndpoints<T> {
             key: key.to_string(),
             base_url,
             http,
+            keep_raw: false,
         }
     }
 
+    /// Keeps the raw JSON body of each response in `Response::raw`, see `PaystackClient::with_keep_raw`.
+    pub fn with_keep_raw(mut self, keep_raw: bool) -> Self {
+        self.keep_raw = keep_raw;
+        self
+    }
+
     /// Creates a virtual terminal on your integration
     ///
     /// # Arguments
@@ -59,10 +68,10 @@ impl<T: HttpClient + Default> VirtualTerminalEndpoints<T> {
             .http
             .post(url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::VirtualTerminal(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::VirtualTerminal))?;
 
         let parsed_response: Response<VirtualTerminalResponseData> =
-            serde_json::from_str(&response)
+            Response::parse(&response, self.keep_raw)
                 .map_err(|e| PaystackAPIError::VirtualTerminal(e.to_string()))?;
 
         Ok(parsed_response)
@@ -91,10 +100,10 @@ impl<T: HttpClient + Default> VirtualTerminalEndpoints<T> {
             .http
             .get(url, &self.key, Some(&query))
             .await
-            .map_err(|e| PaystackAPIError::VirtualTerminal(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::VirtualTerminal))?;
 
         let parsed_response: Response<Vec<VirtualTerminalResponseData>> =
-            serde_json::from_str(&response)
+            Response::parse(&response, self.keep_raw)
                 .map_err(|e| PaystackAPIError::VirtualTerminal(e.to_string()))?;
 
         Ok(parsed_response)
@@ -117,10 +126,10 @@ impl<T: HttpClient + Default> VirtualTerminalEndpoints<T> {
             .http
             .get(&url, &self.key, None)
             .await
-            .map_err(|e| PaystackAPIError::VirtualTerminal(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::VirtualTerminal))?;
 
         let parsed_response: Response<VirtualTerminalResponseData> =
-            serde_json::from_str(&response)
+            Response::parse(&response, self.keep_raw)
                 .map_err(|e| PaystackAPIError::VirtualTerminal(e.to_string()))?;
 
         Ok(parsed_response)
@@ -148,10 +157,11 @@ impl<T: HttpClient + Default> VirtualTerminalEndpoints<T> {
             .http
             .put(&url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::VirtualTerminal(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::VirtualTerminal))?;
 
-        let parsed_response: Response<PhantomData<String>> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::VirtualTerminal(e.to_string()))?;
+        let parsed_response: Response<PhantomData<String>> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::VirtualTerminal(e.to_string()))?;
 
         Ok(parsed_response)
     }
@@ -174,10 +184,11 @@ impl<T: HttpClient + Default> VirtualTerminalEndpoints<T> {
             .http
             .put(&url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::VirtualTerminal(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::VirtualTerminal))?;
 
-        let parsed_response: Response<PhantomData<String>> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::VirtualTerminal(e.to_string()))?;
+        let parsed_response: Response<PhantomData<String>> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::VirtualTerminal(e.to_string()))?;
 
         Ok(parsed_response)
     }
@@ -204,10 +215,10 @@ impl<T: HttpClient + Default> VirtualTerminalEndpoints<T> {
             .http
             .post(&url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::VirtualTerminal(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::VirtualTerminal))?;
 
         let parsed_response: Response<Vec<DestinationResponse>> =
-            serde_json::from_str(&response)
+            Response::parse(&response, self.keep_raw)
                 .map_err(|e| PaystackAPIError::VirtualTerminal(e.to_string()))?;
 
         Ok(parsed_response)
@@ -235,10 +246,11 @@ impl<T: HttpClient + Default> VirtualTerminalEndpoints<T> {
             .http
             .post(&url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::VirtualTerminal(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::VirtualTerminal))?;
 
-        let parsed_response: Response<PhantomData<String>> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::VirtualTerminal(e.to_string()))?;
+        let parsed_response: Response<PhantomData<String>> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::VirtualTerminal(e.to_string()))?;
 
         Ok(parsed_response)
     }
@@ -265,10 +277,10 @@ impl<T: HttpClient + Default> VirtualTerminalEndpoints<T> {
             .http
             .put(&url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::VirtualTerminal(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::VirtualTerminal))?;
 
         let parsed_response: Response<TransactionSplitResponseData> =
-            serde_json::from_str(&response)
+            Response::parse(&response, self.keep_raw)
                 .map_err(|e| PaystackAPIError::VirtualTerminal(e.to_string()))?;
 
         Ok(parsed_response)
@@ -296,10 +308,11 @@ impl<T: HttpClient + Default> VirtualTerminalEndpoints<T> {
             .http
             .delete(&url, &self.key, &body)
             .await
-            .map_err(|e| PaystackAPIError::VirtualTerminal(e.to_string()))?;
+            .map_err(|e| PaystackAPIError::from_http(e, PaystackAPIError::VirtualTerminal))?;
 
-        let parsed_response: Response<PhantomData<String>> = serde_json::from_str(&response)
-            .map_err(|e| PaystackAPIError::VirtualTerminal(e.to_string()))?;
+        let parsed_response: Response<PhantomData<String>> =
+            Response::parse(&response, self.keep_raw)
+                .map_err(|e| PaystackAPIError::VirtualTerminal(e.to_string()))?;
 
         Ok(parsed_response)
     }
@@ -1,6 +1,7 @@
 //! Error
 //! ========
 //! This file contains the structs and definitions of the errors in this crate.
+use crate::{HttpError, HttpFailure};
 use thiserror::Error;
 
 /// Custom Error for the Paystack API
@@ -41,4 +42,183 @@ pub enum PaystackAPIError {
     Subscription(String),
     #[error("Refund Error: {0}")]
     Refund(String),
+    /// The request or configuration was rejected before being sent to Paystack
+    #[error("Validation Error: {0}")]
+    Validation(String),
+    /// The requested resource does not exist on the integration
+    #[error("Not Found: {0}")]
+    NotFound(String),
+    /// Paystack ignored a query filter the crate relies on, so the response cannot be trusted
+    #[error("Filter Not Honoured: {0}")]
+    FilterNotHonoured(String),
+    /// A webhook event that was not signed by Paystack or could not be read
+    #[error("Webhook Error: {0}")]
+    Webhook(String),
+    /// The response body is larger than the limit set on the HTTP client
+    #[error("Response Too Large: {0}")]
+    ResponseTooLarge(String),
+    /// The request was not sent because the circuit breaker of its endpoint is open
+    #[error("Circuit Open: {0}")]
+    CircuitOpen(String),
+    /// An operation waiting on Paystack, e.g. `TerminalEndpoints::await_event`, did not finish in the time it was given
+    #[error("Timeout: {0}")]
+    Timeout(String),
+    /// A request to Paystack timed out, could not connect, or got a response with an error status
+    #[error("{source}")]
+    Http {
+        /// How the request failed
+        failure: HttpFailure,
+        /// The error of the endpoint that made the request e.g. `PaystackAPIError::Transaction`
+        source: Box<PaystackAPIError>,
+    },
+}
+
+impl PaystackAPIError {
+    /// Whether the failed request may succeed if it is sent again.
+    ///
+    /// Only `PaystackAPIError::Http` errors can be retryable, see `HttpFailure::is_retryable`.
+    /// Every other error is not retryable, including validation errors,
+    /// responses that could not be deserialized and requests stopped by an open circuit breaker.
+    pub fn is_retryable(&self) -> bool {
+        self.http_failure()
+            .is_some_and(|failure| failure.is_retryable())
+    }
+
+    /// How the request failed, if the error comes from the HTTP client.
+    pub fn http_failure(&self) -> Option<HttpFailure> {
+        match self {
+            PaystackAPIError::Http { failure, .. } => Some(*failure),
+            PaystackAPIError::ResponseTooLarge(_) => Some(HttpFailure::ResponseTooLarge),
+            PaystackAPIError::CircuitOpen(_) => Some(HttpFailure::CircuitOpen),
+            _ => None,
+        }
+    }
+
+    /// Converts an error returned by the HTTP client into the error of the endpoint that made the request.
+    ///
+    /// Responses rejected for exceeding the size limit of the client become `ResponseTooLarge`,
+    /// and requests stopped by a circuit breaker become `CircuitOpen`. Timeouts, connection failures
+    /// and error statuses become `Http`, keeping the failure alongside the endpoint error.
+    /// Failures the client does not classify become the endpoint error.
+    pub(crate) fn from_http<E: HttpError>(
+        error: E,
+        endpoint_error: fn(String) -> PaystackAPIError,
+    ) -> PaystackAPIError {
+        let message = error.to_string();
+        match error.failure() {
+            HttpFailure::ResponseTooLarge => PaystackAPIError::ResponseTooLarge(message),
+            HttpFailure::CircuitOpen => PaystackAPIError::CircuitOpen(message),
+            HttpFailure::Other => endpoint_error(message),
+            failure => PaystackAPIError::Http {
+                failure,
+                source: Box::new(endpoint_error(message)),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ReqwestError;
+    use std::fmt::Display;
+    use std::time::Duration;
+
+    /// An HTTP client error that fails with the given failure
+    #[derive(Debug)]
+    struct FailedRequest(HttpFailure);
+
+    impl Display for FailedRequest {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "request failed: {:?}", self.0)
+        }
+    }
+
+    impl HttpError for FailedRequest {
+        fn failure(&self) -> HttpFailure {
+            self.0
+        }
+    }
+
+    fn http_error(failure: HttpFailure) -> PaystackAPIError {
+        PaystackAPIError::from_http(FailedRequest(failure), PaystackAPIError::Transaction)
+    }
+
+    #[test]
+    fn too_many_requests_is_retryable() {
+        assert!(http_error(HttpFailure::Status(429)).is_retryable());
+    }
+
+    #[test]
+    fn bad_request_is_not_retryable() {
+        let error = http_error(HttpFailure::Status(400));
+
+        assert!(!error.is_retryable());
+        assert_eq!(error.http_failure().and_then(|f| f.status()), Some(400));
+    }
+
+    #[test]
+    fn service_unavailable_is_retryable() {
+        assert!(http_error(HttpFailure::Status(503)).is_retryable());
+    }
+
+    #[test]
+    fn retryability_does_not_depend_on_the_message() {
+        let unclassified = PaystackAPIError::from_http(
+            "status code: 503 Service Unavailable".to_string(),
+            PaystackAPIError::Customer,
+        );
+
+        assert!(matches!(unclassified, PaystackAPIError::Customer(_)));
+        assert!(!unclassified.is_retryable());
+        assert!(!PaystackAPIError::Validation("status code: 503".to_string()).is_retryable());
+        assert!(!PaystackAPIError::Transaction("missing field `id`".to_string()).is_retryable());
+    }
+
+    #[test]
+    fn http_errors_keep_the_endpoint_error() {
+        let error = http_error(HttpFailure::Connect);
+
+        assert!(error.is_retryable());
+        assert!(matches!(
+            &error,
+            PaystackAPIError::Http { source, .. } if matches!(**source, PaystackAPIError::Transaction(_))
+        ));
+        assert_eq!(
+            error.to_string(),
+            "Transaction Error: request failed: Connect"
+        );
+    }
+
+    #[test]
+    fn oversized_responses_are_not_mapped_to_the_endpoint_error() {
+        let too_large = ReqwestError::ResponseTooLarge { limit: 1024 };
+
+        assert!(matches!(
+            PaystackAPIError::from_http(too_large, PaystackAPIError::Transaction),
+            PaystackAPIError::ResponseTooLarge(_)
+        ));
+        assert!(matches!(
+            http_error(HttpFailure::CircuitOpen),
+            PaystackAPIError::CircuitOpen(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn timeout_is_retryable() {
+        // A server that accepts the connection but never responds
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(50))
+            .build()
+            .unwrap();
+
+        let reqwest_error = ReqwestError::from(client.get(url).send().await.unwrap_err());
+        assert!(reqwest_error.is_retryable());
+
+        let error = PaystackAPIError::from_http(reqwest_error, PaystackAPIError::Transaction);
+        assert!(error.is_retryable());
+        assert_eq!(error.http_failure(), Some(HttpFailure::Timeout));
+    }
 }
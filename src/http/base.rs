@@ -1,6 +1,7 @@
+use crate::{HttpError, PAYSTACK_BASE_URL};
 use async_trait::async_trait;
 use serde_json::Value;
-use std::fmt::{Debug, Display};
+use std::fmt::Debug;
 
 /// A predefined type for the query type in the HTTP client.
 pub type Query<'a> = Vec<(&'a str, &'a str)>;
@@ -16,8 +17,8 @@ pub type Query<'a> = Vec<(&'a str, &'a str)>;
 
 #[async_trait]
 pub trait HttpClient: Debug + Default + Clone + Send {
-    /// HTTP error
-    type Error: Debug + Display;
+    /// HTTP error, which reports how the request failed
+    type Error: HttpError;
 
     /// Send http get request
     async fn get(
@@ -32,4 +33,26 @@ pub trait HttpClient: Debug + Default + Clone + Send {
     async fn put(&self, url: &str, api_key: &str, body: &Value) -> Result<String, Self::Error>;
     /// Send http delete request
     async fn delete(&self, url: &str, api_key: &str, body: &Value) -> Result<String, Self::Error>;
+
+    /// Download a file from a URL that is not part of the Paystack API, without the API key,
+    /// e.g. the pre-signed URL of a transaction export.
+    ///
+    /// Pre-signed URLs reject requests that carry an `Authorization` header, so no API key must be sent.
+    /// The default implementation calls `get` with an empty API key; override it if your `get`
+    /// sends the `Authorization` header even when the key is empty.
+    async fn download(&self, url: &str) -> Result<String, Self::Error>
+    where
+        Self: Sync,
+    {
+        self.get(url, "", None).await
+    }
+}
+
+/// Gets the API route from a request URL e.g. `transaction` from `https://api.paystack.co/transaction/verify/ref`
+pub(crate) fn endpoint_name(url: &str) -> &str {
+    let path = url.strip_prefix(PAYSTACK_BASE_URL).unwrap_or(url);
+    path.trim_start_matches('/')
+        .split(['/', '?'])
+        .next()
+        .unwrap_or_default()
 }
@@ -0,0 +1,450 @@
+//! Circuit breaker
+//! ===============
+//! An HTTP client that stops sending requests to an endpoint of the Paystack API
+//! after repeated failures, so an outage does not tie up the calling service.
+
+use super::base::{endpoint_name, Query};
+use super::errors::CIRCUIT_OPEN_ERROR;
+use crate::{HttpClient, HttpError, HttpFailure};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Display};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// When the circuit of an endpoint opens and how long it stays open
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    /// Number of consecutive failures that opens the circuit
+    pub failure_threshold: u32,
+    /// How long requests are stopped once the circuit is open, before a single request probes the endpoint
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    /// Opens after 5 consecutive failures, for 30 seconds
+    fn default() -> Self {
+        CircuitBreakerConfig {
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+/// The error of a `CircuitBreakerClient`
+#[derive(Debug)]
+pub enum CircuitBreakerError<E> {
+    /// The request was not sent because the circuit of the endpoint is open
+    Open {
+        /// The API route of the request e.g. `transaction`
+        endpoint: String,
+        /// Time left until a request may probe the endpoint again
+        retry_after: Duration,
+    },
+    /// The request was sent and failed
+    Http(E),
+}
+
+impl<E: Display> Display for CircuitBreakerError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CircuitBreakerError::Open {
+                endpoint,
+                retry_after,
+            } => write!(
+                f,
+                "{CIRCUIT_OPEN_ERROR}: requests to the {endpoint} endpoint are stopped for {retry_after:?} after repeated failures"
+            ),
+            CircuitBreakerError::Http(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl<E: Debug + Display> std::error::Error for CircuitBreakerError<E> {}
+
+impl<E: HttpError> HttpError for CircuitBreakerError<E> {
+    fn failure(&self) -> HttpFailure {
+        match self {
+            CircuitBreakerError::Open { .. } => HttpFailure::CircuitOpen,
+            CircuitBreakerError::Http(e) => e.failure(),
+        }
+    }
+}
+
+/// The state of the circuit of an endpoint
+#[derive(Debug, Default)]
+struct Circuit {
+    /// Consecutive failures while the circuit is closed
+    failures: u32,
+    /// When the circuit opened, if it is open
+    opened_at: Option<Instant>,
+    /// Whether a request is probing the endpoint after the cooldown
+    probing: bool,
+}
+
+/// An HTTP client that stops requests to an endpoint after repeated failures.
+///
+/// Each API route, e.g. `transaction` or `refund`, has its own circuit. Failures that are retryable,
+/// see `HttpFailure::is_retryable`, count as failures: timeouts, connection failures, `429` and `5xx` responses. After `failure_threshold` consecutive failures the circuit
+/// opens, and requests to the endpoint fail at once with `CircuitBreakerError::Open`, which the
+/// endpoints return as `PaystackAPIError::CircuitOpen`. Once the cooldown has passed, the circuit
+/// half-opens: a single request is sent to probe the endpoint, and closes the circuit if it succeeds
+/// or opens it again if it fails. Other responses, including `4xx` ones, close the circuit.
+///
+/// Clones share the state of the circuits.
+///
+/// # Examples
+///
+/// ```
+/// use paystack::{CircuitBreakerClient, CircuitBreakerConfig, PaystackClient, ReqwestClient};
+/// use std::time::Duration;
+///
+/// let http = CircuitBreakerClient::new(ReqwestClient::default(), CircuitBreakerConfig::default())
+///     .with_endpoint_config(
+///         "transaction",
+///         CircuitBreakerConfig {
+///             failure_threshold: 3,
+///             cooldown: Duration::from_secs(10),
+///         },
+///     );
+/// let client = PaystackClient::with_http("sk_test_xxxxxxxxxxxx".to_string(), http);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CircuitBreakerClient<C: HttpClient> {
+    /// Client that sends the requests
+    inner: C,
+    /// Configuration of the endpoints without their own
+    config: CircuitBreakerConfig,
+    /// Configuration of specific endpoints, by API route
+    endpoint_configs: HashMap<String, CircuitBreakerConfig>,
+    /// Circuit of each endpoint, by API route
+    circuits: Arc<Mutex<HashMap<String, Circuit>>>,
+}
+
+impl<C: HttpClient> CircuitBreakerClient<C> {
+    /// Creates a client that sends requests with `inner`, with the same configuration for every endpoint
+    pub fn new(inner: C, config: CircuitBreakerConfig) -> Self {
+        CircuitBreakerClient {
+            inner,
+            config,
+            endpoint_configs: HashMap::new(),
+            circuits: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Sets the configuration of a single endpoint.
+    ///
+    /// # Arguments
+    /// * `endpoint` - The API route e.g. `transaction` or `refund`
+    /// * `config` - When the circuit of the endpoint opens and for how long
+    pub fn with_endpoint_config(mut self, endpoint: &str, config: CircuitBreakerConfig) -> Self {
+        self.endpoint_configs.insert(endpoint.to_string(), config);
+        self
+    }
+
+    fn config_for(&self, endpoint: &str) -> CircuitBreakerConfig {
+        self.endpoint_configs
+            .get(endpoint)
+            .copied()
+            .unwrap_or(self.config)
+    }
+
+    async fn guard<F>(&self, url: &str, request: F) -> Result<String, CircuitBreakerError<C::Error>>
+    where
+        F: Future<Output = Result<String, C::Error>>,
+    {
+        let endpoint = endpoint_name(url);
+        let config = self.config_for(endpoint);
+
+        let probe = {
+            let mut circuits = self.circuits.lock().unwrap();
+            let circuit = circuits.entry(endpoint.to_string()).or_default();
+            match circuit.opened_at {
+                Some(opened_at) => {
+                    let elapsed = opened_at.elapsed();
+                    if elapsed < config.cooldown || circuit.probing {
+                        return Err(CircuitBreakerError::Open {
+                            endpoint: endpoint.to_string(),
+                            retry_after: config.cooldown.saturating_sub(elapsed),
+                        });
+                    }
+                    circuit.probing = true;
+                    Some(ProbeGuard {
+                        circuits: &self.circuits,
+                        endpoint,
+                    })
+                }
+                None => None,
+            }
+        };
+
+        let result = request.await;
+        let probing = probe.is_some();
+        drop(probe);
+        let failed = matches!(&result, Err(e) if e.failure().is_retryable());
+
+        let mut circuits = self.circuits.lock().unwrap();
+        let circuit = circuits.entry(endpoint.to_string()).or_default();
+        if !failed {
+            *circuit = Circuit::default();
+        } else if probing {
+            circuit.opened_at = Some(Instant::now());
+            log::warn!("The circuit of the {endpoint} endpoint opened again, the probe failed");
+        } else {
+            circuit.failures += 1;
+            if circuit.failures >= config.failure_threshold {
+                circuit.failures = 0;
+                circuit.opened_at = Some(Instant::now());
+                log::warn!(
+                    "The circuit of the {endpoint} endpoint opened after {} consecutive failures",
+                    config.failure_threshold
+                );
+            }
+        }
+
+        result.map_err(CircuitBreakerError::Http)
+    }
+}
+
+/// Clears the probe flag of a circuit when the probe ends, including when its future is dropped
+/// before the request completes, e.g. by a timeout. Otherwise the circuit would stay open for good.
+struct ProbeGuard<'a> {
+    circuits: &'a Mutex<HashMap<String, Circuit>>,
+    endpoint: &'a str,
+}
+
+impl Drop for ProbeGuard<'_> {
+    fn drop(&mut self) {
+        let mut circuits = self
+            .circuits
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(circuit) = circuits.get_mut(self.endpoint) {
+            circuit.probing = false;
+        }
+    }
+}
+
+#[async_trait]
+impl<C: HttpClient + Sync> HttpClient for CircuitBreakerClient<C> {
+    type Error = CircuitBreakerError<C::Error>;
+
+    async fn get(
+        &self,
+        url: &str,
+        api_key: &str,
+        query: Option<&Query>,
+    ) -> Result<String, Self::Error> {
+        self.guard(url, self.inner.get(url, api_key, query)).await
+    }
+
+    async fn post(&self, url: &str, api_key: &str, body: &Value) -> Result<String, Self::Error> {
+        self.guard(url, self.inner.post(url, api_key, body)).await
+    }
+
+    async fn put(&self, url: &str, api_key: &str, body: &Value) -> Result<String, Self::Error> {
+        self.guard(url, self.inner.put(url, api_key, body)).await
+    }
+
+    async fn delete(&self, url: &str, api_key: &str, body: &Value) -> Result<String, Self::Error> {
+        self.guard(url, self.inner.delete(url, api_key, body)).await
+    }
+
+    /// Downloads are not requests to a Paystack endpoint, so they bypass the circuits
+    async fn download(&self, url: &str) -> Result<String, Self::Error> {
+        self.inner
+            .download(url)
+            .await
+            .map_err(CircuitBreakerError::Http)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PaystackAPIError, RefundEndpoints, TransactionEndpoints};
+    use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+    /// An HTTP client that fails with the configured error, or succeeds if there is none,
+    /// and counts the requests it receives
+    #[derive(Debug, Clone, Default)]
+    struct ScriptedClient {
+        error: Arc<Mutex<Option<HttpFailure>>>,
+        requests: Arc<AtomicU32>,
+        stalled: Arc<AtomicBool>,
+    }
+
+    impl ScriptedClient {
+        fn fail_with(&self, error: Option<HttpFailure>) {
+            *self.error.lock().unwrap() = error;
+        }
+
+        /// Makes the requests hang until their future is dropped
+        fn stall(&self, stalled: bool) {
+            self.stalled.store(stalled, Ordering::SeqCst);
+        }
+
+        async fn respond(&self) -> Result<String, ScriptedError> {
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            if self.stalled.load(Ordering::SeqCst) {
+                std::future::pending::<()>().await;
+            }
+            match *self.error.lock().unwrap() {
+                Some(failure) => Err(ScriptedError(failure)),
+                None => Ok(r#"{"status": true, "message": "ok", "data": null}"#.to_string()),
+            }
+        }
+    }
+
+    /// The error of a `ScriptedClient`, failing with the configured failure
+    #[derive(Debug)]
+    struct ScriptedError(HttpFailure);
+
+    impl Display for ScriptedError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "request failed: {:?}", self.0)
+        }
+    }
+
+    impl HttpError for ScriptedError {
+        fn failure(&self) -> HttpFailure {
+            self.0
+        }
+    }
+
+    #[async_trait]
+    impl HttpClient for ScriptedClient {
+        type Error = ScriptedError;
+
+        async fn get(&self, _: &str, _: &str, _: Option<&Query>) -> Result<String, ScriptedError> {
+            self.respond().await
+        }
+
+        async fn post(&self, _: &str, _: &str, _: &Value) -> Result<String, ScriptedError> {
+            self.respond().await
+        }
+
+        async fn put(&self, _: &str, _: &str, _: &Value) -> Result<String, ScriptedError> {
+            self.respond().await
+        }
+
+        async fn delete(&self, _: &str, _: &str, _: &Value) -> Result<String, ScriptedError> {
+            self.respond().await
+        }
+    }
+
+    const TRANSACTION_URL: &str = "https://api.paystack.co/transaction/verify/ref";
+
+    fn breaker(inner: ScriptedClient) -> CircuitBreakerClient<ScriptedClient> {
+        CircuitBreakerClient::new(
+            inner,
+            CircuitBreakerConfig {
+                failure_threshold: 2,
+                cooldown: Duration::from_millis(50),
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn circuit_opens_after_repeated_failures_and_recovers() {
+        let inner = ScriptedClient::default();
+        inner.fail_with(Some(HttpFailure::Status(503)));
+        let client = breaker(inner.clone());
+
+        // Two failures open the circuit
+        for _ in 0..2 {
+            let error = client.get(TRANSACTION_URL, "key", None).await.unwrap_err();
+            assert!(matches!(error, CircuitBreakerError::Http(_)));
+        }
+
+        // Requests are now stopped without reaching the inner client
+        let error = client.get(TRANSACTION_URL, "key", None).await.unwrap_err();
+        assert!(matches!(
+            error,
+            CircuitBreakerError::Open { ref endpoint, .. } if endpoint == "transaction"
+        ));
+        assert_eq!(inner.requests.load(Ordering::SeqCst), 2);
+
+        // The endpoints report an open circuit with its own error
+        let key = Arc::new("sk_test_xxxxxxxxxxxx".to_string());
+        let transactions = TransactionEndpoints::new(key.clone(), Arc::new(client.clone()));
+        let res = transactions.verify_transaction("ref").await;
+        assert!(matches!(res, Err(PaystackAPIError::CircuitOpen(_))));
+
+        // Other endpoints have their own circuit
+        let refunds = RefundEndpoints::new(key, Arc::new(client.clone()));
+        assert!(matches!(
+            refunds.fetch_refund(1).await,
+            Err(PaystackAPIError::Http {
+                failure: HttpFailure::Status(503),
+                ..
+            })
+        ));
+
+        // After the cooldown, a successful probe closes the circuit
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        inner.fail_with(None);
+        assert!(client.get(TRANSACTION_URL, "key", None).await.is_ok());
+        assert!(client.get(TRANSACTION_URL, "key", None).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn failed_probe_opens_circuit_again() {
+        let inner = ScriptedClient::default();
+        inner.fail_with(Some(HttpFailure::Timeout));
+        let client = breaker(inner.clone());
+
+        for _ in 0..2 {
+            assert!(client.get(TRANSACTION_URL, "key", None).await.is_err());
+        }
+        tokio::time::sleep(Duration::from_millis(60)).await;
+
+        let probe = client.get(TRANSACTION_URL, "key", None).await.unwrap_err();
+        assert!(matches!(probe, CircuitBreakerError::Http(_)));
+        let error = client.get(TRANSACTION_URL, "key", None).await.unwrap_err();
+        assert!(matches!(error, CircuitBreakerError::Open { .. }));
+        assert_eq!(inner.requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn dropped_probe_lets_next_request_probe() {
+        let inner = ScriptedClient::default();
+        inner.fail_with(Some(HttpFailure::Status(503)));
+        let client = breaker(inner.clone());
+
+        for _ in 0..2 {
+            assert!(client.get(TRANSACTION_URL, "key", None).await.is_err());
+        }
+        tokio::time::sleep(Duration::from_millis(60)).await;
+
+        // The probe is abandoned by the caller before the endpoint answers
+        inner.stall(true);
+        let probe = tokio::time::timeout(
+            Duration::from_millis(10),
+            client.get(TRANSACTION_URL, "key", None),
+        )
+        .await;
+        assert!(probe.is_err());
+
+        // The next request probes the endpoint instead of being stopped
+        inner.stall(false);
+        inner.fail_with(None);
+        assert!(client.get(TRANSACTION_URL, "key", None).await.is_ok());
+        assert_eq!(inner.requests.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn client_errors_do_not_open_circuit() {
+        let inner = ScriptedClient::default();
+        inner.fail_with(Some(HttpFailure::Status(404)));
+        let client = breaker(inner.clone());
+
+        for _ in 0..3 {
+            let error = client.get(TRANSACTION_URL, "key", None).await.unwrap_err();
+            assert!(matches!(error, CircuitBreakerError::Http(_)));
+        }
+        assert_eq!(inner.requests.load(Ordering::SeqCst), 3);
+    }
+}
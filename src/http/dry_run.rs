@@ -0,0 +1,206 @@
+use super::base::Query;
+use crate::HttpClient;
+use async_trait::async_trait;
+use serde_json::Value;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+
+/// Response returned for every request made with the `DryRunClient`.
+/// Its `data` is empty so it can be parsed as the response of any endpoint.
+const DRY_RUN_RESPONSE: &str = r#"{"status":true,"message":"Dry run: the request was not sent"}"#;
+
+/// A request as it would have been sent to the Paystack API
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreparedRequest {
+    /// HTTP method of the request e.g. `POST`
+    pub method: String,
+    /// Full URL of the request, including the query string
+    pub url: String,
+    /// Headers of the request. The API key in the `Authorization` header is redacted.
+    pub headers: Vec<(String, String)>,
+    /// JSON body of the request. `None` for `GET` requests.
+    pub body: Option<Value>,
+}
+
+/// An HTTP client that records the requests it is given instead of sending them.
+///
+/// Every call succeeds with an empty response, so endpoint methods return a `Response` with no `data`.
+/// Clones share the recorded requests, so keep a clone to inspect them after making calls.
+///
+/// # Examples
+///
+/// ```
+/// use paystack::{DryRunClient, PaystackClient};
+///
+/// let dry_run = DryRunClient::default();
+/// let client = PaystackClient::with_http("sk_test_xxxxxxxxxxxx".to_string(), dry_run.clone());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DryRunClient {
+    requests: Arc<Mutex<Vec<PreparedRequest>>>,
+}
+
+impl DryRunClient {
+    /// All the requests prepared so far, oldest first
+    pub fn requests(&self) -> Vec<PreparedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// The most recently prepared request, if any
+    pub fn last_request(&self) -> Option<PreparedRequest> {
+        self.requests.lock().unwrap().last().cloned()
+    }
+
+    fn prepare(
+        &self,
+        method: &str,
+        url: &str,
+        api_key: &str,
+        query: Option<&Query>,
+        body: Option<&Value>,
+    ) -> Result<String, Infallible> {
+        let url = match query {
+            Some(query) if !query.is_empty() => reqwest::Url::parse_with_params(url, query)
+                .map(String::from)
+                .unwrap_or_else(|_| url.to_string()),
+            _ => url.to_string(),
+        };
+        let headers = vec![
+            (
+                String::from("Authorization"),
+                format!("Bearer {}", redact_key(api_key)),
+            ),
+            (
+                String::from("Content-Type"),
+                String::from("application/json"),
+            ),
+        ];
+
+        self.requests.lock().unwrap().push(PreparedRequest {
+            method: method.to_string(),
+            url,
+            headers,
+            body: body.cloned(),
+        });
+
+        Ok(DRY_RUN_RESPONSE.to_string())
+    }
+}
+
+/// Hides an API key, keeping only its type and mode e.g. `sk_test_****`
+fn redact_key(api_key: &str) -> String {
+    let visible = ["sk_test_", "sk_live_", "pk_test_", "pk_live_"]
+        .into_iter()
+        .find(|prefix| api_key.starts_with(prefix))
+        .unwrap_or_default();
+
+    format!("{visible}****")
+}
+
+#[async_trait]
+impl HttpClient for DryRunClient {
+    type Error = Infallible;
+
+    async fn get(
+        &self,
+        url: &str,
+        api_key: &str,
+        query: Option<&Query>,
+    ) -> Result<String, Self::Error> {
+        self.prepare("GET", url, api_key, query, None)
+    }
+
+    async fn post(&self, url: &str, api_key: &str, body: &Value) -> Result<String, Self::Error> {
+        self.prepare("POST", url, api_key, None, Some(body))
+    }
+
+    async fn put(&self, url: &str, api_key: &str, body: &Value) -> Result<String, Self::Error> {
+        self.prepare("PUT", url, api_key, None, Some(body))
+    }
+
+    async fn delete(&self, url: &str, api_key: &str, body: &Value) -> Result<String, Self::Error> {
+        self.prepare("DELETE", url, api_key, None, Some(body))
+    }
+
+    /// Records the download as a `GET` request without the `Authorization` header
+    async fn download(&self, url: &str) -> Result<String, Self::Error> {
+        self.requests.lock().unwrap().push(PreparedRequest {
+            method: String::from("GET"),
+            url: url.to_string(),
+            headers: Vec::new(),
+            body: None,
+        });
+
+        Ok(DRY_RUN_RESPONSE.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn dry_run_client_records_download_without_api_key() {
+        let client = DryRunClient::default();
+        let url = "https://s3.eu-west-1.amazonaws.com/files.paystack.co/exports/transactions.csv";
+
+        client.download(url).await.unwrap();
+
+        let request = client.last_request().unwrap();
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.url, url);
+        assert!(request.headers.is_empty());
+    }
+
+    #[tokio::test]
+    async fn dry_run_client_records_get_with_query() {
+        let client = DryRunClient::default();
+        let query = vec![("perPage", "10"), ("status", "success")];
+
+        let res = client
+            .get(
+                "https://api.paystack.co/transaction",
+                "sk_test_xxxxxxxxxxxx",
+                Some(&query),
+            )
+            .await
+            .unwrap();
+
+        let request = client.last_request().unwrap();
+        assert_eq!(res, DRY_RUN_RESPONSE);
+        assert_eq!(request.method, "GET");
+        assert_eq!(
+            request.url,
+            "https://api.paystack.co/transaction?perPage=10&status=success"
+        );
+        assert_eq!(request.body, None);
+    }
+
+    #[tokio::test]
+    async fn dry_run_client_redacts_api_key() {
+        let client = DryRunClient::default();
+
+        client
+            .post(
+                "https://api.paystack.co/refund",
+                "sk_live_0123456789abcdef",
+                &Value::Null,
+            )
+            .await
+            .unwrap();
+        client
+            .post("https://api.paystack.co/refund", "not-a-key", &Value::Null)
+            .await
+            .unwrap();
+
+        let requests = client.requests();
+        assert_eq!(
+            requests[0].headers[0],
+            (
+                "Authorization".to_string(),
+                "Bearer sk_live_****".to_string()
+            )
+        );
+        assert_eq!(requests[1].headers[0].1, "Bearer ****");
+    }
+}
@@ -1,3 +1,5 @@
+use std::convert::Infallible;
+use std::fmt::{Debug, Display};
 use thiserror::Error;
 
 /// An error enum to hold errors from reqwest client
@@ -5,7 +7,7 @@ use thiserror::Error;
 pub enum ReqwestError {
     /// Default HTTP error from the Reqwest crate.
     /// This happens when the request cannot be completed.
-    #[error("request: {0}")]
+    #[error("{}", describe_request_error(.0))]
     Reqwest(#[from] reqwest::Error),
 
     /// The initial request was successful, but the status code is in the 400
@@ -13,4 +15,132 @@ pub enum ReqwestError {
     /// We are only interested in the status code of this error
     #[error("status code: {}", reqwest::Response::status(.0))]
     StatusCode(reqwest::Response),
+
+    /// The response body is larger than the limit set on the client.
+    /// The rest of the body is not read.
+    #[error("{RESPONSE_TOO_LARGE_ERROR}: the body exceeds the limit of {limit} bytes")]
+    ResponseTooLarge {
+        /// The maximum number of bytes the client reads from a response
+        limit: usize,
+    },
+}
+
+impl ReqwestError {
+    /// Whether the request may succeed if it is sent again.
+    /// See `HttpFailure::is_retryable` for the failures considered retryable.
+    pub fn is_retryable(&self) -> bool {
+        self.failure().is_retryable()
+    }
+
+    /// Whether the request timed out while connecting to Paystack.
+    pub fn is_connect_timeout(&self) -> bool {
+        matches!(self, ReqwestError::Reqwest(e) if e.is_timeout() && e.is_connect())
+    }
+
+    /// Whether the request timed out after connecting, while waiting for the response.
+    pub fn is_read_timeout(&self) -> bool {
+        matches!(self, ReqwestError::Reqwest(e) if e.is_timeout() && !e.is_connect())
+    }
+}
+
+impl HttpError for ReqwestError {
+    fn failure(&self) -> HttpFailure {
+        match self {
+            ReqwestError::Reqwest(e) if e.is_timeout() && e.is_connect() => {
+                HttpFailure::ConnectTimeout
+            }
+            ReqwestError::Reqwest(e) if e.is_timeout() => HttpFailure::Timeout,
+            ReqwestError::Reqwest(e) if e.is_connect() => HttpFailure::Connect,
+            ReqwestError::Reqwest(_) => HttpFailure::Other,
+            ReqwestError::StatusCode(response) => HttpFailure::Status(response.status().as_u16()),
+            ReqwestError::ResponseTooLarge { .. } => HttpFailure::ResponseTooLarge,
+        }
+    }
+}
+
+/// How a request sent by an `HttpClient` failed.
+///
+/// It is kept on `PaystackAPIError::Http`, so errors are classified from the failure itself
+/// rather than from their message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum HttpFailure {
+    /// The request timed out after connecting, while waiting for the response
+    Timeout,
+    /// The request timed out while connecting to Paystack
+    ConnectTimeout,
+    /// The request could not connect to Paystack
+    Connect,
+    /// The response has an error status code, e.g. `404` or `503`
+    Status(u16),
+    /// The response body is larger than the limit set on the client
+    ResponseTooLarge,
+    /// The request was not sent because the circuit breaker of its endpoint is open
+    CircuitOpen,
+    /// Any other failure, including failures the client does not classify
+    Other,
+}
+
+impl HttpFailure {
+    /// Whether the request may succeed if it is sent again.
+    ///
+    /// The retryable failures are:
+    /// * requests that timed out or could not connect to Paystack
+    /// * responses with status code `429 Too Many Requests`
+    /// * responses with a `5xx` status code (`500`-`599`)
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            HttpFailure::Timeout | HttpFailure::ConnectTimeout | HttpFailure::Connect => true,
+            HttpFailure::Status(code) => *code == 429 || (500..=599).contains(code),
+            HttpFailure::ResponseTooLarge | HttpFailure::CircuitOpen | HttpFailure::Other => false,
+        }
+    }
+
+    /// The status code of the response, if the request got one
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            HttpFailure::Status(code) => Some(*code),
+            _ => None,
+        }
+    }
+}
+
+/// The error of an `HttpClient`, which reports how the request failed.
+///
+/// Errors that do not implement `failure` are reported as `HttpFailure::Other`,
+/// so they are never retried or counted as outages.
+pub trait HttpError: Debug + Display {
+    /// How the request failed
+    fn failure(&self) -> HttpFailure {
+        HttpFailure::Other
+    }
+}
+
+impl HttpError for String {}
+
+impl HttpError for Infallible {}
+
+/// Prefix of the message of a request that timed out after connecting
+pub(crate) const TIMEOUT_ERROR: &str = "request timed out";
+/// Prefix of the message of a request that timed out while connecting
+pub(crate) const CONNECT_TIMEOUT_ERROR: &str = "connection timed out";
+/// Prefix of the message of a request that could not connect to the server
+pub(crate) const CONNECT_ERROR: &str = "connection failed";
+/// Prefix of the message of a response that exceeded the size limit of the client
+pub(crate) const RESPONSE_TOO_LARGE_ERROR: &str = "response too large";
+/// Prefix of the message of a request stopped by an open circuit breaker
+pub(crate) const CIRCUIT_OPEN_ERROR: &str = "circuit open";
+
+/// Describes a reqwest error, marking timeouts and connection failures so they
+/// can still be recognised once the error has been converted to a string.
+fn describe_request_error(error: &reqwest::Error) -> String {
+    if error.is_timeout() && error.is_connect() {
+        format!("{CONNECT_TIMEOUT_ERROR}: {error}")
+    } else if error.is_timeout() {
+        format!("{TIMEOUT_ERROR}: {error}")
+    } else if error.is_connect() {
+        format!("{CONNECT_ERROR}: {error}")
+    } else {
+        format!("request: {error}")
+    }
 }
@@ -3,10 +3,18 @@
 //! If both are selected, a compiler error is raised.
 
 pub mod base;
+pub mod circuit_breaker;
+pub mod dry_run;
 pub mod errors;
+#[cfg(feature = "request-observer")]
+pub mod observer;
 pub mod reqwest;
 
 // public re-export
 pub use base::HttpClient;
-pub use errors::ReqwestError;
-pub use reqwest::ReqwestClient;
+pub use circuit_breaker::{CircuitBreakerClient, CircuitBreakerConfig, CircuitBreakerError};
+pub use dry_run::{DryRunClient, PreparedRequest};
+pub use errors::{HttpError, HttpFailure, ReqwestError};
+#[cfg(feature = "request-observer")]
+pub use observer::{ObservedClient, RequestInfo, RequestObserver, RequestOutcome};
+pub use reqwest::{ReqwestClient, DEFAULT_MAX_RESPONSE_BYTES};
@@ -0,0 +1,303 @@
+//! Request observer
+//! ================
+//! Hooks for recording metrics about the requests sent to the Paystack API,
+//! without the crate depending on a specific metrics backend.
+
+use super::base::{endpoint_name, Query};
+use crate::{HttpClient, HttpError};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::fmt::{self, Debug};
+use std::future::Future;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Details of a request sent to the Paystack API
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestInfo<'a> {
+    /// The API route of the request e.g. `transaction` or `refund`
+    pub endpoint: &'a str,
+    /// HTTP method of the request e.g. `POST`
+    pub method: &'a str,
+    /// Full URL of the request, without the query string
+    pub url: &'a str,
+}
+
+/// The outcome of a request sent to the Paystack API
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestOutcome {
+    /// Time taken to get a response or an error
+    pub duration: Duration,
+    /// If the request got a successful (2xx) response
+    pub success: bool,
+    /// Status code of the failed response. `None` for successful requests and requests that got no response.
+    pub status: Option<u16>,
+    /// Message of the error, if the request failed
+    pub error: Option<String>,
+}
+
+/// Observes every request made by an `ObservedClient`, e.g. to record latency and error metrics.
+///
+/// A panic in an observer is caught and logged, so it never affects the request.
+pub trait RequestObserver: Send + Sync {
+    /// Called before the request is sent
+    fn before_request(&self, _request: &RequestInfo) {}
+
+    /// Called after the request completes, whether it succeeded or failed
+    fn after_request(&self, request: &RequestInfo, outcome: &RequestOutcome);
+}
+
+/// An HTTP client that reports every request it sends to a `RequestObserver`.
+///
+/// # Examples
+///
+/// ```
+/// use paystack::{ObservedClient, PaystackClient, ReqwestClient, RequestInfo, RequestObserver, RequestOutcome};
+/// use std::sync::Arc;
+///
+/// struct LatencyLogger;
+///
+/// impl RequestObserver for LatencyLogger {
+///     fn after_request(&self, request: &RequestInfo, outcome: &RequestOutcome) {
+///         println!("{} {} took {:?}", request.method, request.endpoint, outcome.duration);
+///     }
+/// }
+///
+/// let http = ObservedClient::new(ReqwestClient::default(), Arc::new(LatencyLogger));
+/// let client = PaystackClient::with_http("sk_test_xxxxxxxxxxxx".to_string(), http);
+/// ```
+#[derive(Clone, Default)]
+pub struct ObservedClient<C: HttpClient> {
+    /// Client that sends the requests
+    inner: C,
+    /// Observer notified of each request. The default client has none.
+    observer: Option<Arc<dyn RequestObserver>>,
+}
+
+impl<C: HttpClient> ObservedClient<C> {
+    /// Creates a client that sends requests with `inner` and reports them to `observer`
+    pub fn new(inner: C, observer: Arc<dyn RequestObserver>) -> Self {
+        ObservedClient {
+            inner,
+            observer: Some(observer),
+        }
+    }
+
+    async fn observe<F>(&self, method: &str, url: &str, request: F) -> Result<String, C::Error>
+    where
+        F: Future<Output = Result<String, C::Error>>,
+    {
+        let Some(observer) = &self.observer else {
+            return request.await;
+        };
+
+        let info = RequestInfo {
+            endpoint: endpoint_name(url),
+            method,
+            url,
+        };
+        notify(|| observer.before_request(&info));
+
+        let start = Instant::now();
+        let result = request.await;
+        let outcome = RequestOutcome {
+            duration: start.elapsed(),
+            success: result.is_ok(),
+            status: result.as_ref().err().and_then(|e| e.failure().status()),
+            error: result.as_ref().err().map(|e| e.to_string()),
+        };
+        notify(|| observer.after_request(&info, &outcome));
+
+        result
+    }
+}
+
+impl<C: HttpClient> Debug for ObservedClient<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObservedClient")
+            .field("inner", &self.inner)
+            .field(
+                "observer",
+                &self.observer.as_ref().map(|_| "RequestObserver"),
+            )
+            .finish()
+    }
+}
+
+/// Calls the observer, logging instead of unwinding if it panics
+fn notify(call: impl FnOnce()) {
+    if catch_unwind(AssertUnwindSafe(call)).is_err() {
+        log::warn!("A request observer panicked, the panic was ignored");
+    }
+}
+
+#[async_trait]
+impl<C: HttpClient + Sync> HttpClient for ObservedClient<C> {
+    type Error = C::Error;
+
+    async fn get(
+        &self,
+        url: &str,
+        api_key: &str,
+        query: Option<&Query>,
+    ) -> Result<String, Self::Error> {
+        self.observe("GET", url, self.inner.get(url, api_key, query))
+            .await
+    }
+
+    async fn post(&self, url: &str, api_key: &str, body: &Value) -> Result<String, Self::Error> {
+        self.observe("POST", url, self.inner.post(url, api_key, body))
+            .await
+    }
+
+    async fn put(&self, url: &str, api_key: &str, body: &Value) -> Result<String, Self::Error> {
+        self.observe("PUT", url, self.inner.put(url, api_key, body))
+            .await
+    }
+
+    async fn delete(&self, url: &str, api_key: &str, body: &Value) -> Result<String, Self::Error> {
+        self.observe("DELETE", url, self.inner.delete(url, api_key, body))
+            .await
+    }
+
+    /// Downloads are not requests to the Paystack API, so they are not reported to the observer
+    async fn download(&self, url: &str) -> Result<String, Self::Error> {
+        self.inner.download(url).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DryRunClient, HttpFailure, RefundEndpoints, TransactionEndpoints};
+    use std::convert::Infallible;
+    use std::sync::Mutex;
+
+    /// Counts the requests it observes and keeps their outcomes
+    #[derive(Default)]
+    struct CountingObserver {
+        before: Mutex<u32>,
+        after: Mutex<Vec<(String, String, RequestOutcome)>>,
+    }
+
+    impl RequestObserver for CountingObserver {
+        fn before_request(&self, _request: &RequestInfo) {
+            *self.before.lock().unwrap() += 1;
+        }
+
+        fn after_request(&self, request: &RequestInfo, outcome: &RequestOutcome) {
+            self.after.lock().unwrap().push((
+                request.endpoint.to_string(),
+                request.method.to_string(),
+                outcome.clone(),
+            ));
+        }
+    }
+
+    /// An HTTP client whose requests always fail with a 404
+    #[derive(Debug, Clone, Default)]
+    struct NotFoundClient;
+
+    /// The error of a `NotFoundClient`
+    #[derive(Debug)]
+    struct NotFound;
+
+    impl fmt::Display for NotFound {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "status code: 404 Not Found")
+        }
+    }
+
+    impl HttpError for NotFound {
+        fn failure(&self) -> HttpFailure {
+            HttpFailure::Status(404)
+        }
+    }
+
+    #[async_trait]
+    impl HttpClient for NotFoundClient {
+        type Error = NotFound;
+
+        async fn get(&self, _: &str, _: &str, _: Option<&Query>) -> Result<String, NotFound> {
+            Err(NotFound)
+        }
+
+        async fn post(&self, _: &str, _: &str, _: &Value) -> Result<String, NotFound> {
+            Err(NotFound)
+        }
+
+        async fn put(&self, _: &str, _: &str, _: &Value) -> Result<String, NotFound> {
+            Err(NotFound)
+        }
+
+        async fn delete(&self, _: &str, _: &str, _: &Value) -> Result<String, NotFound> {
+            Err(NotFound)
+        }
+    }
+
+    /// An observer that always panics
+    struct PanickingObserver;
+
+    impl RequestObserver for PanickingObserver {
+        fn after_request(&self, _request: &RequestInfo, _outcome: &RequestOutcome) {
+            panic!("observer failure");
+        }
+    }
+
+    #[tokio::test]
+    async fn observer_sees_successful_and_failed_requests() {
+        let observer = Arc::new(CountingObserver::default());
+        let key = Arc::new("sk_test_xxxxxxxxxxxx".to_string());
+
+        let success = ObservedClient::new(DryRunClient::default(), observer.clone());
+        let refund = RefundEndpoints::new(key.clone(), Arc::new(success));
+        refund.fetch_refund(3018284).await.unwrap();
+
+        let failure = ObservedClient::new(NotFoundClient, observer.clone());
+        let transaction = TransactionEndpoints::new(key, Arc::new(failure));
+        assert!(transaction.verify_transaction("missing").await.is_err());
+
+        let after = observer.after.lock().unwrap();
+        assert_eq!(*observer.before.lock().unwrap(), 2);
+        assert_eq!(after.len(), 2);
+
+        let (endpoint, method, outcome) = &after[0];
+        assert_eq!((endpoint.as_str(), method.as_str()), ("refund", "GET"));
+        assert!(outcome.success);
+        assert_eq!(outcome.status, None);
+
+        let (endpoint, method, outcome) = &after[1];
+        assert_eq!((endpoint.as_str(), method.as_str()), ("transaction", "GET"));
+        assert!(!outcome.success);
+        assert_eq!(outcome.status, Some(404));
+        assert_eq!(outcome.error.as_deref(), Some("status code: 404 Not Found"));
+    }
+
+    #[tokio::test]
+    async fn panicking_observer_does_not_affect_request() {
+        let http = ObservedClient::new(DryRunClient::default(), Arc::new(PanickingObserver));
+
+        let res: Result<String, Infallible> = http
+            .get(
+                "https://api.paystack.co/refund",
+                "sk_test_xxxxxxxxxxxx",
+                None,
+            )
+            .await;
+
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn endpoint_name_is_the_api_route() {
+        assert_eq!(
+            endpoint_name("https://api.paystack.co/transaction/verify/ref"),
+            "transaction"
+        );
+        assert_eq!(
+            endpoint_name("https://api.paystack.co/subscription?perPage=50"),
+            "subscription"
+        );
+    }
+}
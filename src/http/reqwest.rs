@@ -1,40 +1,210 @@
 use super::ReqwestError;
 use crate::http::base::Query;
-use crate::HttpClient;
+use crate::{HttpClient, PaystackAPIError};
 use async_trait::async_trait;
 use reqwest::{Client, Method, RequestBuilder};
 use serde_json::Value;
 use std::fmt::Debug;
+use std::time::Duration;
+
+/// The default maximum size of a response body read by `ReqwestClient`, 10 MiB.
+pub const DEFAULT_MAX_RESPONSE_BYTES: usize = 10 * 1024 * 1024;
 
 #[derive(Debug, Clone)]
 pub struct ReqwestClient {
     /// An instance of the client to perform the http requests with
     client: Client,
+    /// The maximum number of bytes read from a response body
+    max_response_bytes: usize,
+    /// Timeout of the whole request, from connecting to reading the end of the body
+    timeout: Option<Duration>,
+    /// Timeout of establishing the connection to Paystack
+    connect_timeout: Option<Duration>,
+    /// Timeout of each read of the response
+    read_timeout: Option<Duration>,
 }
 
 impl Default for ReqwestClient {
+    /// Creates a client with the default configuration.
+    ///
+    /// # Panics
+    /// Panics with a description of the problem if the client cannot be created, see `ReqwestClient::try_new`.
     fn default() -> Self {
-        let client = reqwest::ClientBuilder::new().build().unwrap();
-
-        Self { client }
+        Self::try_new().unwrap_or_else(|e| panic!("{e}"))
     }
 }
 
 impl ReqwestClient {
+    /// Creates a client with the default configuration, checking that it can make HTTPS requests to Paystack.
+    ///
+    /// A TLS backend is required, through the `default-tls` (enabled by default) or `rustls-tls` feature.
+    /// Requests must be made from within a Tokio runtime, which also drives the timeouts.
+    ///
+    /// # Returns
+    /// A Result containing the client, or a validation error if no TLS backend is enabled
+    /// or the underlying reqwest client cannot be built
+    pub fn try_new() -> Result<Self, PaystackAPIError> {
+        check_tls_backend()?;
+        let client = build_client(reqwest::ClientBuilder::new())?;
+
+        Ok(Self {
+            client,
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            timeout: None,
+            connect_timeout: None,
+            read_timeout: None,
+        })
+    }
+
+    /// Sets the timeout of the whole request, from connecting to reading the end of the response.
+    /// There is no timeout by default.
+    ///
+    /// # Arguments
+    /// * `timeout` - The maximum duration of a request
+    ///
+    /// # Returns
+    /// A Result containing the client, or a validation error if the underlying reqwest client cannot be built
+    pub fn with_timeout(mut self, timeout: Duration) -> Result<Self, PaystackAPIError> {
+        self.timeout = Some(timeout);
+        self.rebuild()
+    }
+
+    /// Sets the timeout of establishing the connection to Paystack, e.g. to fail fast on network issues.
+    /// A request that times out while connecting fails with an error for which
+    /// `ReqwestError::is_connect_timeout` is true.
+    ///
+    /// # Arguments
+    /// * `timeout` - The maximum duration of connecting
+    ///
+    /// # Returns
+    /// A Result containing the client, or a validation error if the underlying reqwest client cannot be built
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Result<Self, PaystackAPIError> {
+        self.connect_timeout = Some(timeout);
+        self.rebuild()
+    }
+
+    /// Sets the timeout of each read of the response, e.g. to bound how long Paystack may take to respond
+    /// once connected. A request that times out after connecting fails with an error for which
+    /// `ReqwestError::is_read_timeout` is true.
+    ///
+    /// # Arguments
+    /// * `timeout` - The maximum duration to wait for data from Paystack
+    ///
+    /// # Returns
+    /// A Result containing the client, or a validation error if the underlying reqwest client cannot be built
+    ///
+    /// # Example
+    /// ```
+    /// use paystack::{PaystackAPIError, PaystackClient, ReqwestClient};
+    /// use std::time::Duration;
+    ///
+    /// # fn main() -> Result<(), PaystackAPIError> {
+    /// let http = ReqwestClient::try_new()?
+    ///     .with_connect_timeout(Duration::from_secs(2))?
+    ///     .with_read_timeout(Duration::from_secs(20))?
+    ///     .with_timeout(Duration::from_secs(30))?;
+    /// let client = PaystackClient::with_http("sk_test_xxxxxxxxxxxx".to_string(), http);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_read_timeout(mut self, timeout: Duration) -> Result<Self, PaystackAPIError> {
+        self.read_timeout = Some(timeout);
+        self.rebuild()
+    }
+
+    /// The timeout of the whole request, if set
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// The timeout of establishing the connection, if set
+    pub fn connect_timeout(&self) -> Option<Duration> {
+        self.connect_timeout
+    }
+
+    /// The timeout of each read of the response, if set
+    pub fn read_timeout(&self) -> Option<Duration> {
+        self.read_timeout
+    }
+
+    /// Builds the reqwest client again with the configured timeouts.
+    fn rebuild(mut self) -> Result<Self, PaystackAPIError> {
+        let mut builder = reqwest::ClientBuilder::new();
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.read_timeout {
+            builder = builder.read_timeout(timeout);
+        }
+
+        self.client = build_client(builder)?;
+        Ok(self)
+    }
+
+    /// Sets the maximum number of bytes read from a response body.
+    ///
+    /// Requests whose response is larger fail with `ReqwestError::ResponseTooLarge`,
+    /// which the endpoints return as `PaystackAPIError::ResponseTooLarge`.
+    /// Defaults to `DEFAULT_MAX_RESPONSE_BYTES`.
+    ///
+    /// # Arguments
+    /// * `limit` - The maximum size of a response body in bytes
+    ///
+    /// # Example
+    /// ```
+    /// use paystack::{PaystackClient, ReqwestClient};
+    ///
+    /// let http = ReqwestClient::default().with_max_response_bytes(1024 * 1024);
+    /// let client = PaystackClient::with_http("sk_test_xxxxxxxxxxxx".to_string(), http);
+    /// ```
+    pub fn with_max_response_bytes(mut self, limit: usize) -> Self {
+        self.max_response_bytes = limit;
+        self
+    }
+
+    /// Reads the body of the response, stopping as soon as it exceeds the size limit.
+    async fn read_body(&self, mut response: reqwest::Response) -> Result<String, ReqwestError> {
+        let limit = self.max_response_bytes;
+        let too_large = ReqwestError::ResponseTooLarge { limit };
+
+        if response
+            .content_length()
+            .is_some_and(|length| length > limit as u64)
+        {
+            return Err(too_large);
+        }
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            if body.len() + chunk.len() > limit {
+                return Err(too_large);
+            }
+            body.extend_from_slice(&chunk);
+        }
+
+        Ok(String::from_utf8_lossy(&body).into_owned())
+    }
+
     async fn send_request<D: Fn(RequestBuilder) -> RequestBuilder>(
         &self,
         method: Method,
         url: &str,
-        auth_key: &str,
+        auth_key: Option<&str>,
         add_data: D,
     ) -> Result<String, ReqwestError> {
         // configure the request object
         let mut request = self
             .client
             .request(method.clone(), url)
-            .bearer_auth(auth_key)
             .header("Content-Type", "application/json");
 
+        if let Some(auth_key) = auth_key {
+            request = request.bearer_auth(auth_key);
+        }
+
         // Configure the request for the specific type (get/post/put/delete)
         request = add_data(request);
 
@@ -44,13 +214,32 @@ impl ReqwestClient {
 
         // Checking that we get a 200 range response
         if response.status().is_success() {
-            response.text().await.map_err(Into::into)
+            self.read_body(response).await
         } else {
             Err(ReqwestError::StatusCode(response))
         }
     }
 }
 
+/// Builds the reqwest client, describing why it could not be built.
+fn build_client(builder: reqwest::ClientBuilder) -> Result<Client, PaystackAPIError> {
+    builder
+        .build()
+        .map_err(|e| PaystackAPIError::Validation(format!("unable to build the HTTP client: {e}")))
+}
+
+/// Checks that a TLS backend is enabled, since Paystack is only reachable over HTTPS.
+fn check_tls_backend() -> Result<(), PaystackAPIError> {
+    if cfg!(any(feature = "default-tls", feature = "rustls-tls")) {
+        Ok(())
+    } else {
+        Err(PaystackAPIError::Validation(String::from(
+            "no TLS backend is enabled, so requests to Paystack cannot be made over HTTPS. \
+             Enable the `default-tls` or `rustls-tls` feature of paystack-rs",
+        )))
+    }
+}
+
 #[async_trait]
 impl HttpClient for ReqwestClient {
     type Error = ReqwestError;
@@ -61,7 +250,7 @@ impl HttpClient for ReqwestClient {
         api_key: &str,
         query: Option<&Query>,
     ) -> Result<String, Self::Error> {
-        self.send_request(Method::GET, url, api_key, |req| {
+        self.send_request(Method::GET, url, Some(api_key), |req| {
             if let Some(query) = query {
                 req.query(query)
             } else {
@@ -72,24 +261,29 @@ impl HttpClient for ReqwestClient {
     }
 
     async fn post(&self, url: &str, api_key: &str, body: &Value) -> Result<String, Self::Error> {
-        self.send_request(Method::POST, url, api_key, |req| req.json(body))
+        self.send_request(Method::POST, url, Some(api_key), |req| req.json(body))
             .await
     }
 
     async fn put(&self, url: &str, api_key: &str, body: &Value) -> Result<String, Self::Error> {
-        self.send_request(Method::PUT, url, api_key, |req| req.json(body))
+        self.send_request(Method::PUT, url, Some(api_key), |req| req.json(body))
             .await
     }
 
     async fn delete(&self, url: &str, api_key: &str, body: &Value) -> Result<String, Self::Error> {
-        self.send_request(Method::DELETE, url, api_key, |req| req.json(body))
+        self.send_request(Method::DELETE, url, Some(api_key), |req| req.json(body))
             .await
     }
+
+    async fn download(&self, url: &str) -> Result<String, Self::Error> {
+        self.send_request(Method::GET, url, None, |req| req).await
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(any(feature = "default-tls", feature = "rustls-tls"))]
     #[tokio::test]
     async fn reqwest_client_cannot_get_unauthorized() {
         // Set
@@ -111,10 +305,14 @@ mod tests {
                 ReqwestError::StatusCode(code) => {
                     assert_eq!(code.status(), 401);
                 }
+                ReqwestError::ResponseTooLarge { .. } => {
+                    panic!("the error response should not be read")
+                }
             }
         }
     }
 
+    #[cfg(any(feature = "default-tls", feature = "rustls-tls"))]
     #[tokio::test]
     async fn reqwest_client_can_get() {
         // Set
@@ -136,4 +334,111 @@ mod tests {
             }
         }
     }
+
+    #[cfg(any(feature = "default-tls", feature = "rustls-tls"))]
+    #[test]
+    fn reqwest_client_can_be_created_with_tls_backend() {
+        assert!(ReqwestClient::try_new().is_ok());
+    }
+
+    #[cfg(not(any(feature = "default-tls", feature = "rustls-tls")))]
+    #[test]
+    fn reqwest_client_requires_tls_backend() {
+        let error = ReqwestClient::try_new().unwrap_err();
+
+        assert!(matches!(error, PaystackAPIError::Validation(_)));
+        let message = error.to_string();
+        assert!(
+            message.contains("no TLS backend"),
+            "unexpected error: {message}"
+        );
+        assert!(
+            message.contains("rustls-tls"),
+            "unexpected error: {message}"
+        );
+    }
+
+    /// Serves a single response with a chunked body of `chunks` chunks of 1 KiB each.
+    #[cfg(any(feature = "default-tls", feature = "rustls-tls"))]
+    async fn serve_chunked_body(chunks: usize) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0; 1024];
+            let _ = socket.read(&mut request).await;
+
+            let head = "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nTransfer-Encoding: chunked\r\n\r\n";
+            let _ = socket.write_all(head.as_bytes()).await;
+            let chunk = format!("400\r\n{}\r\n", "a".repeat(1024));
+            for _ in 0..chunks {
+                if socket.write_all(chunk.as_bytes()).await.is_err() {
+                    return;
+                }
+            }
+            let _ = socket.write_all(b"0\r\n\r\n").await;
+        });
+
+        url
+    }
+
+    #[cfg(any(feature = "default-tls", feature = "rustls-tls"))]
+    #[tokio::test]
+    async fn reqwest_client_rejects_response_larger_than_limit() {
+        // Set
+        let url = serve_chunked_body(64).await;
+        let client = ReqwestClient::default().with_max_response_bytes(16 * 1024);
+
+        // Run
+        let res = client.get(&url, "fake-key", None).await;
+
+        // Assert
+        assert!(matches!(
+            res,
+            Err(ReqwestError::ResponseTooLarge { limit: 16384 })
+        ));
+    }
+
+    #[cfg(any(feature = "default-tls", feature = "rustls-tls"))]
+    #[tokio::test]
+    async fn reqwest_client_reads_response_within_limit() {
+        // Set
+        let url = serve_chunked_body(4).await;
+        let client = ReqwestClient::default().with_max_response_bytes(16 * 1024);
+
+        // Run
+        let res = client.get(&url, "fake-key", None).await;
+
+        // Assert
+        assert_eq!(res.unwrap().len(), 4 * 1024);
+    }
+
+    #[cfg(any(feature = "default-tls", feature = "rustls-tls"))]
+    #[tokio::test]
+    async fn reqwest_client_applies_connect_and_read_timeouts() {
+        // Set
+        // A server that accepts the connection but never responds
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let client = ReqwestClient::default()
+            .with_connect_timeout(Duration::from_secs(5))
+            .unwrap()
+            .with_read_timeout(Duration::from_millis(50))
+            .unwrap();
+
+        // Run
+        let res = client.get(&url, "fake-key", None).await;
+
+        // Assert
+        assert_eq!(client.connect_timeout(), Some(Duration::from_secs(5)));
+        assert_eq!(client.read_timeout(), Some(Duration::from_millis(50)));
+        assert_eq!(client.timeout(), None);
+        let error = res.unwrap_err();
+        assert!(error.is_read_timeout());
+        assert!(!error.is_connect_timeout());
+        assert!(error.to_string().starts_with("request timed out"));
+    }
 }
@@ -13,6 +13,10 @@
 //!     paystack-rs = "1.6.0"
 //! ```
 //!
+//! Requests are sent over HTTPS, so a TLS backend is required. The `default-tls` feature, enabled by default,
+//! uses the platform's native TLS library. To use rustls instead, disable the default features and enable `rustls-tls`.
+//! Without either, `ReqwestClient::try_new` returns a validation error.
+//!
 //! ## Usage
 //!
 //! Initializing an instance of the Paystack client and creating a transaction.
@@ -71,7 +75,9 @@ pub mod errors;
 pub mod http;
 pub mod macros;
 pub mod models;
+pub mod prelude;
 pub mod utils;
+pub mod webhook;
 
 // public re-export of modules
 pub use client::*;
@@ -80,6 +86,7 @@ pub use errors::*;
 pub use http::*;
 pub use models::*;
 pub use utils::*;
+pub use webhook::*;
 
 /// Custom result type for the Paystack API
 pub type PaystackResult<T> = Result<Response<T>, PaystackAPIError>;
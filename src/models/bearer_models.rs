@@ -1,13 +1,15 @@
 //! Bearer Type
 //! =================
 //! This file contains the charge bearer option for the paystack API.
+//! The same `Bearer` is used wherever the API lets you choose who bears the transaction fees:
+//! transaction splits, transaction initialization and charge authorization.
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Represents the type of bearer for a charge.
 ///
-/// The `BearerType` enum defines the possible types of bearers for a charge, indicating who
+/// The `Bearer` enum defines the possible types of bearers for a charge, indicating who
 /// is responsible for the transaction split.
 ///
 /// # Variants
@@ -20,21 +22,21 @@ use std::fmt;
 /// # Examples
 ///
 /// ```
-/// use paystack::BearerType;
+/// use paystack::Bearer;
 ///
-/// let subaccount_bearer = BearerType::Subaccount;
-/// let account_bearer = BearerType::Account;
-/// let all_proportional_bearer = BearerType::AllProportional;
-/// let all_bearer = BearerType::All;
+/// let subaccount_bearer = Bearer::Subaccount;
+/// let account_bearer = Bearer::Account;
+/// let all_proportional_bearer = Bearer::AllProportional;
+/// let all_bearer = Bearer::All;
 ///
 /// println!("{:?}", subaccount_bearer); // Prints: Subaccount
 /// ```
 ///
-/// The example demonstrates the usage of the `BearerType` enum, creating instances of each variant
+/// The example demonstrates the usage of the `Bearer` enum, creating instances of each variant
 /// and printing their debug representation.
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
-pub enum BearerType {
+pub enum Bearer {
     /// The subaccount bears the transaction split
     #[default]
     Subaccount,
@@ -47,14 +49,39 @@ pub enum BearerType {
     All,
 }
 
-impl fmt::Display for BearerType {
+impl fmt::Display for Bearer {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let lowercase_string = match self {
-            BearerType::Subaccount => "subaccount",
-            BearerType::Account => "account",
-            BearerType::AllProportional => "all-proportional",
-            BearerType::All => "all",
+            Bearer::Subaccount => "subaccount",
+            Bearer::Account => "account",
+            Bearer::AllProportional => "all-proportional",
+            Bearer::All => "all",
         };
         write!(f, "{lowercase_string}")
     }
 }
+
+/// The previous name of `Bearer`.
+#[deprecated(note = "use `Bearer` instead")]
+pub type BearerType = Bearer;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bearer_round_trips() {
+        let bearers = [
+            (Bearer::Subaccount, "\"subaccount\""),
+            (Bearer::Account, "\"account\""),
+            (Bearer::AllProportional, "\"all-proportional\""),
+            (Bearer::All, "\"all\""),
+        ];
+
+        for (bearer, json) in bearers {
+            assert_eq!(serde_json::to_string(&bearer).unwrap(), json);
+            assert_eq!(serde_json::from_str::<Bearer>(json).unwrap(), bearer);
+            assert_eq!(format!("\"{bearer}\""), json);
+        }
+    }
+}
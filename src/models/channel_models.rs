@@ -69,8 +69,100 @@ impl fmt::Display for Channel {
             Channel::Qr => "qr",
             Channel::MobileMoney => "mobile_money",
             Channel::BankTransfer => "bank_transfer",
-            Channel::ApplePay => "mobile_money",
+            Channel::ApplePay => "apple_pay",
         };
         write!(f, "{lower_case}")
     }
 }
+
+/// The channel a transaction was paid with, as returned by Paystack.
+///
+/// Unlike `Channel`, which is used to choose the channels offered to a customer,
+/// it covers every channel a payment can be made with.
+/// Values not known to this crate are kept in the `Unknown` variant instead of failing deserialization.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum PaymentChannel {
+    /// Debit or credit card
+    #[default]
+    Card,
+    /// Bank account, through the bank interface
+    Bank,
+    /// USSD code
+    Ussd,
+    /// QR code
+    Qr,
+    /// Mobile money
+    MobileMoney,
+    /// Bank transfer
+    BankTransfer,
+    /// Electronic funds transfer
+    Eft,
+    /// Apple Pay
+    ApplePay,
+    /// A channel not yet supported by this crate
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+impl fmt::Display for PaymentChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let channel = match self {
+            PaymentChannel::Card => "card",
+            PaymentChannel::Bank => "bank",
+            PaymentChannel::Ussd => "ussd",
+            PaymentChannel::Qr => "qr",
+            PaymentChannel::MobileMoney => "mobile_money",
+            PaymentChannel::BankTransfer => "bank_transfer",
+            PaymentChannel::Eft => "eft",
+            PaymentChannel::ApplePay => "apple_pay",
+            PaymentChannel::Unknown(channel) => channel,
+        };
+        write!(f, "{channel}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn payment_channel_round_trips() {
+        let channels = [
+            (PaymentChannel::Card, "\"card\""),
+            (PaymentChannel::Bank, "\"bank\""),
+            (PaymentChannel::Ussd, "\"ussd\""),
+            (PaymentChannel::Qr, "\"qr\""),
+            (PaymentChannel::MobileMoney, "\"mobile_money\""),
+            (PaymentChannel::BankTransfer, "\"bank_transfer\""),
+            (PaymentChannel::Eft, "\"eft\""),
+            (PaymentChannel::ApplePay, "\"apple_pay\""),
+        ];
+
+        for (channel, json) in channels {
+            assert_eq!(serde_json::to_string(&channel).unwrap(), json);
+            assert_eq!(
+                serde_json::from_str::<PaymentChannel>(json).unwrap(),
+                channel
+            );
+            assert_eq!(format!("\"{channel}\""), json);
+        }
+    }
+
+    #[test]
+    fn unknown_payment_channel_is_kept() {
+        let channel: PaymentChannel = serde_json::from_str("\"dedicated_nuban\"").unwrap();
+
+        assert_eq!(
+            channel,
+            PaymentChannel::Unknown("dedicated_nuban".to_string())
+        );
+    }
+
+    #[test]
+    fn channel_displays_as_sent_to_paystack() {
+        assert_eq!(Channel::ApplePay.to_string(), "apple_pay");
+        assert_eq!(Channel::MobileMoney.to_string(), "mobile_money");
+    }
+}
@@ -3,7 +3,8 @@
 //! This file contains all the structs and definitions needed to
 //! create charges using the Paystack API.
 
-use crate::{Channel, Currency};
+use crate::utils::empty_object_as_none;
+use crate::{Bearer, Channel, Currency, PaymentChannel, SplitInfo, TransactionStatus};
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
@@ -31,8 +32,10 @@ pub struct ChargeRequest {
     /// Sample: {"custom_fields":[{"display_name":"Cart ID","variable_name": "cart_id","value": "8393"}]}
     #[builder(setter(strip_option), default)]
     metadata: Option<String>,
-    /// Send us 'card' or 'bank' or 'card','bank' as an array to specify what options to show the user paying
+    /// Send us 'card' or 'bank' or 'card','bank' as an array to specify what options to show the user paying.
+    /// Sent to Paystack as `channels`.
     #[builder(setter(strip_option), default)]
+    #[serde(rename = "channels")]
     channel: Option<Vec<Channel>>,
     /// The code for the subaccount that owns the payment. e.g. `ACCT_8f4s1eq7ml6rlzj`
     #[builder(setter(strip_option), default)]
@@ -44,7 +47,7 @@ pub struct ChargeRequest {
     transaction_charge: Option<u32>,
     /// Who bears Paystack charges? account or subaccount (defaults to account).
     #[builder(setter(strip_option), default)]
-    bearer: Option<String>,
+    bearer: Option<Bearer>,
     /// If you are making a scheduled charge call, it is a good idea to queue them so the processing system does not
     /// get overloaded causing transaction processing errors.
     /// Send queue:true to take advantage of our queued charging.
@@ -58,16 +61,118 @@ pub struct ChargeResponseData {
     pub amount: u64,
     pub currency: String,
     pub transaction_date: String,
-    pub status: String,
+    pub status: TransactionStatus,
     pub reference: String,
     pub metadata: Option<String>,
     pub gateway_response: String,
     pub message: Option<String>,
-    pub channel: String,
+    /// Channel the authorization was charged on e.g. `card`
+    pub channel: PaymentChannel,
     pub ip_address: Option<String>,
     pub fees: u64,
+    /// The authorization that was charged
     pub authorization: Authorization,
     pub customer: CustomerResponseData,
     pub plan: Option<String>,
     pub id: Option<u64>,
+    /// The split the charge was settled with, if the authorization was charged against a split.
+    #[serde(default, deserialize_with = "empty_object_as_none")]
+    pub split: Option<SplitInfo>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn can_deserialize_split_charge_authorization_response() -> Result<(), Box<dyn Error>> {
+        let charge: ChargeResponseData = serde_json::from_str(
+            r#"{
+                "amount": 35247,
+                "currency": "NGN",
+                "transaction_date": "2024-08-22T10:53:49.000Z",
+                "status": "success",
+                "reference": "0m7frfnr47ezyxl",
+                "metadata": "",
+                "gateway_response": "Approved",
+                "message": null,
+                "channel": "card",
+                "ip_address": null,
+                "fees": 529,
+                "authorization": {
+                    "authorization_code": "AUTH_uh8bcl3zbn",
+                    "bin": "408408",
+                    "last4": "4081",
+                    "exp_month": "12",
+                    "exp_year": "2030",
+                    "channel": "card",
+                    "card_type": "visa ",
+                    "bank": "TEST BANK",
+                    "country_code": "NG",
+                    "brand": "visa",
+                    "reusable": true,
+                    "signature": "SIG_yEXu7dLBeqG0kU7g95Ke",
+                    "account_name": null
+                },
+                "customer": {
+                    "id": 181873746,
+                    "email": "demo@test.com",
+                    "customer_code": "CUS_1rkzaqsv4rrhqo6"
+                },
+                "plan": null,
+                "id": 4099490251,
+                "split": {
+                    "split_code": "SPL_98WF13Eb3w",
+                    "subaccounts": [
+                        { "subaccount": "ACCT_6uujpqtzmnufzkw", "amount": 10574 },
+                        { "subaccount": "ACCT_eg4sob4590pq9vb", "amount": 7049 }
+                    ]
+                }
+            }"#,
+        )?;
+
+        assert_eq!(charge.channel, PaymentChannel::Card);
+        assert_eq!(
+            charge.authorization.authorization_code,
+            Some("AUTH_uh8bcl3zbn".to_string())
+        );
+        let split = charge.split.unwrap();
+        let shares = split.subaccount_shares();
+        assert_eq!(shares.len(), 2);
+        assert_eq!(
+            shares[0].subaccount_code,
+            Some("ACCT_6uujpqtzmnufzkw".to_string())
+        );
+        assert_eq!(shares[1].amount, Some(7049));
+
+        Ok(())
+    }
+
+    #[test]
+    fn charge_authorization_response_without_split_has_none() -> Result<(), Box<dyn Error>> {
+        let charge: ChargeResponseData = serde_json::from_str(
+            r#"{
+                "amount": 35247,
+                "currency": "NGN",
+                "transaction_date": "2024-08-22T10:53:49.000Z",
+                "status": "success",
+                "reference": "0m7frfnr47ezyxl",
+                "gateway_response": "Approved",
+                "channel": "card",
+                "fees": 529,
+                "authorization": {},
+                "customer": {
+                    "id": 181873746,
+                    "email": "demo@test.com",
+                    "customer_code": "CUS_1rkzaqsv4rrhqo6"
+                },
+                "split": {}
+            }"#,
+        )?;
+
+        assert!(charge.split.is_none());
+
+        Ok(())
+    }
 }
@@ -2,6 +2,7 @@
 //! ===============
 //! This file contains the currency options for the paystack API.
 
+use crate::PaystackAPIError;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -73,3 +74,105 @@ impl fmt::Display for Currency {
         write!(f, "{currency}")
     }
 }
+
+impl Currency {
+    /// Number of decimal places of the currency's subunit, as defined by ISO 4217.
+    ///
+    /// Amounts sent to Paystack are in the subunit of the currency, e.g. kobo for NGN.
+    /// Zero-decimal currencies such as XOF have no subunit, so their amounts are in the currency itself.
+    /// `EMPTY` uses two decimal places, like most currencies.
+    pub fn exponent(&self) -> u32 {
+        match self {
+            Currency::XOF => 0,
+            Currency::NGN
+            | Currency::GHS
+            | Currency::USD
+            | Currency::ZAR
+            | Currency::KES
+            | Currency::EMPTY => 2,
+        }
+    }
+
+    /// Converts an amount in the currency's major unit, e.g. `"150.50"` Naira, to its subunit e.g. `15050` kobo.
+    ///
+    /// # Arguments
+    /// * `major` - The amount as a decimal string. It cannot have more decimal places than the currency.
+    ///
+    /// # Returns
+    /// A Result containing the amount in the subunit, or a validation error if the amount is not valid
+    pub fn to_subunit(&self, major: &str) -> Result<u64, PaystackAPIError> {
+        let invalid = || {
+            PaystackAPIError::Validation(format!(
+                "{major:?} is not a valid {self} amount with at most {} decimal places",
+                self.exponent()
+            ))
+        };
+
+        let (whole, fraction) = major.trim().split_once('.').unwrap_or((major.trim(), ""));
+        let exponent = self.exponent() as usize;
+        if whole.is_empty()
+            || fraction.len() > exponent
+            || !whole
+                .chars()
+                .chain(fraction.chars())
+                .all(|c| c.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+
+        let whole: u64 = whole.parse().map_err(|_| invalid())?;
+        let fraction: u64 = format!("{fraction:0<exponent$}").parse().unwrap_or(0);
+        whole
+            .checked_mul(10u64.pow(self.exponent()))
+            .and_then(|amount| amount.checked_add(fraction))
+            .ok_or_else(invalid)
+    }
+
+    /// Formats an amount in the currency's subunit, e.g. `15050` kobo, in its major unit e.g. `"150.50"`.
+    pub fn to_major(&self, subunit: u64) -> String {
+        let exponent = self.exponent();
+        if exponent == 0 {
+            return subunit.to_string();
+        }
+
+        let divisor = 10u64.pow(exponent);
+        format!(
+            "{}.{:0width$}",
+            subunit / divisor,
+            subunit % divisor,
+            width = exponent as usize
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_decimal_currency_converts_to_and_from_subunit() {
+        assert_eq!(Currency::NGN.exponent(), 2);
+        assert_eq!(Currency::NGN.to_subunit("150.50").unwrap(), 15050);
+        assert_eq!(Currency::NGN.to_subunit("150.5").unwrap(), 15050);
+        assert_eq!(Currency::NGN.to_subunit("150").unwrap(), 15000);
+        assert_eq!(Currency::NGN.to_major(15050), "150.50");
+        assert_eq!(Currency::USD.to_major(7), "0.07");
+    }
+
+    #[test]
+    fn zero_decimal_currency_converts_to_and_from_subunit() {
+        assert_eq!(Currency::XOF.exponent(), 0);
+        assert_eq!(Currency::XOF.to_subunit("5000").unwrap(), 5000);
+        assert_eq!(Currency::XOF.to_major(5000), "5000");
+        assert!(Currency::XOF.to_subunit("5000.50").is_err());
+    }
+
+    #[test]
+    fn invalid_amounts_are_rejected() {
+        assert!(Currency::NGN.to_subunit("").is_err());
+        assert!(Currency::NGN.to_subunit("-1").is_err());
+        assert!(Currency::NGN.to_subunit("1.005").is_err());
+        assert!(Currency::NGN.to_subunit("1e3").is_err());
+        assert!(Currency::NGN.to_subunit("184467440737095516.16").is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fmt;
 
 use derive_builder::Builder;
@@ -40,6 +41,55 @@ pub struct CustomerMetaData {
     pub calling_code: Option<String>,
 }
 
+/// The successful transactions of a customer, as computed by `CustomersEndpoints::summary`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct CustomerSummary {
+    /// Number of successful transactions
+    pub transaction_count: u64,
+    /// Total amount spent in each currency, keyed by the currency code e.g. `NGN`,
+    /// in the lowest denomination of the currency
+    pub total_spend: HashMap<String, u64>,
+}
+
+/// The result of a customer identification started with `CustomersEndpoints::validate_customer`.
+///
+/// It is read from the `customeridentification.success` and `customeridentification.failed`
+/// webhook events with `WebhookEvent::customer_identification`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct CustomerIdentificationResult {
+    /// Whether the customer was identified. It comes from the name of the event, not its data.
+    #[serde(skip)]
+    pub identified: bool,
+    /// Code of the customer, to match the event with the validation request
+    pub customer_code: String,
+    /// Email of the customer
+    pub email: String,
+    /// The identification details that were checked, partly masked
+    pub identification: IdentificationDetails,
+    /// Why the customer could not be identified, for failed identifications
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// The identification details of a customer, as sent in the customer identification events.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct IdentificationDetails {
+    /// Two-letter country code of the identification document e.g. `NG`
+    pub country: String,
+    /// Type of identification e.g. `bank_account`
+    #[serde(rename = "type")]
+    pub identification_type: String,
+    /// Masked Bank Verification Number of the customer
+    #[serde(default)]
+    pub bvn: Option<String>,
+    /// Masked bank account number of the customer
+    #[serde(default)]
+    pub account_number: Option<String>,
+    /// Code of the bank of the account
+    #[serde(default)]
+    pub bank_code: Option<String>,
+}
+
 /// This struct constains the data for creating a customer in your integration
 #[derive(Debug, Clone, Serialize, Default, Deserialize, Builder)]
 pub struct CreateCustomerRequest {
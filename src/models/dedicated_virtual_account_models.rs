@@ -2,6 +2,7 @@ use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
 use super::{Currency, CustomerResponseData};
+use crate::utils::empty_object_as_none;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default, Builder)]
 pub struct DedicatedVirtualAccountRequest {
@@ -56,6 +57,8 @@ pub struct DedicatedVirtualAccountResponseData {
     pub updated_at: String,
     pub assignment: Assignment,
     pub customer: Option<CustomerResponseData>,
+    /// The split currently applied to transactions on the account, if any.
+    #[serde(default, deserialize_with = "empty_object_as_none")]
     pub split_config: Option<SplitConfig>,
 }
 
@@ -76,9 +79,30 @@ pub struct Assignment {
     pub integration: u64,
     pub assignee_id: u64,
     pub assignee_type: String,
+    /// If the account has expired. Temporary accounts from some providers expire.
     pub expired: bool,
     pub account_type: String,
-    pub assinged_at: String,
+    pub assigned_at: String,
+    /// When the account expires or expired, for temporary accounts.
+    #[serde(default)]
+    pub expired_at: Option<String>,
+}
+
+impl DedicatedVirtualAccountResponseData {
+    /// If the account can be shown to a customer to pay into: it is active and has not expired.
+    pub fn is_usable(&self) -> bool {
+        self.active && !self.assignment.expired
+    }
+
+    /// When the account expires or expired, parsed from the assignment's `expired_at`.
+    /// Returns `None` if the account does not expire or the timestamp cannot be parsed.
+    #[cfg(feature = "chrono")]
+    pub fn expiry(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        let expired_at = self.assignment.expired_at.as_deref()?;
+        chrono::DateTime::parse_from_rfc3339(expired_at)
+            .ok()
+            .map(|expiry| expiry.with_timezone(&chrono::Utc))
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -122,3 +146,74 @@ pub struct SplitDedicatedAccountTransactionRequest {
     #[builder(setter(strip_option), default)]
     pub preferred_bank: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dedicated_account(active: bool, expired: bool) -> serde_json::Value {
+        serde_json::json!({
+            "bank": { "name": "Test Bank", "id": 20, "slug": "test-bank" },
+            "account_name": "PAYSTACK/John Doe",
+            "account_number": "1234567890",
+            "assigned": true,
+            "currency": "NGN",
+            "metadata": null,
+            "active": active,
+            "id": 22495,
+            "created_at": "2024-11-03T11:18:13.000Z",
+            "updated_at": "2024-11-04T11:18:13.000Z",
+            "assignment": {
+                "integration": 463433,
+                "assignee_id": 17328797,
+                "assignee_type": "Customer",
+                "expired": expired,
+                "account_type": "PAY-WITH-TRANSFER",
+                "assigned_at": "2024-11-03T11:18:13.176Z",
+                "expired_at": "2024-11-04T11:18:13.000Z"
+            }
+        })
+    }
+
+    #[test]
+    fn expired_dedicated_account_is_not_usable() {
+        let account: DedicatedVirtualAccountResponseData =
+            serde_json::from_value(dedicated_account(true, true)).unwrap();
+
+        assert!(account.assignment.expired);
+        assert_eq!(
+            account.assignment.expired_at.as_deref(),
+            Some("2024-11-04T11:18:13.000Z")
+        );
+        assert!(!account.is_usable());
+    }
+
+    #[test]
+    fn only_active_unexpired_dedicated_account_is_usable() {
+        let usable: DedicatedVirtualAccountResponseData =
+            serde_json::from_value(dedicated_account(true, false)).unwrap();
+        let inactive: DedicatedVirtualAccountResponseData =
+            serde_json::from_value(dedicated_account(false, false)).unwrap();
+
+        assert!(usable.is_usable());
+        assert!(!inactive.is_usable());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn can_parse_dedicated_account_expiry() {
+        use chrono::TimeZone;
+
+        let account: DedicatedVirtualAccountResponseData =
+            serde_json::from_value(dedicated_account(true, true)).unwrap();
+
+        assert_eq!(
+            account.expiry(),
+            Some(
+                chrono::Utc
+                    .with_ymd_and_hms(2024, 11, 4, 11, 18, 13)
+                    .unwrap()
+            )
+        );
+    }
+}
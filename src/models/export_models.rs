@@ -0,0 +1,181 @@
+//! Transaction Export
+//! ==================
+//! This file contains the typed rows of the CSV file produced by `TransactionEndpoints::export_transaction`.
+
+use crate::PaystackAPIError;
+use serde::{Deserialize, Serialize};
+
+/// A transaction read from a transaction export file.
+///
+/// Amounts are kept as written in the export, in the major unit of the currency e.g. `500.00`.
+/// Use `Currency::to_subunit` to convert them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExportedTransaction {
+    /// Reference of the transaction
+    pub reference: String,
+    /// Amount of the transaction in the major unit of the currency
+    pub amount: String,
+    /// Status of the transaction e.g. `success`
+    pub status: String,
+    /// Currency code of the transaction, if the export includes it
+    pub currency: Option<String>,
+    /// Time the transaction was paid
+    pub paid_at: Option<String>,
+    /// Channel the transaction was paid with e.g. `card`
+    pub channel: Option<String>,
+    /// Email of the customer
+    pub customer_email: Option<String>,
+    /// Fees charged on the transaction in the major unit of the currency
+    pub fees: Option<String>,
+}
+
+/// The names each column can have in the header of the export, after normalization,
+/// in order of priority.
+const REFERENCE: &[&str] = &["reference", "transaction_reference", "ref"];
+const AMOUNT: &[&str] = &["amount", "amount_paid", "transaction_amount"];
+const STATUS: &[&str] = &["status", "transaction_status"];
+const CURRENCY: &[&str] = &["currency"];
+const PAID_AT: &[&str] = &["paid_at", "paid_on", "date_paid"];
+const CHANNEL: &[&str] = &["channel", "payment_channel"];
+const CUSTOMER_EMAIL: &[&str] = &["customer_email", "email"];
+const FEES: &[&str] = &["fees", "fee", "paystack_fees"];
+
+/// Parses the CSV file downloaded from the path returned by `TransactionEndpoints::export_transaction`.
+///
+/// Columns are found by their header rather than their position, so the order of the columns
+/// and the case or spacing of their names do not matter e.g. `Customer Email` or `customer_email`.
+/// If several columns match, the one with the preferred name is used e.g. `Customer Email` over `Email`.
+/// Columns that are not needed are ignored.
+///
+/// # Arguments
+/// * `bytes` - The content of the export file
+///
+/// # Returns
+/// A Result containing the exported transactions, or an error if the file is not valid CSV or
+/// lacks the reference, amount or status column
+pub fn parse_transaction_export(
+    bytes: &[u8],
+) -> Result<Vec<ExportedTransaction>, PaystackAPIError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(bytes);
+
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| PaystackAPIError::Transaction(e.to_string()))?
+        .iter()
+        .map(normalize_header)
+        .collect();
+    let column = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|name| headers.iter().position(|h| h == name))
+    };
+    let required = |names: &[&str]| {
+        column(names).ok_or_else(|| {
+            PaystackAPIError::Transaction(format!("the export has no `{}` column", names[0]))
+        })
+    };
+
+    let (reference, amount, status) = (required(REFERENCE)?, required(AMOUNT)?, required(STATUS)?);
+    let (currency, paid_at, channel, customer_email, fees) = (
+        column(CURRENCY),
+        column(PAID_AT),
+        column(CHANNEL),
+        column(CUSTOMER_EMAIL),
+        column(FEES),
+    );
+
+    let mut transactions = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| PaystackAPIError::Transaction(e.to_string()))?;
+        let field = |index: Option<usize>| {
+            index
+                .and_then(|i| record.get(i))
+                .filter(|value| !value.is_empty())
+                .map(String::from)
+        };
+
+        transactions.push(ExportedTransaction {
+            reference: field(Some(reference)).unwrap_or_default(),
+            amount: field(Some(amount)).unwrap_or_default(),
+            status: field(Some(status)).unwrap_or_default(),
+            currency: field(currency),
+            paid_at: field(paid_at),
+            channel: field(channel),
+            customer_email: field(customer_email),
+            fees: field(fees),
+        });
+    }
+
+    Ok(transactions)
+}
+
+/// Lowercases a header and replaces spaces and punctuation with `_`, e.g. `Customer Email` to `customer_email`.
+fn normalize_header(header: &str) -> String {
+    header
+        .trim_start_matches('\u{feff}')
+        .trim()
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_transaction_export() {
+        let export = "\u{feff}Paid At,Reference,Status,Amount,Currency,Channel,Customer Email,Fees,Gateway Response\n\
+            2024-08-22 09:15:02,T592386937184729,success,500.00,NGN,card,demo@test.com,7.50,Approved\n\
+            2024-08-22 10:01:44,re4lyvq3s3,abandoned,200.00,NGN,,customer@email.com,,\n";
+
+        let transactions = parse_transaction_export(export.as_bytes()).unwrap();
+
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(
+            transactions[0],
+            ExportedTransaction {
+                reference: "T592386937184729".to_string(),
+                amount: "500.00".to_string(),
+                status: "success".to_string(),
+                currency: Some("NGN".to_string()),
+                paid_at: Some("2024-08-22 09:15:02".to_string()),
+                channel: Some("card".to_string()),
+                customer_email: Some("demo@test.com".to_string()),
+                fees: Some("7.50".to_string()),
+            }
+        );
+        assert_eq!(transactions[1].channel, None);
+        assert_eq!(transactions[1].fees, None);
+    }
+
+    #[test]
+    fn columns_are_picked_by_alias_priority() {
+        let export = "Customer,Reference,Status,Amount,Transaction Date,Email,Customer Email\n\
+            Ada Obi,T592386937184729,success,500.00,2024-08-22,ada@work.com,ada@test.com\n";
+
+        let transactions = parse_transaction_export(export.as_bytes()).unwrap();
+
+        assert_eq!(
+            transactions[0].customer_email.as_deref(),
+            Some("ada@test.com")
+        );
+        assert_eq!(transactions[0].paid_at, None);
+    }
+
+    #[test]
+    fn export_without_reference_column_is_rejected() {
+        let export = "Status,Amount\nsuccess,500.00\n";
+
+        let result = parse_transaction_export(export.as_bytes());
+
+        assert!(
+            matches!(result, Err(PaystackAPIError::Transaction(msg)) if msg.contains("reference"))
+        );
+    }
+}
@@ -7,6 +7,8 @@ pub mod currency_models;
 pub mod customer_models;
 pub mod dedicated_virtual_account_models;
 pub mod domain_models;
+#[cfg(feature = "csv")]
+pub mod export_models;
 pub mod plans_models;
 pub mod refund_models;
 pub mod response_models;
@@ -29,6 +31,8 @@ pub use currency_models::*;
 pub use customer_models::*;
 pub use dedicated_virtual_account_models::*;
 pub use domain_models::*;
+#[cfg(feature = "csv")]
+pub use export_models::*;
 pub use plans_models::*;
 pub use refund_models::*;
 pub use response_models::*;
@@ -1,6 +1,7 @@
 //! Refund Models
 //! ==============
 
+use crate::{utils::empty_object_as_none, PaystackAPIError, SplitInfo};
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
@@ -17,6 +18,12 @@ pub struct CreateRefundRequest {
     /// Currency of the refund
     #[builder(setter(strip_option), default)]
     pub currency: Option<String>,
+    /// Currency of the original transaction, if known.
+    /// When both this and `currency` are set, they are checked to match before the refund is sent.
+    /// This is not sent to Paystack.
+    #[builder(setter(strip_option), default)]
+    #[serde(skip)]
+    pub transaction_currency: Option<String>,
     /// Customer-facing reason for the refund
     #[builder(setter(strip_option), default)]
     pub customer_note: Option<String>,
@@ -60,6 +67,10 @@ pub struct RefundData {
     /// The transaction this refund belongs to.
     /// On create this is a transaction object; on list/fetch it is a numeric ID.
     pub transaction: Option<serde_json::Value>,
+    /// The split of the refunded transaction, if it was a split payment.
+    /// It shows which subaccounts the refund affected.
+    #[serde(default, deserialize_with = "empty_object_as_none")]
+    pub split: Option<SplitInfo>,
     /// Refund amount in the lowest denomination of the currency
     pub amount: u64,
     /// Amount deducted from the integration's balance
@@ -91,3 +102,187 @@ pub struct RefundData {
     #[serde(alias = "updatedAt")]
     pub updated_at: Option<String>,
 }
+
+impl RefundData {
+    /// Amount of the refunded transaction, in the subunit of the currency.
+    /// It is only known when `transaction` is the full transaction object, as on create.
+    pub fn transaction_amount(&self) -> Option<u64> {
+        self.transaction.as_ref()?.get("amount")?.as_u64()
+    }
+
+    /// If only part of the transaction amount was refunded.
+    /// Returns `None` when the transaction amount is not known.
+    pub fn is_partial(&self) -> Option<bool> {
+        self.transaction_amount()
+            .map(|transaction_amount| self.amount < transaction_amount)
+    }
+
+    /// ID of the refunded transaction, whether `transaction` is the ID or the full transaction object.
+    pub fn transaction_id(&self) -> Option<u64> {
+        match self.transaction.as_ref()? {
+            serde_json::Value::Object(transaction) => transaction.get("id")?.as_u64(),
+            id => id.as_u64(),
+        }
+    }
+}
+
+/// Checks that refunds listed for a transaction were made for it, in case Paystack ignored
+/// or loosened the `transaction` filter of the list.
+///
+/// Listed refunds only carry the ID of their transaction. For a transaction ID, every refund
+/// must have that ID. For a reference, the refunds must all share one transaction ID.
+#[derive(Debug)]
+pub(crate) struct RefundTransactionCheck<'a> {
+    /// The transaction ID or reference the refunds were listed for
+    transaction: &'a str,
+    /// The ID every refund must have, once known
+    transaction_id: Option<u64>,
+}
+
+impl<'a> RefundTransactionCheck<'a> {
+    pub(crate) fn new(transaction: &'a str) -> Self {
+        RefundTransactionCheck {
+            transaction,
+            transaction_id: transaction.parse().ok(),
+        }
+    }
+
+    /// Returns a `FilterNotHonoured` error if the refund was made for another transaction.
+    pub(crate) fn check(&mut self, refund: &RefundData) -> Result<(), PaystackAPIError> {
+        let Some(id) = refund.transaction_id() else {
+            return Ok(());
+        };
+        let expected = *self.transaction_id.get_or_insert(id);
+        if id == expected {
+            Ok(())
+        } else {
+            Err(PaystackAPIError::FilterNotHonoured(format!(
+                "listed refunds of transaction {} but got refund {} of transaction {id}",
+                self.transaction, refund.id
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_deserialize_refund_of_split_transaction() {
+        let refund: RefundData = serde_json::from_value(serde_json::json!({
+            "id": 3018284,
+            "integration": 412829,
+            "domain": "test",
+            "transaction": 4099260520u64,
+            "split": {
+                "id": 1461,
+                "name": "Marketplace split",
+                "split_code": "SPL_98WF13Eb3w",
+                "shares": {
+                    "paystack": 1500,
+                    "subaccounts": [
+                        {
+                            "amount": 29550,
+                            "original_share": 30,
+                            "subaccount_code": "ACCT_6uujpqtzmnufzkw",
+                            "name": "Vendor One"
+                        }
+                    ],
+                    "integration": 68950
+                }
+            },
+            "amount": 100000,
+            "currency": "NGN",
+            "status": "pending",
+            "created_at": "2024-09-02T09:21:17.122Z"
+        }))
+        .expect("unable to deserialize refund");
+
+        let split = refund.split.unwrap();
+        assert_eq!(split.split_code, Some("SPL_98WF13Eb3w".to_string()));
+        assert_eq!(
+            split.shares.unwrap().subaccounts[0].subaccount_code,
+            Some("ACCT_6uujpqtzmnufzkw".to_string())
+        );
+    }
+
+    #[test]
+    fn can_deserialize_partial_refund() {
+        let refund: RefundData = serde_json::from_value(serde_json::json!({
+            "transaction": {
+                "id": 1004723697,
+                "domain": "live",
+                "reference": "T685312322670591",
+                "amount": 200000,
+                "paid_at": "2021-08-20T18:34:11.000Z",
+                "channel": "apple_pay",
+                "currency": "NGN",
+                "authorization": {
+                    "exp_month": null,
+                    "exp_year": null,
+                    "account_name": null
+                },
+                "customer": { "international_format_phone": null },
+                "plan": {},
+                "subaccount": { "currency": null },
+                "split": {},
+                "order_id": null,
+                "paidAt": "2021-08-20T18:34:11.000Z",
+                "pos_transaction_data": null,
+                "source": null,
+                "fees_breakdown": null
+            },
+            "integration": 412829,
+            "deducted_amount": 0,
+            "channel": null,
+            "merchant_note": "Refund for transaction T685312322670591 by test@me.com",
+            "customer_note": "Refund for transaction T685312322670591",
+            "status": "pending",
+            "refunded_by": "test@me.com",
+            "expected_at": "2021-12-16T09:21:17.016Z",
+            "currency": "NGN",
+            "domain": "live",
+            "amount": 50000,
+            "fully_deducted": false,
+            "id": 3018284,
+            "createdAt": "2021-12-07T09:21:17.122Z",
+            "updatedAt": "2021-12-07T09:21:17.122Z"
+        }))
+        .expect("unable to deserialize refund");
+
+        assert_eq!(refund.amount, 50000);
+        assert_eq!(refund.transaction_amount(), Some(200000));
+        assert_eq!(refund.is_partial(), Some(true));
+    }
+
+    #[test]
+    fn refund_with_transaction_id_has_unknown_partial_state() {
+        let refund: RefundData = serde_json::from_value(serde_json::json!({
+            "id": 3018285,
+            "transaction": 1004723697,
+            "amount": 50000,
+            "currency": "NGN",
+            "status": "processed"
+        }))
+        .expect("unable to deserialize refund");
+
+        assert_eq!(refund.transaction_amount(), None);
+        assert_eq!(refund.is_partial(), None);
+    }
+
+    #[test]
+    fn refund_without_split_has_none() {
+        let refund: RefundData = serde_json::from_value(serde_json::json!({
+            "id": 3018285,
+            "transaction": 4099260517u64,
+            "split": {},
+            "amount": 20000,
+            "currency": "NGN",
+            "status": "processed"
+        }))
+        .expect("unable to deserialize refund");
+
+        assert!(refund.split.is_none());
+    }
+}
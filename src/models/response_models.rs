@@ -2,10 +2,16 @@
 //! ========
 //! Holds the generic response templates for the API
 use crate::utils::option_string_or_number_to_u16;
-use serde::{Deserialize, Serialize};
+use crate::{PaystackAPIError, PaystackResult};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
 
 /// Generic response body template for the API
+///
+/// New fields may be added in minor releases, so the struct cannot be built with a literal
+/// outside this crate.
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct Response<T> {
     /// This lets you know if your request was successful or not.
     pub status: bool,
@@ -19,6 +25,158 @@ pub struct Response<T> {
     #[serde(rename = "type")]
     pub response_type: Option<String>,
     pub code: Option<String>,
+    /// The raw JSON body of the response, kept for fields the typed data does not cover yet.
+    /// It is only set when the client is configured with `PaystackClient::with_keep_raw(true)`.
+    #[serde(skip)]
+    pub raw: Option<Value>,
+}
+
+impl<T: DeserializeOwned + Default> Response<T> {
+    /// Parses a response body, keeping the raw JSON alongside the typed data if `keep_raw` is set.
+    pub(crate) fn parse(body: &str, keep_raw: bool) -> Result<Self, serde_json::Error> {
+        if !keep_raw {
+            return serde_json::from_str(body);
+        }
+
+        let raw: Value = serde_json::from_str(body)?;
+        let mut response: Response<T> = Response::deserialize(&raw)?;
+        response.raw = Some(raw);
+        Ok(response)
+    }
+}
+
+impl<T> Response<T> {
+    /// Replaces the data of the response, keeping its status and message.
+    pub(crate) fn with_data<U>(self, data: U) -> Response<U> {
+        Response {
+            status: self.status,
+            message: self.message,
+            data: Some(data),
+            meta: self.meta,
+            response_type: self.response_type,
+            code: self.code,
+            raw: self.raw,
+        }
+    }
+}
+
+impl<T: Serialize> Response<T> {
+    /// Deserializes the data of the response into another type, without making a second request.
+    ///
+    /// Some Paystack fields change shape between endpoints, e.g. an object on one and an ID or an
+    /// array on another, so the data may be better read as a different type than `T`.
+    /// The raw body is used when the response was kept with `with_keep_raw(true)`, so fields
+    /// dropped by `T` are still available. Otherwise the typed data is serialized again.
+    ///
+    /// # Returns
+    /// A Result containing the data as `U`, `None` if the response has no data,
+    /// or a generic error if the data cannot be read as `U`
+    pub fn data_as<U: DeserializeOwned>(&self) -> Result<Option<U>, PaystackAPIError> {
+        let data = match (&self.raw, &self.data) {
+            (Some(raw), _) => raw.get("data").cloned().unwrap_or(Value::Null),
+            (None, Some(data)) => {
+                serde_json::to_value(data).map_err(|e| PaystackAPIError::Generic(e.to_string()))?
+            }
+            (None, None) => Value::Null,
+        };
+
+        if data.is_null() {
+            return Ok(None);
+        }
+
+        serde_json::from_value(data)
+            .map(Some)
+            .map_err(|e| PaystackAPIError::Generic(format!("unable to read the data: {e}")))
+    }
+}
+
+/// A list whose records are deserialized one by one, so a malformed record does not fail the whole list.
+///
+/// It is returned by the lenient list methods, e.g. `TransactionEndpoints::list_transactions_lenient`.
+/// Records that cannot be deserialized are reported in `errors` with their position in the list.
+#[derive(Clone, Debug, Serialize)]
+pub struct PartialList<T> {
+    /// The records that were deserialized
+    pub items: Vec<T>,
+    /// The position in the list and the deserialization error of each record that was skipped
+    pub errors: Vec<(usize, String)>,
+}
+
+impl<T> Default for PartialList<T> {
+    fn default() -> Self {
+        PartialList {
+            items: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for PartialList<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let records = Vec::<Value>::deserialize(deserializer)?;
+        let mut list = PartialList::default();
+
+        for (index, record) in records.into_iter().enumerate() {
+            match T::deserialize(record) {
+                Ok(item) => list.items.push(item),
+                Err(e) => list.errors.push((index, e.to_string())),
+            }
+        }
+
+        Ok(list)
+    }
+}
+
+/// Extracts the data from a response, so it does not have to be unwrapped at every call.
+///
+/// It is implemented for `Response<T>` and for the `PaystackResult<T>` returned by the endpoints.
+/// The `Response` itself stays available for callers that need the message or meta data.
+///
+/// # Example
+/// ```no_run
+/// use paystack::{PaystackAPIError, PaystackClient, ReqwestClient, ResponseExt};
+///
+/// # async fn run() -> Result<(), PaystackAPIError> {
+/// let client = PaystackClient::<ReqwestClient>::from_env()?;
+/// let customer = client
+///     .customers
+///     .fetch_customer("CUS_xnxdt6s1zg1f4nx".to_string())
+///     .await
+///     .into_data()?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait ResponseExt<T> {
+    /// Returns the data of the response.
+    ///
+    /// # Returns
+    /// A Result containing the data, or a generic error including the API message
+    /// if the request was not successful or the response has no data
+    fn into_data(self) -> Result<T, PaystackAPIError>;
+}
+
+impl<T> ResponseExt<T> for Response<T> {
+    fn into_data(self) -> Result<T, PaystackAPIError> {
+        if !self.status {
+            return Err(PaystackAPIError::Generic(format!(
+                "the request was not successful: {}",
+                self.message
+            )));
+        }
+
+        self.data.ok_or_else(|| {
+            PaystackAPIError::Generic(format!("the response has no data: {}", self.message))
+        })
+    }
+}
+
+impl<T> ResponseExt<T> for PaystackResult<T> {
+    fn into_data(self) -> Result<T, PaystackAPIError> {
+        self?.into_data()
+    }
 }
 
 /// The Meta object is used to provide context for the contents of the data key.
@@ -45,3 +203,174 @@ pub struct Meta {
     pub previous: Option<String>,
     pub next_step: Option<String>,
 }
+
+/// A list response read only for the total number of records.
+/// The records in `data` are skipped instead of being deserialized.
+#[derive(Deserialize)]
+pub(crate) struct CountResponse {
+    status: bool,
+    message: String,
+    #[serde(default)]
+    meta: Option<CountMeta>,
+    #[serde(rename = "type")]
+    response_type: Option<String>,
+    code: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CountMeta {
+    #[serde(default)]
+    total: Option<Value>,
+}
+
+impl CountResponse {
+    /// Parses a list response into a response holding the total number of records.
+    ///
+    /// The total is read as a `u64`, since `Meta::total` cannot hold more than `u16::MAX` records.
+    pub(crate) fn parse(response: &str) -> Result<Response<u64>, String> {
+        let count: CountResponse = serde_json::from_str(response).map_err(|e| e.to_string())?;
+        let total = match count.meta.and_then(|meta| meta.total) {
+            Some(Value::Number(total)) => total.as_u64(),
+            Some(Value::String(total)) => total.parse().ok(),
+            _ => None,
+        }
+        .ok_or("the response does not include the total number of records")?;
+
+        Ok(Response {
+            status: count.status,
+            message: count.message,
+            data: Some(total),
+            meta: None,
+            response_type: count.response_type,
+            code: count.code,
+            raw: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(status: bool, data: Option<u32>) -> Response<u32> {
+        Response {
+            status,
+            message: "Customer retrieved".to_string(),
+            data,
+            meta: None,
+            response_type: None,
+            code: None,
+            raw: None,
+        }
+    }
+
+    #[test]
+    fn into_data_returns_data() {
+        assert_eq!(response(true, Some(42)).into_data().unwrap(), 42);
+
+        let result: PaystackResult<u32> = Ok(response(true, Some(42)));
+        assert_eq!(result.into_data().unwrap(), 42);
+    }
+
+    #[test]
+    fn into_data_reports_missing_data_with_message() {
+        let error = response(true, None).into_data().unwrap_err();
+
+        assert!(
+            matches!(error, PaystackAPIError::Generic(msg) if msg.contains("Customer retrieved"))
+        );
+    }
+
+    #[test]
+    fn into_data_reports_unsuccessful_response() {
+        assert!(response(false, Some(42)).into_data().is_err());
+
+        let result: PaystackResult<u32> = Err(PaystackAPIError::Customer("not found".to_string()));
+        assert!(matches!(
+            result.into_data(),
+            Err(PaystackAPIError::Customer(_))
+        ));
+    }
+
+    #[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+    struct Record {
+        id: u32,
+        amount: u32,
+    }
+
+    #[test]
+    fn partial_list_skips_malformed_records() {
+        let list: PartialList<Record> = serde_json::from_str(
+            r#"[{"id": 1, "amount": 100}, {"id": 2, "amount": "200"}, {"id": 3, "amount": 300}]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            list.items,
+            vec![Record { id: 1, amount: 100 }, Record { id: 3, amount: 300 }]
+        );
+        assert_eq!(list.errors.len(), 1);
+        assert_eq!(list.errors[0].0, 1);
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct PricedRecord {
+        amount: u32,
+        currency: String,
+    }
+
+    #[test]
+    fn data_as_reads_raw_data_as_alternate_type() {
+        let body = r#"{
+            "status": true,
+            "message": "Record retrieved",
+            "data": {"id": 1, "amount": 100, "currency": "NGN"}
+        }"#;
+
+        let response: Response<Record> = Response::parse(body, true).unwrap();
+
+        assert_eq!(response.data, Some(Record { id: 1, amount: 100 }));
+        assert_eq!(
+            response.data_as::<PricedRecord>().unwrap(),
+            Some(PricedRecord {
+                amount: 100,
+                currency: "NGN".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn data_as_reads_typed_data_without_raw() {
+        let body =
+            r#"{"status": true, "message": "Records retrieved", "data": {"id": 1, "amount": 100}}"#;
+
+        let parsed: Response<Record> = Response::parse(body, false).unwrap();
+
+        assert!(parsed.raw.is_none());
+        let data = parsed.data_as::<Value>().unwrap().unwrap();
+        assert_eq!(data["amount"], 100);
+        assert!(parsed.data_as::<Vec<Record>>().is_err());
+        assert!(response(true, None).data_as::<Record>().unwrap().is_none());
+    }
+
+    #[test]
+    fn count_response_reads_total_larger_than_u16() {
+        let response = r#"{
+            "status": true,
+            "message": "Transactions retrieved",
+            "data": [{"id": 1}],
+            "meta": {"total": 1234567, "perPage": 1, "page": 1}
+        }"#;
+
+        let count = CountResponse::parse(response).unwrap();
+
+        assert_eq!(count.data, Some(1234567));
+    }
+
+    #[test]
+    fn count_response_requires_total() {
+        let response = r#"{"status": true, "message": "Transactions retrieved", "data": []}"#;
+
+        assert!(CountResponse::parse(response).is_err());
+    }
+}
@@ -1,8 +1,9 @@
 //! Split Type
 //! ===============
-//! This file contains the transaction split options for the paystack API.
+//! This file contains the transaction split options for the paystack API,
+//! and the split details returned on transactions and refunds.
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Represents the type of transaction split.
@@ -47,3 +48,57 @@ impl fmt::Display for SplitType {
         write!(f, "{lowercase_string}")
     }
 }
+
+/// The split a transaction was settled with, as returned on transactions and refunds.
+/// It shows how the amount was shared between Paystack, the integration and its subaccounts.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct SplitInfo {
+    /// ID of the transaction split
+    pub id: Option<u64>,
+    /// Name of the transaction split
+    pub name: Option<String>,
+    /// Code of the transaction split e.g. `SPL_98WF13Eb3w`
+    pub split_code: Option<String>,
+    /// How the amount was shared
+    pub shares: Option<SplitShares>,
+    /// Amounts settled to each subaccount, when they are returned directly on the split
+    /// instead of under `shares`, e.g. on the response of `charge_authorization`
+    #[serde(default)]
+    pub subaccounts: Vec<SubaccountShare>,
+}
+
+impl SplitInfo {
+    /// The amounts settled to each subaccount, wherever they were returned on the split.
+    pub fn subaccount_shares(&self) -> &[SubaccountShare] {
+        match &self.shares {
+            Some(shares) if !shares.subaccounts.is_empty() => &shares.subaccounts,
+            _ => &self.subaccounts,
+        }
+    }
+}
+
+/// The amounts each party received from a split transaction, in the subunit of the currency.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct SplitShares {
+    /// Amount taken by Paystack as fees
+    pub paystack: Option<u64>,
+    /// Amount settled to the main account
+    pub integration: Option<u64>,
+    /// Amounts settled to each subaccount
+    #[serde(default)]
+    pub subaccounts: Vec<SubaccountShare>,
+}
+
+/// The amount a subaccount received from a split transaction.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct SubaccountShare {
+    /// Code of the subaccount e.g. `ACCT_6uujpqtzmnufzkw`
+    #[serde(alias = "subaccount")]
+    pub subaccount_code: Option<String>,
+    /// Name of the subaccount
+    pub name: Option<String>,
+    /// Amount settled to the subaccount
+    pub amount: Option<u64>,
+    /// Share of the subaccount in the split configuration
+    pub original_share: Option<f64>,
+}
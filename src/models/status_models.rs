@@ -51,3 +51,78 @@ impl fmt::Display for Status {
         write!(f, "{lowercase_string}")
     }
 }
+
+/// The status of a transaction returned by Paystack.
+///
+/// Unlike `Status`, which is used to filter requests, it covers every status a transaction can be in.
+/// Values not known to this crate are kept in the `Unknown` variant instead of failing deserialization.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum TransactionStatus {
+    /// The payment was successful
+    Success,
+    /// The payment failed, e.g. the card was declined
+    Failed,
+    /// The customer did not complete the payment
+    Abandoned,
+    /// The payment was reversed
+    Reversed,
+    /// The payment is queued to be processed, e.g. a queued authorization charge
+    Queued,
+    /// The payment is being processed
+    #[default]
+    Pending,
+    /// A status not yet supported by this crate
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+impl fmt::Display for TransactionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self {
+            TransactionStatus::Success => "success",
+            TransactionStatus::Failed => "failed",
+            TransactionStatus::Abandoned => "abandoned",
+            TransactionStatus::Reversed => "reversed",
+            TransactionStatus::Queued => "queued",
+            TransactionStatus::Pending => "pending",
+            TransactionStatus::Unknown(status) => status,
+        };
+        write!(f, "{status}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transaction_status_round_trips() {
+        let statuses = [
+            (TransactionStatus::Success, "\"success\""),
+            (TransactionStatus::Failed, "\"failed\""),
+            (TransactionStatus::Abandoned, "\"abandoned\""),
+            (TransactionStatus::Reversed, "\"reversed\""),
+            (TransactionStatus::Queued, "\"queued\""),
+            (TransactionStatus::Pending, "\"pending\""),
+        ];
+
+        for (status, json) in statuses {
+            assert_eq!(serde_json::to_string(&status).unwrap(), json);
+            assert_eq!(
+                serde_json::from_str::<TransactionStatus>(json).unwrap(),
+                status
+            );
+            assert_eq!(format!("\"{status}\""), json);
+        }
+    }
+
+    #[test]
+    fn unknown_transaction_status_is_kept() {
+        let status: TransactionStatus = serde_json::from_str("\"ongoing\"").unwrap();
+
+        assert_eq!(status, TransactionStatus::Unknown("ongoing".to_string()));
+        assert_eq!(status.to_string(), "ongoing");
+    }
+}
@@ -3,13 +3,18 @@
 //! This file contains the models for working with the subaccounts endpoint.
 
 use super::Currency;
-use crate::{utils::bool_from_int_or_bool, Domain};
+use crate::{
+    utils::{bool_from_int_or_bool, option_json_string},
+    Domain, PaystackAPIError,
+};
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// This struct is used to create the body for creating a subaccount on your integration.
 /// Use the `SubaccountRequestBuilder` to create this object.
 #[derive(Serialize, Debug, Builder, Default)]
+#[builder(build_fn(validate = "Self::validate"))]
 pub struct CreateSubaccountRequest {
     /// Name of business for subaccount
     #[builder(setter(strip_option), default)]
@@ -51,6 +56,129 @@ pub struct CreateSubaccountRequest {
     #[builder(setter(strip_option), default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     metadata: Option<String>,
+    /// How often the subaccount is paid out. Use the `SettlementSchedule` enum for valid options.
+    #[builder(setter(strip_option), default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    settlement_schedule: Option<SettlementSchedule>,
+    /// Whether the subaccount is active. Only used when updating a subaccount,
+    /// e.g. to deactivate a vendor while their payout account is changed.
+    #[builder(setter(strip_option), default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    active: Option<bool>,
+}
+
+impl CreateSubaccountRequest {
+    /// Checks that the fields Paystack requires to create a subaccount are set.
+    /// They are optional on the request since it is also used to update a subaccount.
+    pub(crate) fn validate_for_create(&self) -> Result<(), PaystackAPIError> {
+        let missing: Vec<&str> = [
+            ("business_name", self.business_name.is_none()),
+            ("settlement_bank", self.settlement_bank.is_none()),
+            ("account_number", self.account_number.is_none()),
+            ("percentage_charge", self.percentage_charge.is_none()),
+        ]
+        .into_iter()
+        .filter_map(|(field, missing)| missing.then_some(field))
+        .collect();
+
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(PaystackAPIError::Validation(format!(
+                "{} must be set to create a subaccount",
+                missing.join(", ")
+            )))
+        }
+    }
+}
+
+impl CreateSubaccountRequestBuilder {
+    /// Checks that the percentage charge is a percentage.
+    fn validate(&self) -> Result<(), String> {
+        match self.percentage_charge {
+            Some(Some(charge)) if !(0.0..=100.0).contains(&charge) => Err(format!(
+                "percentage_charge must be between 0 and 100, got {charge}"
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Filters for listing the subaccounts of your integration.
+/// This struct should be created using the `ListSubaccountsRequestBuilder`.
+#[derive(Debug, Clone, Default, Builder)]
+pub struct ListSubaccountsRequest {
+    /// Number of subaccounts to return per page. Defaults to 50 on Paystack.
+    #[builder(setter(strip_option), default)]
+    pub per_page: Option<u32>,
+    /// Page of subaccounts to return. Defaults to 1 on Paystack.
+    #[builder(setter(strip_option), default)]
+    pub page: Option<u32>,
+    /// Start of the date range e.g. `2024-08-01T00:00:00.000Z`
+    #[builder(setter(strip_option), default)]
+    pub from: Option<String>,
+    /// End of the date range e.g. `2024-08-31T23:59:59.000Z`
+    #[builder(setter(strip_option), default)]
+    pub to: Option<String>,
+}
+
+impl ListSubaccountsRequest {
+    /// The query parameters of the filters that are set
+    pub(crate) fn to_query(&self) -> Vec<(&'static str, String)> {
+        let mut query = Vec::new();
+        if let Some(per_page) = self.per_page {
+            query.push(("perPage", per_page.to_string()));
+        }
+        if let Some(page) = self.page {
+            query.push(("page", page.to_string()));
+        }
+        if let Some(from) = &self.from {
+            query.push(("from", from.clone()));
+        }
+        if let Some(to) = &self.to {
+            query.push(("to", to.clone()));
+        }
+        query
+    }
+}
+
+/// Represents how often a subaccount is settled.
+///
+/// Paystack returns the schedule in uppercase on subaccounts, e.g. `AUTO`, which is also accepted.
+/// Values not known to this crate are kept in the `Unknown` variant instead of failing deserialization.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum SettlementSchedule {
+    /// Settled automatically, the next business day (T+1)
+    #[default]
+    #[serde(alias = "AUTO")]
+    Auto,
+    /// Settled once a week
+    #[serde(alias = "WEEKLY")]
+    Weekly,
+    /// Settled once a month
+    #[serde(alias = "MONTHLY")]
+    Monthly,
+    /// Settlements are held until they are released manually
+    #[serde(alias = "MANUAL")]
+    Manual,
+    /// A schedule not yet supported by this crate
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+impl fmt::Display for SettlementSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let schedule = match self {
+            SettlementSchedule::Auto => "auto",
+            SettlementSchedule::Weekly => "weekly",
+            SettlementSchedule::Monthly => "monthly",
+            SettlementSchedule::Manual => "manual",
+            SettlementSchedule::Unknown(schedule) => schedule,
+        };
+        write!(f, "{schedule}")
+    }
 }
 
 /// This struct represents the subaccount.
@@ -93,7 +221,8 @@ pub struct SubaccountsResponseData {
     pub primary_contact_email: Option<String>,
     /// The phone number of the primary contact for the business, if available.
     pub primary_contact_phone: Option<String>,
-    /// Additional metadata associated with the subaccount, if available.
+    /// Additional metadata associated with the subaccount, if available, as stringified JSON.
+    #[serde(default, deserialize_with = "option_json_string")]
     pub metadata: Option<String>,
     /// The percentage charge for transactions associated with the subaccount.
     pub percentage_charge: Option<f32>,
@@ -105,13 +234,16 @@ pub struct SubaccountsResponseData {
     pub bank_id: Option<u32>,
     /// The account number of the subaccount.
     pub account_number: String,
+    /// The name on the settlement account of the subaccount.
+    #[serde(default)]
+    pub account_name: Option<String>,
     /// Currency of the subaccount
     pub currency: Option<Currency>,
     /// If the account is active or not, should be 1 for active and 0 for inactive
     #[serde(default, deserialize_with = "bool_from_int_or_bool")]
     pub active: Option<bool>,
     /// Settlement schedule of subaccount.
-    pub settlement_schedule: Option<String>,
+    pub settlement_schedule: Option<SettlementSchedule>,
     /// The ID of the subaccount.
     pub id: u32,
     /// Creation time of subaccount.
@@ -122,6 +254,9 @@ pub struct SubaccountsResponseData {
     pub updated_at: Option<String>,
     pub product: Option<String>,
     pub managed_by_integration: Option<u32>,
+    /// Whether the subaccount was migrated from the previous version of subaccounts.
+    #[serde(default, deserialize_with = "bool_from_int_or_bool")]
+    pub migrate: Option<bool>,
 }
 
 /// This struct is used to create the body for deleting a subaccount on your integration.
@@ -130,3 +265,124 @@ pub struct DeleteSubAccountBody {
     /// This is the subaccount code
     pub subaccount: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settlement_schedule_round_trips() {
+        let schedules = [
+            (SettlementSchedule::Auto, "\"auto\""),
+            (SettlementSchedule::Weekly, "\"weekly\""),
+            (SettlementSchedule::Monthly, "\"monthly\""),
+            (SettlementSchedule::Manual, "\"manual\""),
+        ];
+
+        for (schedule, json) in schedules {
+            assert_eq!(serde_json::to_string(&schedule).unwrap(), json);
+            assert_eq!(
+                serde_json::from_str::<SettlementSchedule>(json).unwrap(),
+                schedule
+            );
+            assert_eq!(format!("\"{schedule}\""), json);
+        }
+    }
+
+    #[test]
+    fn unknown_settlement_schedule_falls_back() {
+        let schedule: SettlementSchedule = serde_json::from_str("\"fortnightly\"").unwrap();
+
+        assert_eq!(
+            schedule,
+            SettlementSchedule::Unknown("fortnightly".to_string())
+        );
+        assert_eq!(serde_json::to_string(&schedule).unwrap(), "\"fortnightly\"");
+    }
+
+    #[test]
+    fn can_create_subaccount_request_with_settlement_schedule() {
+        let request = CreateSubaccountRequestBuilder::default()
+            .business_name("Oasis".to_string())
+            .settlement_schedule(SettlementSchedule::Weekly)
+            .build()
+            .expect("unable to build subaccount request");
+
+        let body = serde_json::to_value(request).unwrap();
+        assert_eq!(body["settlement_schedule"], "weekly");
+    }
+
+    #[test]
+    fn percentage_charge_must_be_a_percentage() {
+        let request = CreateSubaccountRequestBuilder::default()
+            .percentage_charge(120.0)
+            .build();
+
+        assert!(request.is_err_and(|e| e.to_string().contains("120")));
+    }
+
+    #[test]
+    fn create_requires_business_and_settlement_details() {
+        let request = CreateSubaccountRequestBuilder::default()
+            .business_name("Oasis".to_string())
+            .account_number("0193274682".to_string())
+            .build()
+            .unwrap();
+
+        let error = request.validate_for_create().unwrap_err();
+
+        assert!(matches!(
+            error,
+            PaystackAPIError::Validation(msg) if msg == "settlement_bank, percentage_charge must be set to create a subaccount"
+        ));
+    }
+
+    #[test]
+    fn can_deserialize_fully_populated_subaccount() {
+        let subaccount: SubaccountsResponseData = serde_json::from_str(
+            r#"{
+                "integration": 100973,
+                "domain": "test",
+                "subaccount_code": "ACCT_4hl4xenwpjy5wb",
+                "business_name": "Sunshine Studios",
+                "description": "Sunshine Studios",
+                "primary_contact_name": "Oluwa Sunshine",
+                "primary_contact_email": "sunshine@studios.com",
+                "primary_contact_phone": "+2348012345678",
+                "metadata": null,
+                "percentage_charge": 18.2,
+                "is_verified": false,
+                "settlement_bank": "Access Bank",
+                "bank_id": 1,
+                "account_number": "0193274682",
+                "account_name": "Sunshine Studios Ltd",
+                "currency": "NGN",
+                "active": 1,
+                "settlement_schedule": "AUTO",
+                "id": 55,
+                "createdAt": "2016-10-05T12:22:04.000Z",
+                "updatedAt": "2016-10-21T02:19:47.000Z",
+                "product": "collection",
+                "managed_by_integration": 100973,
+                "migrate": false
+            }"#,
+        )
+        .expect("unable to deserialize subaccount");
+
+        assert_eq!(subaccount.subaccount_code, "ACCT_4hl4xenwpjy5wb");
+        assert_eq!(subaccount.settlement_bank, "Access Bank");
+        assert_eq!(subaccount.bank_id, Some(1));
+        assert_eq!(subaccount.account_number, "0193274682");
+        assert_eq!(
+            subaccount.account_name,
+            Some("Sunshine Studios Ltd".to_string())
+        );
+        assert_eq!(subaccount.is_verified, Some(false));
+        assert_eq!(subaccount.active, Some(true));
+        assert_eq!(subaccount.migrate, Some(false));
+        assert_eq!(
+            subaccount.settlement_schedule,
+            Some(SettlementSchedule::Auto)
+        );
+    }
+}
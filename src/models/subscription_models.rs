@@ -3,11 +3,17 @@ use std::fmt;
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
-use crate::{Authorization, Domain};
+#[cfg(feature = "chrono")]
+use crate::Interval;
+use crate::{utils::id_from_int_or_object, Authorization, Domain};
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub struct Subscription {
+    /// ID of the subscribed customer. Accepts either the ID or the expanded customer object.
+    #[serde(deserialize_with = "id_from_int_or_object")]
     pub customer: u32,
+    /// ID of the plan. Accepts either the ID or the expanded plan object.
+    #[serde(deserialize_with = "id_from_int_or_object")]
     pub plan: u32,
     pub integration: u32,
     pub domain: Domain,
@@ -29,18 +35,93 @@ pub struct Subscription {
     pub updated_at: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+impl Subscription {
+    /// Projects the next `n` charge dates of the subscription, starting with `next_payment_date`.
+    ///
+    /// `plan` only holds the ID of the plan, so the interval of the plan is passed in,
+    /// e.g. from `PlansEndpoints::fetch_plan`. Each date is counted from `next_payment_date`
+    /// rather than from the previous date, so a charge on the 31st falls on the last day of
+    /// shorter months without drifting to an earlier day in the following ones.
+    ///
+    /// # Arguments
+    /// * `n` - The number of charge dates to project
+    /// * `interval` - The interval of the plan of the subscription
+    ///
+    /// # Returns
+    /// The projected charge dates, or an empty list if the subscription will not be charged again,
+    /// e.g. it is non-renewing or cancelled, or if `next_payment_date` cannot be parsed
+    #[cfg(feature = "chrono")]
+    pub fn upcoming_charges(
+        &self,
+        n: usize,
+        interval: &Interval,
+    ) -> Vec<chrono::DateTime<chrono::Utc>> {
+        use chrono::{Days, Months};
+
+        if !matches!(
+            self.status,
+            SubscriptionStatus::Active | SubscriptionStatus::Attention
+        ) {
+            return Vec::new();
+        }
+        let Ok(next_payment) = chrono::DateTime::parse_from_rfc3339(&self.next_payment_date) else {
+            return Vec::new();
+        };
+        let next_payment = next_payment.with_timezone(&chrono::Utc);
+
+        (0..u32::try_from(n).unwrap_or(u32::MAX))
+            .map_while(|cycle| match interval {
+                Interval::Daily => next_payment.checked_add_days(Days::new(cycle.into())),
+                Interval::Weekly => next_payment.checked_add_days(Days::new(7 * u64::from(cycle))),
+                Interval::Monthly => next_payment.checked_add_months(Months::new(cycle)),
+                Interval::Quarterly => {
+                    next_payment.checked_add_months(Months::new(cycle.saturating_mul(3)))
+                }
+                Interval::Biannually => {
+                    next_payment.checked_add_months(Months::new(cycle.saturating_mul(6)))
+                }
+                Interval::Annually => {
+                    next_payment.checked_add_months(Months::new(cycle.saturating_mul(12)))
+                }
+            })
+            .collect()
+    }
+}
+
+/// Represents the status of a subscription.
+///
+/// Values not known to this crate are kept in the `Unknown` variant instead of failing deserialization.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 #[non_exhaustive]
 pub enum SubscriptionStatus {
+    /// The subscription has run for all its invoices
     #[default]
+    #[serde(alias = "completed")]
     Complete,
+    /// The subscription is currently active and will be charged on the next payment date
+    Active,
+    /// The subscription is active but will not be charged on the next payment date
+    #[serde(rename = "non-renewing")]
+    NonRenewing,
+    /// The last charge attempt on the subscription failed
+    Attention,
+    /// The subscription has been cancelled
+    Cancelled,
+    /// A status not yet supported by this crate
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 impl fmt::Display for SubscriptionStatus {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let status = match self {
             SubscriptionStatus::Complete => "complete",
+            SubscriptionStatus::Active => "active",
+            SubscriptionStatus::NonRenewing => "non-renewing",
+            SubscriptionStatus::Attention => "attention",
+            SubscriptionStatus::Cancelled => "cancelled",
+            SubscriptionStatus::Unknown(status) => status,
         };
         write!(f, "{status}")
     }
@@ -77,3 +158,188 @@ pub struct UpdateSubscriptionRequest {
     pub token: String,
     pub code: String,
 }
+
+/// This struct represents the link a customer can use to manage their subscription, e.g. to update their card.
+///
+/// The link is checked to be an absolute HTTPS URL when it is deserialized,
+/// so it is safe to present to customers.
+#[derive(Clone, Default, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(try_from = "RawManagementLink")]
+pub struct ManagementLink {
+    /// The subscription management page
+    pub link: String,
+}
+
+impl ManagementLink {
+    /// Whether the link is an absolute HTTPS URL, e.g. `https://paystack.com/manage/subscriptions/...`.
+    pub fn is_absolute_https(&self) -> bool {
+        reqwest::Url::parse(&self.link)
+            .is_ok_and(|url| url.scheme() == "https" && url.host_str().is_some())
+    }
+}
+
+/// The management link as sent by Paystack, before it is validated.
+#[derive(Deserialize)]
+struct RawManagementLink {
+    link: String,
+}
+
+impl TryFrom<RawManagementLink> for ManagementLink {
+    type Error = String;
+
+    fn try_from(raw: RawManagementLink) -> Result<Self, Self::Error> {
+        let link = ManagementLink { link: raw.link };
+        if link.is_absolute_https() {
+            Ok(link)
+        } else {
+            Err(format!(
+                "the management link `{}` is not an absolute HTTPS URL",
+                link.link
+            ))
+        }
+    }
+}
+
+/// The result of generating and emailing the subscription management link with `SubscriptionEndpoints::manage`.
+#[derive(Clone, Default, Debug, Serialize, Deserialize)]
+pub struct ManageLinks {
+    /// The subscription management page
+    pub link: String,
+    /// Whether Paystack accepted to email the link to the customer
+    pub emailed: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subscription_status_round_trips() {
+        let statuses = [
+            (SubscriptionStatus::Complete, "\"complete\""),
+            (SubscriptionStatus::Active, "\"active\""),
+            (SubscriptionStatus::NonRenewing, "\"non-renewing\""),
+            (SubscriptionStatus::Attention, "\"attention\""),
+            (SubscriptionStatus::Cancelled, "\"cancelled\""),
+        ];
+
+        for (status, json) in statuses {
+            assert_eq!(serde_json::to_string(&status).unwrap(), json);
+            assert_eq!(
+                serde_json::from_str::<SubscriptionStatus>(json).unwrap(),
+                status
+            );
+            assert_eq!(format!("\"{status}\""), json);
+        }
+    }
+
+    #[test]
+    fn can_deserialize_subscription_with_expanded_customer_and_plan() {
+        let subscription: Subscription = serde_json::from_value(serde_json::json!({
+            "customer": { "id": 1173, "email": "customer@email.com" },
+            "plan": { "id": 28, "plan_code": "PLN_gx2wn530m0i3w3m" },
+            "integration": 100032,
+            "domain": "test",
+            "start": 1459296064,
+            "status": "active",
+            "quantity": 1,
+            "amount": 50000,
+            "subscription_code": "SUB_vsyqdmlzble3uii",
+            "email_token": "d7gofp6yppn3qz7",
+            "authorization": { "authorization_code": "AUTH_8dfhjjdt" },
+            "easy_cron_id": null,
+            "cron_expression": "0 0 28 * *",
+            "next_payment_date": "2016-05-19T07:00:00.000Z",
+            "open_invoice": null,
+            "id": 9,
+            "createdAt": "2016-03-30T00:01:04.000Z",
+            "updatedAt": "2016-03-30T00:22:58.000Z"
+        }))
+        .expect("unable to deserialize subscription");
+
+        assert_eq!(subscription.customer, 1173);
+        assert_eq!(subscription.plan, 28);
+        assert_eq!(subscription.status, SubscriptionStatus::Active);
+    }
+
+    #[cfg(feature = "chrono")]
+    fn subscription(status: SubscriptionStatus, next_payment_date: &str) -> Subscription {
+        Subscription {
+            status,
+            next_payment_date: next_payment_date.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn upcoming_charges_projects_monthly_cycles() {
+        use chrono::TimeZone;
+
+        let subscription = subscription(SubscriptionStatus::Active, "2024-01-31T07:00:00.000Z");
+
+        let charges = subscription.upcoming_charges(3, &Interval::Monthly);
+
+        assert_eq!(
+            charges,
+            vec![
+                chrono::Utc.with_ymd_and_hms(2024, 1, 31, 7, 0, 0).unwrap(),
+                chrono::Utc.with_ymd_and_hms(2024, 2, 29, 7, 0, 0).unwrap(),
+                chrono::Utc.with_ymd_and_hms(2024, 3, 31, 7, 0, 0).unwrap(),
+            ]
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn upcoming_charges_projects_annual_cycles() {
+        use chrono::TimeZone;
+
+        let subscription = subscription(SubscriptionStatus::Active, "2024-02-29T07:00:00.000Z");
+
+        let charges = subscription.upcoming_charges(3, &Interval::Annually);
+
+        assert_eq!(
+            charges,
+            vec![
+                chrono::Utc.with_ymd_and_hms(2024, 2, 29, 7, 0, 0).unwrap(),
+                chrono::Utc.with_ymd_and_hms(2025, 2, 28, 7, 0, 0).unwrap(),
+                chrono::Utc.with_ymd_and_hms(2026, 2, 28, 7, 0, 0).unwrap(),
+            ]
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn upcoming_charges_are_empty_for_non_renewing_subscription() {
+        let subscription =
+            subscription(SubscriptionStatus::NonRenewing, "2024-01-31T07:00:00.000Z");
+
+        assert!(subscription
+            .upcoming_charges(3, &Interval::Monthly)
+            .is_empty());
+    }
+
+    #[test]
+    fn can_deserialize_management_link() {
+        let link: ManagementLink = serde_json::from_str(
+            r#"{"link": "https://paystack.com/manage/subscriptions/qlgwhpyq1ts2nsw?subscription_token=uqmnm4tw8yb0gqi"}"#,
+        )
+        .unwrap();
+
+        assert!(link.is_absolute_https());
+    }
+
+    #[test]
+    fn malformed_management_link_is_rejected() {
+        for link in [
+            r#"{"link": "paystack.com/manage/subscriptions/qlgwhpyq1ts2nsw"}"#,
+            r#"{"link": "http://paystack.com/manage/subscriptions/qlgwhpyq1ts2nsw"}"#,
+            r#"{"link": ""}"#,
+        ] {
+            let result = serde_json::from_str::<ManagementLink>(link);
+
+            assert!(result.is_err_and(|e| e.to_string().contains("not an absolute HTTPS URL")));
+        }
+    }
+}
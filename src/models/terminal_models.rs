@@ -9,7 +9,11 @@ use std::fmt;
 use crate::Domain;
 
 /// The request body to send an event from your application to the Paystack Terminal
+///
+/// The builder checks that the action can be performed on the type of event,
+/// and that an invoice event carries the offline reference of the invoice.
 #[derive(Debug, Clone, Builder, Serialize, Deserialize)]
+#[builder(build_fn(validate = "Self::validate"))]
 pub struct EventRequest {
     #[serde(rename = "type")]
     pub event_type: EventType,
@@ -17,6 +21,61 @@ pub struct EventRequest {
     pub data: EventRequestData,
 }
 
+impl EventRequest {
+    /// Checks that the action and data are valid for the type of event.
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        validate_event(&self.event_type, &self.action, &self.data)
+    }
+}
+
+impl EventRequestBuilder {
+    fn validate(&self) -> Result<(), String> {
+        match (&self.event_type, &self.action, &self.data) {
+            (Some(event_type), Some(action), Some(data)) => {
+                validate_event(event_type, action, data)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Checks the combination of type, action and data of a Terminal event.
+///
+/// Invoices can be processed or viewed and need the offline reference of the invoice.
+/// Transactions can be processed or printed.
+fn validate_event(
+    event_type: &EventType,
+    action: &TerminalAction,
+    data: &EventRequestData,
+) -> Result<(), String> {
+    let valid_action = match event_type {
+        EventType::Invoice => matches!(action, TerminalAction::Process | TerminalAction::View),
+        EventType::Transaction => {
+            matches!(action, TerminalAction::Process | TerminalAction::Print)
+        }
+    };
+    if !valid_action {
+        return Err(format!(
+            "the {action} action cannot be performed on a {event_type} event"
+        ));
+    }
+
+    if data.id.trim().is_empty() {
+        return Err(format!("the id of the {event_type} is empty"));
+    }
+
+    if *event_type == EventType::Invoice
+        && data
+            .reference
+            .as_deref()
+            .is_none_or(|r| r.trim().is_empty())
+    {
+        return Err("an invoice event needs the offline reference of the invoice".to_string());
+    }
+
+    Ok(())
+}
+
 /// The paramters needed to perform the specified action.
 ///
 /// For the invoice type, you need to pass the invoice id and offline reference: {id: invoice_id, reference: offline_reference}.
@@ -26,12 +85,14 @@ pub struct EventRequest {
 pub struct EventRequestData {
     pub id: String,
     #[builder(setter(strip_option), default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub reference: Option<String>,
 }
 
 /// The type of event to push.
 /// Paystack currently support `invoice` and `transaction`
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
 pub enum EventType {
     Invoice,
     Transaction,
@@ -43,6 +104,7 @@ pub enum EventType {
 ///
 /// - For the `Transaction` type, the action can either be `Process` or `Print`.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
 pub enum TerminalAction {
     Process,
     View,
@@ -81,9 +143,45 @@ pub struct UpdateTerminalRequest {
     pub name: Option<String>,
 }
 
+/// Options for listing the Terminals of your integration.
+/// This struct should be created using the `ListTerminalsRequestBuilder`.
+///
+/// Terminals are paged with cursors: the cursors of the next and previous pages
+/// are returned in `Meta::next` and `Meta::previous`.
+#[derive(Debug, Clone, Default, Builder)]
+pub struct ListTerminalsRequest {
+    /// Number of Terminals to return per page. Defaults to 50 on Paystack.
+    #[builder(setter(strip_option), default)]
+    pub per_page: Option<u32>,
+    /// Cursor of the next page, from `Meta::next`
+    #[builder(setter(strip_option), default)]
+    pub next: Option<String>,
+    /// Cursor of the previous page, from `Meta::previous`
+    #[builder(setter(strip_option), default)]
+    pub previous: Option<String>,
+}
+
+impl ListTerminalsRequest {
+    /// The query parameters of the options that are set
+    pub(crate) fn to_query(&self) -> Vec<(&'static str, String)> {
+        let mut query = Vec::new();
+        if let Some(per_page) = self.per_page {
+            query.push(("perPage", per_page.to_string()));
+        }
+        if let Some(next) = &self.next {
+            query.push(("next", next.clone()));
+        }
+        if let Some(previous) = &self.previous {
+            query.push(("previous", previous.clone()));
+        }
+        query
+    }
+}
+
 /// Response data for the send event route in the terminal endpoint.
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct SendEventResponseData {
+    /// ID of the event, used to poll its status with `fetch_event_status`
     pub id: String,
 }
 
@@ -96,21 +194,32 @@ pub struct FetchEventStatusResponseData {
 /// Response data for fetch terminal status route in the terminal endpoint.
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct FetchTerminalStatusResponseData {
+    /// Whether the Terminal is connected and can receive events
     pub online: bool,
+    /// Whether the Terminal is free to process an event, i.e. it is not busy with another one
     pub available: bool,
 }
 
 /// Response data for terminal
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct TerminalData {
+    /// ID of the Terminal on Paystack
     pub id: u64,
+    /// Serial number of the device
     pub serial_number: String,
+    /// Manufacturer of the device, if known
     pub device_make: Option<String>,
+    /// ID of the Terminal, used to send events to it
     pub terminal_id: String,
+    /// ID of the integration the Terminal belongs to
     pub integration: u64,
+    /// Domain of the Terminal, `test` or `live`
     pub domain: Domain,
+    /// Name of the Terminal
     pub name: String,
+    /// Address of the Terminal, if set
     pub address: Option<String>,
+    /// Status of the Terminal, e.g. `active`
     pub status: String,
 }
 
@@ -139,6 +248,59 @@ mod tests {
         assert_eq!(&event_request.data.reference, &Some("some-ref".to_string()))
     }
 
+    #[test]
+    fn event_request_is_sent_in_lowercase() {
+        let event_request = EventRequestBuilder::default()
+            .event_type(EventType::Transaction)
+            .action(TerminalAction::Print)
+            .data(
+                EventRequestDataBuilder::default()
+                    .id("4099260516".to_string())
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .expect("failed to build event request");
+
+        let body = serde_json::to_value(event_request).unwrap();
+        assert_eq!(body["type"], "transaction");
+        assert_eq!(body["action"], "print");
+        assert_eq!(body["data"]["id"], "4099260516");
+    }
+
+    #[test]
+    fn event_request_rejects_invalid_combinations() {
+        let invoice = EventRequestDataBuilder::default()
+            .id("7895939".to_string())
+            .reference("4634337895939".to_string())
+            .build()
+            .unwrap();
+        let without_reference = EventRequestDataBuilder::default()
+            .id("7895939".to_string())
+            .build()
+            .unwrap();
+
+        let cases = [
+            (EventType::Invoice, TerminalAction::Print, invoice.clone()),
+            (EventType::Transaction, TerminalAction::View, invoice),
+            (
+                EventType::Invoice,
+                TerminalAction::Process,
+                without_reference,
+            ),
+        ];
+
+        for (event_type, action, data) in cases {
+            let event_request = EventRequestBuilder::default()
+                .event_type(event_type)
+                .action(action)
+                .data(data)
+                .build();
+
+            assert!(event_request.is_err());
+        }
+    }
+
     #[test]
     fn create_update_terminal_request() {
         let update_request = UpdateTerminalRequestBuilder::default()
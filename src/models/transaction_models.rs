@@ -3,8 +3,15 @@
 
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
 
-use crate::{Authorization, Channel, Currency, CustomerResponseData};
+use crate::utils::{empty_object_as_none, plan_code_from_string_or_object};
+use crate::{
+    Authorization, Bearer, Channel, Currency, CustomerResponseData, Interval, PaymentChannel,
+    RefundData, SplitInfo, Status, TransactionStatus,
+};
 
 /// This struct is used to create a transaction body for creating a transaction using the Paystack API.
 /// This struct is built using the `TransactionRequestBuilder` struct.
@@ -34,7 +41,9 @@ pub struct TransactionRequest {
     #[builder(setter(strip_option), default)]
     pub metadata: Option<String>,
     /// An array of payment channels to control what channels you want to make available to the user to make a payment with.
+    /// Sent to Paystack as `channels`.
     #[builder(setter(strip_option), default)]
+    #[serde(rename = "channels")]
     pub channel: Option<Vec<Channel>>,
     /// The split code of the transaction split. e.g. `SPL_98WF13Eb3w`
     #[builder(setter(strip_option), default)]
@@ -48,13 +57,144 @@ pub struct TransactionRequest {
     pub transaction_charge: Option<String>,
     /// Use this param to indicate who bears the transaction charges. Allowed values are: `account` or `subaccount` (defaults to `account`).
     #[builder(setter(strip_option), default)]
-    pub bearer: Option<String>,
+    pub bearer: Option<Bearer>,
+}
+
+/// Filters for listing the transactions of your integration.
+/// This struct should be created using the `ListTransactionsRequestBuilder`.
+#[derive(Debug, Clone, Default, Builder)]
+pub struct ListTransactionsRequest {
+    /// Number of transactions to return per page. Defaults to 50 on Paystack.
+    #[builder(setter(strip_option), default)]
+    pub per_page: Option<u32>,
+    /// Page of transactions to return. Defaults to 1 on Paystack.
+    #[builder(setter(strip_option), default)]
+    pub page: Option<u32>,
+    /// ID of the customer whose transactions are listed
+    #[builder(setter(strip_option), default)]
+    pub customer: Option<u64>,
+    /// ID of the terminal whose transactions are listed
+    #[builder(setter(strip_option), default)]
+    pub terminal_id: Option<String>,
+    /// Status of the transactions to list
+    #[builder(setter(strip_option), default)]
+    pub status: Option<Status>,
+    /// Start of the date range e.g. `2024-08-01T00:00:00.000Z`
+    #[builder(setter(strip_option), default)]
+    pub from: Option<String>,
+    /// End of the date range e.g. `2024-08-31T23:59:59.000Z`
+    #[builder(setter(strip_option), default)]
+    pub to: Option<String>,
+    /// Amount of the transactions to list, in the subunit of the currency
+    #[builder(setter(strip_option), default)]
+    pub amount: Option<u64>,
+}
+
+impl ListTransactionsRequest {
+    /// The query parameters of the filters that are set
+    pub(crate) fn to_query(&self) -> Vec<(&'static str, String)> {
+        let mut query = Vec::new();
+        if let Some(per_page) = self.per_page {
+            query.push(("perPage", per_page.to_string()));
+        }
+        if let Some(page) = self.page {
+            query.push(("page", page.to_string()));
+        }
+        if let Some(customer) = self.customer {
+            query.push(("customer", customer.to_string()));
+        }
+        if let Some(terminal_id) = &self.terminal_id {
+            query.push(("terminalid", terminal_id.clone()));
+        }
+        if let Some(status) = &self.status {
+            query.push(("status", status.to_string()));
+        }
+        if let Some(from) = &self.from {
+            query.push(("from", from.clone()));
+        }
+        if let Some(to) = &self.to {
+            query.push(("to", to.clone()));
+        }
+        if let Some(amount) = self.amount {
+            query.push(("amount", amount.to_string()));
+        }
+        query
+    }
+}
+
+/// Filters for exporting the transactions of your integration.
+/// This struct should be created using the `ExportTransactionsRequestBuilder`.
+#[derive(Debug, Clone, Default, Builder)]
+pub struct ExportTransactionsRequest {
+    /// Start of the date range e.g. `2024-08-01T00:00:00.000Z`
+    #[builder(setter(strip_option), default)]
+    pub from: Option<String>,
+    /// End of the date range e.g. `2024-08-31T23:59:59.000Z`
+    #[builder(setter(strip_option), default)]
+    pub to: Option<String>,
+    /// ID of the customer whose transactions are exported
+    #[builder(setter(strip_option), default)]
+    pub customer: Option<u64>,
+    /// Status of the transactions to export
+    #[builder(setter(strip_option), default)]
+    pub status: Option<Status>,
+    /// Currency of the transactions to export
+    #[builder(setter(strip_option), default)]
+    pub currency: Option<Currency>,
+    /// Amount of the transactions to export, in the subunit of the currency
+    #[builder(setter(strip_option), default)]
+    pub amount: Option<u64>,
+    /// Whether to export only settled (`true`) or only unsettled (`false`) transactions
+    #[builder(setter(strip_option), default)]
+    pub settled: Option<bool>,
+    /// ID of the settlement whose transactions are exported
+    #[builder(setter(strip_option), default)]
+    pub settlement: Option<u64>,
+    /// ID of the payment page whose transactions are exported
+    #[builder(setter(strip_option), default)]
+    pub payment_page: Option<u64>,
+}
+
+impl ExportTransactionsRequest {
+    /// The query parameters of the filters that are set
+    pub(crate) fn to_query(&self) -> Vec<(&'static str, String)> {
+        let mut query = Vec::new();
+        if let Some(from) = &self.from {
+            query.push(("from", from.clone()));
+        }
+        if let Some(to) = &self.to {
+            query.push(("to", to.clone()));
+        }
+        if let Some(customer) = self.customer {
+            query.push(("customer", customer.to_string()));
+        }
+        if let Some(status) = &self.status {
+            query.push(("status", status.to_string()));
+        }
+        if let Some(currency) = &self.currency {
+            query.push(("currency", currency.to_string()));
+        }
+        if let Some(amount) = self.amount {
+            query.push(("amount", amount.to_string()));
+        }
+        if let Some(settled) = self.settled {
+            query.push(("settled", settled.to_string()));
+        }
+        if let Some(settlement) = self.settlement {
+            query.push(("settlement", settlement.to_string()));
+        }
+        if let Some(payment_page) = self.payment_page {
+            query.push(("payment_page", payment_page.to_string()));
+        }
+        query
+    }
 }
 
 /// This struct is used to create a partial debit transaction body for creating a partial debit using the Paystack API.
 /// This struct should be created using the `PartialDebitTransactionRequestBuilder`
 /// The derive Builder allows for the automatic creation of the BuilderPattern
 #[derive(Debug, Clone, Serialize, Default, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
 pub struct PartialDebitTransactionRequest {
     /// Authorization Code
     authorization_code: String,
@@ -67,11 +207,42 @@ pub struct PartialDebitTransactionRequest {
     /// Unique transaction reference. Only `-`, `.`, `=` and alphanumeric characters allowed.
     #[builder(default)]
     reference: Option<String>,
-    /// Minimum amount to charge
+    /// Minimum amount to charge. Must not be more than `amount`.
     #[builder(default)]
     at_least: Option<String>,
 }
 
+impl PartialDebitTransactionRequestBuilder {
+    /// Checks that the amounts are positive and the `at_least` floor does not exceed `amount`.
+    fn validate(&self) -> Result<(), String> {
+        let Some(amount) = &self.amount else {
+            return Ok(());
+        };
+        let amount = parse_positive_amount("amount", amount)?;
+
+        if let Some(Some(at_least)) = &self.at_least {
+            let at_least = parse_positive_amount("at_least", at_least)?;
+            if at_least > amount {
+                return Err(format!(
+                    "at_least ({at_least}) cannot be greater than amount ({amount})"
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses an amount in the subunit of a currency, ensuring it is greater than zero.
+fn parse_positive_amount(field: &str, value: &str) -> Result<u64, String> {
+    match value.parse::<u64>() {
+        Ok(amount) if amount > 0 => Ok(amount),
+        _ => Err(format!(
+            "{field} must be a positive whole number, got {value:?}"
+        )),
+    }
+}
+
 /// This struct represents the data of the transaction response.
 #[derive(Deserialize, Debug, Clone, Default)]
 pub struct TransactionResponseData {
@@ -83,13 +254,21 @@ pub struct TransactionResponseData {
     pub reference: String,
 }
 
+impl TransactionResponseData {
+    /// The access code to resume the transaction with the Paystack Popup (InlineJS) checkout,
+    /// as an alternative to redirecting the customer to the `authorization_url`.
+    pub fn popup_access_code(&self) -> &str {
+        &self.access_code
+    }
+}
+
 /// This struct represents the data of the transaction status response.
 #[derive(Deserialize, Serialize, Debug, Clone, Default)]
 pub struct TransactionStatusData {
     /// Id of the Transaction
     pub id: u64,
-    /// Status of the Transaction. It can be `success`, `abandoned` or `failed`
-    pub status: String,
+    /// Status of the Transaction e.g. `success`, `abandoned` or `failed`
+    pub status: TransactionStatus,
     /// Reference of the Transaction
     pub reference: String,
     /// Amount of the transaction in the lowest denomination of the currency e.g. Kobo for NGN and cent for USD.
@@ -102,8 +281,8 @@ pub struct TransactionStatusData {
     pub paid_at: Option<String>,
     /// Time the Transaction was created.
     pub created_at: String,
-    /// Transaction channel. It can be `card` or `bank`.
-    pub channel: String,
+    /// Channel the Transaction was paid with e.g. `card` or `bank`.
+    pub channel: PaymentChannel,
     /// Currency code of the Transaction e.g. `NGN for Nigerian Naira` and `USD for US Dollar`.
     pub currency: String,
     /// IP address of the computers the Transaction has passed through.
@@ -116,13 +295,168 @@ pub struct TransactionStatusData {
     pub customer: CustomerResponseData,
     /// Transaction authorization data.
     pub authorization: Authorization,
-    /// Transaction plan.
+    /// Code of the plan, if the transaction is a charge of a subscription.
+    /// Paystack sends either the code or the plan object, whose details are kept in `plan_object`.
+    #[serde(default, deserialize_with = "plan_code_from_string_or_object")]
     pub plan: Option<String>,
+    /// Details of the plan, if the transaction is a charge of a subscription.
+    /// Paystack sends an empty object for one-off transactions, which is read as `None`.
+    #[serde(default, deserialize_with = "empty_object_as_none")]
+    pub plan_object: Option<TransactionPlan>,
+    /// The split the transaction was settled with, if it was a split payment.
+    #[serde(default, deserialize_with = "empty_object_as_none")]
+    pub split: Option<SplitInfo>,
+    /// Step by step log of the payment attempt.
+    #[serde(default)]
+    pub log: Option<TransactionTimelineData>,
+    /// Where the transaction originated from e.g. the API or a Paystack Terminal.
+    #[serde(default, deserialize_with = "empty_object_as_none")]
+    pub source: Option<TransactionSource>,
+    /// Data captured by the Paystack Terminal for in-person payments.
+    /// It is not sent for online transactions.
+    #[serde(default, deserialize_with = "empty_object_as_none")]
+    pub pos_transaction_data: Option<serde_json::Value>,
+    /// Number of the receipt printed by the Paystack Terminal. It is not sent for online transactions.
+    #[serde(default)]
+    pub receipt_number: Option<String>,
+    /// ID of the Paystack Commerce order the transaction paid for, if any.
+    #[serde(default)]
+    pub order_id: Option<u64>,
+    /// Amount originally requested from the customer, in the lowest denomination of the currency.
+    /// It differs from `amount` for partial debits.
+    #[serde(default)]
+    pub requested_amount: Option<u64>,
+}
+
+impl TransactionStatusData {
+    /// Classifies why a failed transaction was declined, to decide whether to retry it,
+    /// e.g. with another card, or to stop.
+    ///
+    /// The kind is read from the gateway response and message, matching the wording used by
+    /// the card processors and the ISO 8583 response codes some of them include (e.g. `51`).
+    ///
+    /// # Returns
+    /// The kind of decline, `DeclineKind::Other` if the reason is not recognised,
+    /// or `None` if the transaction did not fail
+    pub fn decline_kind(&self) -> Option<DeclineKind> {
+        if self.status != TransactionStatus::Failed {
+            return None;
+        }
+
+        let kind = classify_decline(&self.gateway_response);
+        match (&kind, &self.message) {
+            (DeclineKind::Other, Some(message)) => Some(classify_decline(message)),
+            _ => Some(kind),
+        }
+    }
+}
+
+/// Why a transaction was declined, as returned by `TransactionStatusData::decline_kind`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DeclineKind {
+    /// The account does not have enough funds. Another card may succeed.
+    InsufficientFunds,
+    /// The card was declined by the issuer, or is not allowed to make the payment.
+    CardDeclined,
+    /// The card has expired. Another card may succeed.
+    ExpiredCard,
+    /// The issuer declined without a reason ("Do not honor"). Retrying the same card rarely helps.
+    DoNotHonor,
+    /// The issuer or processor did not respond in time. The same card may succeed later.
+    Timeout,
+    /// A reason not recognised by this crate
+    Other,
+}
+
+/// Maps a gateway response or message to a kind of decline.
+fn classify_decline(response: &str) -> DeclineKind {
+    let response = response.to_ascii_lowercase();
+    let has_code = |codes: &[&str]| {
+        response
+            .split(|c: char| !c.is_ascii_digit())
+            .any(|token| codes.contains(&token))
+    };
+
+    if response.contains("insufficient") || response.contains("not sufficient") || has_code(&["51"])
+    {
+        DeclineKind::InsufficientFunds
+    } else if response.contains("expired") || has_code(&["33", "54"]) {
+        DeclineKind::ExpiredCard
+    } else if response.contains("do not honor")
+        || response.contains("do not honour")
+        || has_code(&["05"])
+    {
+        DeclineKind::DoNotHonor
+    } else if response.contains("timeout")
+        || response.contains("timed out")
+        || response.contains("issuer unavailable")
+        || response.contains("inoperative")
+        || has_code(&["68", "91"])
+    {
+        DeclineKind::Timeout
+    } else if response.contains("declined")
+        || response.contains("not permitted")
+        || response.contains("restricted")
+        || has_code(&["57", "62"])
+    {
+        DeclineKind::CardDeclined
+    } else {
+        DeclineKind::Other
+    }
+}
+
+/// A transaction together with its refunds, as returned by `TransactionEndpoints::fetch_with_context`.
+#[derive(Debug, Clone, Default)]
+pub struct TransactionContext {
+    /// The transaction
+    pub transaction: TransactionStatusData,
+    /// All the refunds made for the transaction. It is empty if the refunds could not be fetched.
+    pub refunds: Vec<RefundData>,
+}
+
+/// This struct represents the plan a subscription transaction was charged for.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+pub struct TransactionPlan {
+    /// ID of the plan.
+    pub id: Option<u64>,
+    /// Name of the plan.
+    pub name: Option<String>,
+    /// Code of the plan e.g. `PLN_gx2wn530m0i3w3m`.
+    pub plan_code: Option<String>,
+    /// Description of the plan.
+    pub description: Option<String>,
+    /// Amount of the plan in the subunit of the currency.
+    pub amount: Option<u64>,
+    /// How often the plan is charged.
+    pub interval: Option<Interval>,
+    /// If invoices are sent to the customer.
+    pub send_invoices: Option<bool>,
+    /// If text messages are sent to the customer.
+    pub send_sms: Option<bool>,
+    /// Currency of the plan.
+    pub currency: Option<Currency>,
+}
+
+/// This struct represents the origin of a transaction.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+pub struct TransactionSource {
+    /// The kind of source e.g. `api`, `web` or `offline` for Terminal payments.
+    #[serde(rename = "type")]
+    pub source_type: Option<String>,
+    /// The product the transaction came from e.g. `merchant_api` or `pos`.
+    pub source: Option<String>,
+    /// The route used to start the transaction e.g. `transaction_initialize`.
+    pub entry_point: Option<String>,
+    /// Identifier of the source, such as the Terminal ID for in-person payments.
+    pub identifier: Option<String>,
 }
 
 /// This struct represents the transaction timeline data.
 #[derive(Deserialize, Serialize, Debug, Clone, Default)]
 pub struct TransactionTimelineData {
+    /// Time the customer started the payment, as a unix timestamp.
+    pub start_time: Option<u64>,
     /// Time spent in carrying out the transaction in ms.
     pub time_spent: Option<u32>,
     /// Number of attempts for the transaction.
@@ -136,23 +470,65 @@ pub struct TransactionTimelineData {
     /// If transaction was carried out with mobile.
     pub mobile: Option<bool>,
     /// Transaction inputs i.e. messages associated with the transaction.
-    pub input: Option<String>,
+    pub input: Option<Vec<serde_json::Value>>,
     /// Transaction channel.
     pub channel: Option<String>,
     /// Transaction history.
-    pub history: Option<Vec<TransactionHistoryResponse>>,
+    pub history: Option<Vec<TimelineEvent>>,
 }
 
-/// This struct represents the transaction history data
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct TransactionHistoryResponse {
-    /// Transaction action.
+/// A single step in the transaction timeline
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct TimelineEvent {
+    /// The kind of step taken.
     #[serde(rename = "type")]
-    pub action_type: String,
-    /// Description of the action.
+    pub event_type: TimelineEventType,
+    /// Description of the step.
     pub message: String,
-    /// Time action was taken in ms.
-    pub time: u32,
+    /// Time the step was taken, in seconds from the start of the payment.
+    pub time: u64,
+}
+
+/// The kind of step recorded in a transaction timeline.
+///
+/// Types not known to this crate are kept in the `Other` variant.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum TimelineEventType {
+    /// The payment page was opened
+    Open,
+    /// The customer entered payment details
+    Input,
+    /// The customer took an action e.g. attempted to pay
+    Action,
+    /// The customer was asked to authenticate the payment
+    Auth,
+    /// The payment was successful
+    Success,
+    /// An error occurred during the payment
+    Error,
+    /// The payment page was closed
+    Close,
+    /// An event type not yet supported by this crate
+    #[serde(untagged)]
+    Other(String),
+}
+
+impl fmt::Display for TimelineEventType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let event_type = match self {
+            TimelineEventType::Open => "open",
+            TimelineEventType::Input => "input",
+            TimelineEventType::Action => "action",
+            TimelineEventType::Auth => "auth",
+            TimelineEventType::Success => "success",
+            TimelineEventType::Error => "error",
+            TimelineEventType::Close => "close",
+            TimelineEventType::Other(event_type) => event_type,
+        };
+        write!(f, "{event_type}")
+    }
 }
 
 /// Transaction total data.
@@ -163,11 +539,11 @@ pub struct TransactionTotalData {
     /// Total of unique number of customers in the integration.
     pub unique_customers: Option<u32>,
     /// Total volume of transaction in the integration.
-    pub total_volume: Option<u32>,
+    pub total_volume: Option<u64>,
     /// Total volume of transaction broken down by currency.
     pub total_volume_by_currency: Option<Vec<VolumeByCurrency>>,
     /// Total volume of pending transfers.
-    pub pending_transfers: Option<u32>,
+    pub pending_transfers: Option<u64>,
     /// Total volume of pending transfer broken down by currency.
     pub pending_transfers_by_currency: Option<Vec<VolumeByCurrency>>,
 }
@@ -178,7 +554,7 @@ pub struct VolumeByCurrency {
     /// Currency code.
     pub currency: String,
     /// Amount in the lowest denomination of the currency.
-    pub amount: u32,
+    pub amount: u64,
 }
 
 /// Export transaction response data.
@@ -196,6 +572,50 @@ pub enum TransactionIdentifier {
     Reference(String),
 }
 
+/// The maximum length of a reference generated with a prefix, including the prefix.
+pub const MAX_REFERENCE_LENGTH: usize = 100;
+/// The length of the unique part appended to the prefix of a generated reference.
+const GENERATED_REFERENCE_SUFFIX_LENGTH: usize = 20;
+
+/// Checks that a prefix for generated references only uses the characters Paystack allows
+/// in a reference (`-`, `.`, `=` and alphanumeric characters) and leaves room for the unique part.
+pub(crate) fn validate_reference_prefix(prefix: &str) -> Result<(), String> {
+    if prefix.is_empty() {
+        return Err("the reference prefix is empty".to_string());
+    }
+
+    if let Some(invalid) = prefix
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '=')))
+    {
+        return Err(format!(
+            "the reference prefix {prefix:?} contains {invalid:?}, only `-`, `.`, `=` and alphanumeric characters are allowed"
+        ));
+    }
+
+    let max_prefix_length = MAX_REFERENCE_LENGTH - GENERATED_REFERENCE_SUFFIX_LENGTH;
+    if prefix.len() > max_prefix_length {
+        return Err(format!(
+            "the reference prefix {prefix:?} is longer than {max_prefix_length} characters"
+        ));
+    }
+
+    Ok(())
+}
+
+/// Generates a unique reference starting with the prefix, from the current time and a counter.
+pub(crate) fn generate_reference(prefix: &str) -> String {
+    static COUNTER: AtomicU16 = AtomicU16::new(0);
+
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as u64)
+        .unwrap_or_default();
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+
+    format!("{prefix}{nanos:016x}{count:04x}")
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -217,6 +637,338 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn can_create_partial_debit_with_valid_floor() -> Result<(), Box<dyn Error>> {
+        let request = PartialDebitTransactionRequestBuilder::default()
+            .authorization_code("AUTH_72btv547".to_string())
+            .currency(Currency::NGN)
+            .amount("20000".to_string())
+            .email("customer@email.com".to_string())
+            .at_least(Some("10000".to_string()))
+            .build()?;
+
+        assert_eq!(request.at_least, Some("10000".to_string()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn cannot_create_partial_debit_with_floor_above_amount() {
+        let request = PartialDebitTransactionRequestBuilder::default()
+            .authorization_code("AUTH_72btv547".to_string())
+            .currency(Currency::NGN)
+            .amount("20000".to_string())
+            .email("customer@email.com".to_string())
+            .at_least(Some("30000".to_string()))
+            .build();
+
+        assert!(matches!(
+            request,
+            Err(PartialDebitTransactionRequestBuilderError::ValidationError(msg)) if msg.contains("at_least")
+        ));
+    }
+
+    #[test]
+    fn can_create_partial_debit_without_floor() -> Result<(), Box<dyn Error>> {
+        let request = PartialDebitTransactionRequestBuilder::default()
+            .authorization_code("AUTH_72btv547".to_string())
+            .currency(Currency::NGN)
+            .amount("20000".to_string())
+            .email("customer@email.com".to_string())
+            .build()?;
+
+        assert_eq!(request.at_least, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn cannot_create_partial_debit_with_non_positive_amount() {
+        let request = PartialDebitTransactionRequestBuilder::default()
+            .authorization_code("AUTH_72btv547".to_string())
+            .currency(Currency::NGN)
+            .amount("0".to_string())
+            .email("customer@email.com".to_string())
+            .build();
+
+        assert!(request.is_err());
+    }
+
+    #[test]
+    fn can_deserialize_timeline_history() -> Result<(), Box<dyn Error>> {
+        let timeline: TransactionTimelineData = serde_json::from_str(
+            r#"{
+                "start_time": 1724318098,
+                "time_spent": 4,
+                "attempts": 1,
+                "errors": 0,
+                "success": true,
+                "mobile": false,
+                "input": [],
+                "history": [
+                    {"type": "open", "message": "Opened payment page", "time": 1},
+                    {"type": "action", "message": "Attempted to pay with card", "time": 3},
+                    {"type": "auth", "message": "Authentication Required: pin", "time": 3},
+                    {"type": "success", "message": "Successfully paid with card", "time": 4},
+                    {"type": "fingerprint", "message": "Device fingerprinted", "time": 4}
+                ]
+            }"#,
+        )?;
+
+        let history = timeline.history.unwrap();
+        assert_eq!(history.len(), 5);
+        assert_eq!(history[0].event_type, TimelineEventType::Open);
+        assert_eq!(history[1].event_type, TimelineEventType::Action);
+        assert_eq!(history[2].event_type, TimelineEventType::Auth);
+        assert_eq!(history[3].event_type, TimelineEventType::Success);
+        assert_eq!(history[3].message, "Successfully paid with card");
+        assert_eq!(history[3].time, 4);
+        assert_eq!(
+            history[4].event_type,
+            TimelineEventType::Other("fingerprint".to_string())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn can_deserialize_pos_transaction() -> Result<(), Box<dyn Error>> {
+        let transaction: TransactionStatusData = serde_json::from_str(
+            r#"{
+                "id": 4099260516,
+                "status": "success",
+                "reference": "T592386937184729",
+                "amount": 50000,
+                "message": null,
+                "gateway_response": "Approved",
+                "paid_at": "2024-08-22T09:15:02.000Z",
+                "created_at": "2024-08-22T09:14:24.000Z",
+                "channel": "card",
+                "currency": "NGN",
+                "ip_address": null,
+                "metadata": null,
+                "fees": 750,
+                "customer": {
+                    "id": 181873746,
+                    "email": "demo@test.com",
+                    "customer_code": "CUS_1rkzaqsv4rrhqo6"
+                },
+                "authorization": {
+                    "authorization_code": "AUTH_uh8bcl3zbn",
+                    "channel": "card",
+                    "reusable": false
+                },
+                "plan": null,
+                "source": {
+                    "type": "offline",
+                    "source": "pos",
+                    "entry_point": "pos_initialize",
+                    "identifier": "2232WE17"
+                },
+                "pos_transaction_data": {
+                    "terminal_id": "2232WE17",
+                    "rrn": "000012345678"
+                },
+                "receipt_number": "10102",
+                "order_id": null,
+                "requested_amount": 50000
+            }"#,
+        )?;
+
+        let source = transaction.source.unwrap();
+        assert_eq!(source.source_type, Some("offline".to_string()));
+        assert_eq!(source.source, Some("pos".to_string()));
+        assert_eq!(source.identifier, Some("2232WE17".to_string()));
+        assert_eq!(
+            transaction.pos_transaction_data.unwrap()["terminal_id"],
+            "2232WE17"
+        );
+        assert_eq!(transaction.receipt_number, Some("10102".to_string()));
+        assert_eq!(transaction.order_id, None);
+        assert_eq!(transaction.requested_amount, Some(50000));
+
+        Ok(())
+    }
+
+    #[test]
+    fn can_deserialize_online_transaction_without_pos_data() -> Result<(), Box<dyn Error>> {
+        let transaction: TransactionStatusData = serde_json::from_str(
+            r#"{
+                "id": 4099260517,
+                "status": "success",
+                "reference": "re4lyvq3s3",
+                "amount": 20000,
+                "gateway_response": "Successful",
+                "created_at": "2024-08-22T09:14:24.000Z",
+                "channel": "card",
+                "currency": "NGN",
+                "customer": {
+                    "id": 181873746,
+                    "email": "demo@test.com",
+                    "customer_code": "CUS_1rkzaqsv4rrhqo6"
+                },
+                "authorization": {},
+                "source": {},
+                "pos_transaction_data": null
+            }"#,
+        )?;
+
+        assert!(transaction.source.is_none());
+        assert!(transaction.pos_transaction_data.is_none());
+        assert!(transaction.receipt_number.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn can_deserialize_subscription_transaction() -> Result<(), Box<dyn Error>> {
+        let transaction: TransactionStatusData = serde_json::from_str(
+            r#"{
+                "id": 4099260518,
+                "status": "success",
+                "reference": "sub_charge_7fb3c1",
+                "amount": 50000,
+                "gateway_response": "Approved",
+                "created_at": "2024-09-01T00:00:04.000Z",
+                "channel": "card",
+                "currency": "NGN",
+                "customer": {
+                    "id": 181873746,
+                    "email": "demo@test.com",
+                    "customer_code": "CUS_1rkzaqsv4rrhqo6"
+                },
+                "authorization": {
+                    "authorization_code": "AUTH_uh8bcl3zbn",
+                    "reusable": true
+                },
+                "plan": "PLN_gx2wn530m0i3w3m",
+                "plan_object": {
+                    "id": 28,
+                    "name": "Monthly retainer",
+                    "plan_code": "PLN_gx2wn530m0i3w3m",
+                    "description": null,
+                    "amount": 50000,
+                    "interval": "monthly",
+                    "send_invoices": true,
+                    "send_sms": true,
+                    "currency": "NGN"
+                }
+            }"#,
+        )?;
+
+        assert_eq!(transaction.plan, Some("PLN_gx2wn530m0i3w3m".to_string()));
+        let plan = transaction.plan_object.unwrap();
+        assert_eq!(plan.plan_code, Some("PLN_gx2wn530m0i3w3m".to_string()));
+        assert_eq!(plan.interval, Some(Interval::Monthly));
+        assert_eq!(plan.amount, Some(50000));
+
+        Ok(())
+    }
+
+    #[test]
+    fn one_off_transaction_has_no_plan_object() -> Result<(), Box<dyn Error>> {
+        let transaction: TransactionStatusData = serde_json::from_str(
+            r#"{
+                "id": 4099260519,
+                "status": "success",
+                "reference": "re4lyvq3s4",
+                "amount": 20000,
+                "gateway_response": "Successful",
+                "created_at": "2024-08-22T09:14:24.000Z",
+                "channel": "card",
+                "currency": "NGN",
+                "customer": {
+                    "id": 181873746,
+                    "email": "demo@test.com",
+                    "customer_code": "CUS_1rkzaqsv4rrhqo6"
+                },
+                "authorization": {},
+                "plan": null,
+                "plan_object": {}
+            }"#,
+        )?;
+
+        assert!(transaction.plan.is_none());
+        assert!(transaction.plan_object.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn can_deserialize_split_transaction() -> Result<(), Box<dyn Error>> {
+        let transaction: TransactionStatusData = serde_json::from_str(
+            r#"{
+                "id": 4099260520,
+                "status": "success",
+                "reference": "split_7fb3c1",
+                "amount": 100000,
+                "gateway_response": "Approved",
+                "created_at": "2024-09-01T00:00:04.000Z",
+                "channel": "card",
+                "currency": "NGN",
+                "customer": {
+                    "id": 181873746,
+                    "email": "demo@test.com",
+                    "customer_code": "CUS_1rkzaqsv4rrhqo6"
+                },
+                "authorization": {},
+                "split": {
+                    "id": 1461,
+                    "name": "Marketplace split",
+                    "split_code": "SPL_98WF13Eb3w",
+                    "formula": { "type": "percentage", "subaccounts": [] },
+                    "shares": {
+                        "paystack": 1500,
+                        "subaccounts": [
+                            {
+                                "amount": 29550,
+                                "original_share": 30,
+                                "subaccount_code": "ACCT_6uujpqtzmnufzkw",
+                                "name": "Vendor One"
+                            }
+                        ],
+                        "integration": 68950
+                    }
+                }
+            }"#,
+        )?;
+
+        let split = transaction.split.unwrap();
+        assert_eq!(split.split_code, Some("SPL_98WF13Eb3w".to_string()));
+        assert_eq!(split.subaccount_shares()[0].amount, Some(29550));
+        let shares = split.shares.unwrap();
+        assert_eq!(shares.paystack, Some(1500));
+        assert_eq!(shares.integration, Some(68950));
+        assert_eq!(
+            shares.subaccounts[0].subaccount_code,
+            Some("ACCT_6uujpqtzmnufzkw".to_string())
+        );
+        assert_eq!(shares.subaccounts[0].amount, Some(29550));
+
+        Ok(())
+    }
+
+    #[test]
+    fn can_deserialize_initialized_transaction() -> Result<(), Box<dyn Error>> {
+        let transaction: TransactionResponseData = serde_json::from_str(
+            r#"{
+                "authorization_url": "https://checkout.paystack.com/0peioxfhpn",
+                "access_code": "0peioxfhpn",
+                "reference": "7PVGX8MEk85tgeEpVDtD"
+            }"#,
+        )?;
+
+        assert_eq!(
+            transaction.authorization_url,
+            "https://checkout.paystack.com/0peioxfhpn"
+        );
+        assert_eq!(transaction.access_code, "0peioxfhpn");
+        assert_eq!(transaction.reference, "7PVGX8MEk85tgeEpVDtD");
+        assert_eq!(transaction.popup_access_code(), "0peioxfhpn");
+
+        Ok(())
+    }
+
     #[test]
     fn cannot_create_transaction_body_without_compulsory_field() -> Result<(), Box<dyn Error>> {
         let transaction = TransactionRequestBuilder::default()
@@ -227,4 +979,77 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn reference_prefix_must_use_allowed_characters() {
+        assert!(validate_reference_prefix("ORD-").is_ok());
+        assert!(validate_reference_prefix("sub.2024=").is_ok());
+        assert!(validate_reference_prefix("").is_err());
+        assert!(validate_reference_prefix("ORD_").is_err());
+        assert!(validate_reference_prefix("ORD ").is_err());
+        assert!(validate_reference_prefix(&"A".repeat(81)).is_err());
+    }
+
+    #[test]
+    fn generated_references_are_unique_and_within_limit() {
+        let prefix = "A".repeat(80);
+        let first = generate_reference(&prefix);
+        let second = generate_reference(&prefix);
+
+        assert!(first.starts_with(&prefix));
+        assert_eq!(first.len(), MAX_REFERENCE_LENGTH);
+        assert_ne!(first, second);
+        assert!(first.chars().all(|c| c.is_ascii_alphanumeric()));
+    }
+
+    fn failed_transaction(gateway_response: &str) -> TransactionStatusData {
+        TransactionStatusData {
+            status: TransactionStatus::Failed,
+            gateway_response: gateway_response.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn decline_kind_maps_known_gateway_responses() {
+        let cases = [
+            ("Insufficient Funds", DeclineKind::InsufficientFunds),
+            ("Declined: 51", DeclineKind::InsufficientFunds),
+            ("Expired Card", DeclineKind::ExpiredCard),
+            ("Do Not Honor", DeclineKind::DoNotHonor),
+            ("Response code 05", DeclineKind::DoNotHonor),
+            ("Issuer or Switch Inoperative", DeclineKind::Timeout),
+            ("Transaction timed out", DeclineKind::Timeout),
+            ("Declined", DeclineKind::CardDeclined),
+            (
+                "Transaction Not Permitted to Cardholder",
+                DeclineKind::CardDeclined,
+            ),
+            ("Something unexpected", DeclineKind::Other),
+        ];
+
+        for (gateway_response, kind) in cases {
+            assert_eq!(
+                failed_transaction(gateway_response).decline_kind(),
+                Some(kind),
+                "{gateway_response}"
+            );
+        }
+    }
+
+    #[test]
+    fn decline_kind_falls_back_to_message() {
+        let mut transaction = failed_transaction("Unknown");
+        transaction.message = Some("Card expired".to_string());
+
+        assert_eq!(transaction.decline_kind(), Some(DeclineKind::ExpiredCard));
+    }
+
+    #[test]
+    fn decline_kind_is_none_for_successful_transaction() {
+        let mut transaction = failed_transaction("Approved");
+        transaction.status = TransactionStatus::Success;
+
+        assert_eq!(transaction.decline_kind(), None);
+    }
 }
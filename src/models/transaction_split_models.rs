@@ -2,7 +2,9 @@
 //! ========================
 //! This file contains the models for working with the transaction splits endpoint.
 
-use crate::{BearerType, Currency, Domain, SplitType, SubaccountBody, SubaccountData};
+use crate::{
+    Bearer, Currency, Domain, PaystackAPIError, SplitType, SubaccountBody, SubaccountData,
+};
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
@@ -20,9 +22,69 @@ pub struct TransactionSplitRequest {
     /// A list of object containing subaccount code and number of shares: `[{subaccount: ‘ACT_xxxxxxxxxx’, share: xxx},{...}]`
     subaccounts: Vec<SubaccountBody>,
     /// Any of subaccount
-    bearer_type: BearerType,
-    /// Subaccount code
-    bearer_subaccount: String,
+    bearer_type: Bearer,
+    /// Subaccount code of the subaccount that bears the charges.
+    /// This should be specified only if the `bearer_type` is subaccount
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(setter(strip_option), default)]
+    bearer_subaccount: Option<String>,
+}
+
+impl TransactionSplitRequest {
+    /// Checks the shares of the subaccounts against the type of the split.
+    pub(crate) fn validate_shares(&self) -> Result<(), PaystackAPIError> {
+        validate_split_shares(
+            &self.split_type,
+            self.subaccounts.iter().map(|subaccount| subaccount.share),
+        )
+    }
+}
+
+/// Checks that the shares of a split are valid for its type, before the split is sent to Paystack.
+///
+/// Percentage shares must each be between 0 and 100, and add up to at most 100:
+/// the main account receives what is left. Flat shares must be positive whole amounts
+/// in the subunit of the currency.
+pub(crate) fn validate_split_shares(
+    split_type: &SplitType,
+    shares: impl IntoIterator<Item = f32>,
+) -> Result<(), PaystackAPIError> {
+    let shares: Vec<f32> = shares.into_iter().collect();
+
+    match split_type {
+        SplitType::Percentage => {
+            if let Some(share) = shares.iter().find(|share| !(0.0..=100.0).contains(*share)) {
+                return Err(PaystackAPIError::Validation(format!(
+                    "percentage shares must be between 0 and 100, got {share}"
+                )));
+            }
+
+            // Summed in hundredths of a percent, so rounding errors do not push an exact 100% over
+            let total: i64 = shares
+                .iter()
+                .map(|share| (f64::from(*share) * 100.0).round() as i64)
+                .sum();
+            if total > 10_000 {
+                return Err(PaystackAPIError::Validation(format!(
+                    "the subaccount shares add up to {}%, which is {}% more than 100%",
+                    total as f64 / 100.0,
+                    (total - 10_000) as f64 / 100.0
+                )));
+            }
+        }
+        SplitType::Flat => {
+            if let Some(share) = shares
+                .iter()
+                .find(|share| **share <= 0.0 || share.fract() != 0.0)
+            {
+                return Err(PaystackAPIError::Validation(format!(
+                    "flat shares must be positive whole amounts, got {share}"
+                )));
+            }
+        }
+    }
+
+    Ok(())
 }
 
 /// Represents the percentage split data received in the JSON response.
@@ -47,9 +109,11 @@ pub struct TransactionSplitResponseData {
     #[serde(default)]
     pub active: Option<bool>,
     /// The bearer type of the percentage split.
-    pub bearer_type: String,
+    pub bearer_type: Bearer,
     /// The subaccount ID of the bearer associated with the percentage split.
-    pub bearer_subaccount: u32,
+    /// It is `None` unless the bearer type is subaccount.
+    #[serde(default)]
+    pub bearer_subaccount: Option<u32>,
     /// The creation timestamp of the percentage split.
     #[serde(rename = "createdAt")]
     pub created_at: Option<String>,
@@ -63,6 +127,87 @@ pub struct TransactionSplitResponseData {
     pub total_subaccounts: u32,
 }
 
+impl TransactionSplitResponseData {
+    /// The sum of the shares of the subaccounts in the split.
+    ///
+    /// For a percentage split, the main account receives what is left of 100.
+    /// For a flat split, it is the amount paid to the subaccounts, in the subunit of the currency.
+    pub fn total_share(&self) -> u32 {
+        self.subaccounts
+            .iter()
+            .map(|subaccount| subaccount.share)
+            .sum()
+    }
+}
+
+/// Confirms that a subaccount was removed from a transaction split,
+/// as returned by `TransactionSplitEndpoints::remove_split_subaccount`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RemovedSplitSubaccount {
+    /// The ID of the transaction split
+    pub split_id: String,
+    /// The code of the removed subaccount e.g. `ACCT_4hl4xenwpjy5wb`
+    pub subaccount: String,
+    /// Whether Paystack confirmed the removal
+    pub removed: bool,
+}
+
+/// Filters for listing the transaction splits of your integration.
+/// This struct should be created using the `ListTransactionSplitsRequestBuilder`.
+#[derive(Debug, Clone, Default, Builder)]
+pub struct ListTransactionSplitsRequest {
+    /// Name of the splits to list
+    #[builder(setter(strip_option), default)]
+    pub name: Option<String>,
+    /// Whether active or inactive splits are listed
+    #[builder(setter(strip_option), default)]
+    pub active: Option<bool>,
+    /// Field the splits are sorted by e.g. `name`. Defaults to the creation date on Paystack.
+    #[builder(setter(strip_option), default)]
+    pub sort_by: Option<String>,
+    /// Number of splits to return per page. Defaults to 50 on Paystack.
+    #[builder(setter(strip_option), default)]
+    pub per_page: Option<u32>,
+    /// Page of splits to return. Defaults to 1 on Paystack.
+    #[builder(setter(strip_option), default)]
+    pub page: Option<u32>,
+    /// Start of the date range e.g. `2024-08-01T00:00:00.000Z`
+    #[builder(setter(strip_option), default)]
+    pub from: Option<String>,
+    /// End of the date range e.g. `2024-08-31T23:59:59.000Z`
+    #[builder(setter(strip_option), default)]
+    pub to: Option<String>,
+}
+
+impl ListTransactionSplitsRequest {
+    /// The query parameters of the filters that are set
+    pub(crate) fn to_query(&self) -> Vec<(&'static str, String)> {
+        let mut query = Vec::new();
+        if let Some(name) = &self.name {
+            query.push(("name", name.clone()));
+        }
+        if let Some(active) = self.active {
+            query.push(("active", active.to_string()));
+        }
+        if let Some(sort_by) = &self.sort_by {
+            query.push(("sort_by", sort_by.clone()));
+        }
+        if let Some(per_page) = self.per_page {
+            query.push(("perPage", per_page.to_string()));
+        }
+        if let Some(page) = self.page {
+            query.push(("page", page.to_string()));
+        }
+        if let Some(from) = &self.from {
+            query.push(("from", from.clone()));
+        }
+        if let Some(to) = &self.to {
+            query.push(("to", to.clone()));
+        }
+        query
+    }
+}
+
 /// This struct is used to update a transaction split details on your integration.
 /// The struct is constructed using the `UpdateTransactionSplitRequestBuilder`
 #[derive(Serialize, Debug, Builder, Default)]
@@ -72,9 +217,12 @@ pub struct UpdateTransactionSplitRequest {
     /// True or False
     active: bool,
     /// Any of subaccount
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[builder(setter(strip_option), default)]
-    bearer_type: Option<BearerType>,
-    /// Subaccount code of a subaccount in the split group. This should be specified only if the `bearer_type is subaccount
+    bearer_type: Option<Bearer>,
+    /// Subaccount code of a subaccount in the split group e.g. `ACCT_4hl4xenwpjy5wb`.
+    /// This should be specified only if the `bearer_type` is subaccount
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[builder(setter(strip_option), default)]
-    bearer_subaccount: Option<SubaccountBody>,
+    bearer_subaccount: Option<String>,
 }
@@ -0,0 +1,61 @@
+//! Prelude
+//! ========
+//! Re-exports the types needed for typical usage of the crate, so a single import covers them.
+//!
+//! ```no_run
+//! use paystack::prelude::*;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let client = PaystackClient::<ReqwestClient>::from_env()?;
+//!
+//!     let body = TransactionRequestBuilder::default()
+//!         .amount("10000".to_string())
+//!         .email("email@example.com".to_string())
+//!         .currency(Currency::NGN)
+//!         .channel(vec![Channel::Card, Channel::BankTransfer])
+//!         .build()?;
+//!     let transaction: PaystackResult<TransactionResponseData> =
+//!         client.transactions.initialize_transaction(body).await;
+//!
+//!     let refund = CreateRefundRequestBuilder::default()
+//!         .transaction(transaction.into_data()?.reference)
+//!         .build()?;
+//!     match client.refund.create_refund(refund).await {
+//!         Ok(res) => println!("{}", res.message),
+//!         Err(e @ PaystackAPIError::Validation(_)) => eprintln!("invalid refund: {e}"),
+//!         Err(e) => return Err(e.into()),
+//!     }
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Everything here is also exported from the crate root.
+
+pub use crate::{HttpClient, PaystackClient, ReqwestClient};
+
+pub use crate::{PaystackAPIError, PaystackResult, Response, ResponseExt};
+
+pub use crate::{
+    Bearer, Channel, Currency, Domain, Interval, SettlementSchedule, SplitType, Status,
+    SubscriptionStatus, TransactionIdentifier,
+};
+
+pub use crate::{
+    ChargeRequest, ChargeRequestBuilder, CreateCustomerRequest, CreateCustomerRequestBuilder,
+    CreateRefundRequest, CreateRefundRequestBuilder, CreateSubaccountRequest,
+    CreateSubaccountRequestBuilder, CreateSubscriptionRequest, CreateSubscriptionRequestBuilder,
+    DedicatedVirtualAccountRequest, DedicatedVirtualAccountRequestBuilder,
+    FetchSubscriptionRequest, FetchSubscriptionRequestBuilder, PartialDebitTransactionRequest,
+    PartialDebitTransactionRequestBuilder, PlanRequest, PlanRequestBuilder, PlanUpdateRequest,
+    PlanUpdateRequestBuilder, TransactionRequest, TransactionRequestBuilder,
+    TransactionSplitRequest, TransactionSplitRequestBuilder, UpdateCustomerRequest,
+    UpdateCustomerRequestBuilder, UpdateTransactionSplitRequest,
+    UpdateTransactionSplitRequestBuilder,
+};
+
+pub use crate::{
+    CustomerResponseData, PlanResponseData, RefundData, SubaccountsResponseData, Subscription,
+    TransactionResponseData, TransactionSplitResponseData, TransactionStatusData,
+};
@@ -205,3 +205,69 @@ where
         _ => Err(serde::de::Error::custom("Expected bool or int")),
     }
 }
+
+pub fn empty_object_as_none<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: serde::de::DeserializeOwned,
+{
+    // Paystack sends `{}` instead of `null` for some unset nested objects
+    let v: Option<Value> = Option::deserialize(deserializer)?;
+    match v {
+        Some(Value::Object(map)) if map.is_empty() => Ok(None),
+        Some(Value::Null) | None => Ok(None),
+        Some(value) => T::deserialize(value)
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+    }
+}
+
+pub fn id_from_int_or_object<'de, D>(deserializer: D) -> Result<u32, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    // Paystack sends related records either as their ID or as the expanded object
+    let v = Value::deserialize(deserializer)?;
+    let id = match &v {
+        Value::Object(map) => map.get("id").and_then(Value::as_u64),
+        value => value.as_u64(),
+    };
+    id.and_then(|id| u32::try_from(id).ok()).ok_or_else(|| {
+        serde::de::Error::custom(format!("expected an ID or an object with an ID, got {v}"))
+    })
+}
+
+pub fn plan_code_from_string_or_object<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    // Paystack sends the plan of a transaction as its code, as the expanded object, or as `{}` if there is none
+    let v: Option<Value> = Option::deserialize(deserializer)?;
+    match v {
+        Some(Value::String(code)) => Ok(Some(code)),
+        Some(Value::Object(map)) if map.is_empty() => Ok(None),
+        Some(Value::Object(map)) => match map.get("plan_code") {
+            Some(Value::String(code)) => Ok(Some(code.clone())),
+            _ => Err(serde::de::Error::custom(
+                "expected a plan object with a plan code",
+            )),
+        },
+        Some(Value::Null) | None => Ok(None),
+        Some(value) => Err(serde::de::Error::custom(format!(
+            "expected a plan code or a plan object, got {value}"
+        ))),
+    }
+}
+
+pub fn option_json_string<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    // Paystack sends metadata set as stringified JSON back either as the string or as the parsed JSON
+    let v: Option<Value> = Option::deserialize(deserializer)?;
+    match v {
+        Some(Value::String(json)) => Ok(Some(json)),
+        Some(Value::Null) | None => Ok(None),
+        Some(value) => Ok(Some(value.to_string())),
+    }
+}
@@ -0,0 +1,278 @@
+//! Webhook
+//! ==========
+//! This file contains the helpers for checking that webhook events were sent by Paystack.
+//!
+//! Paystack signs every event with your secret key. The signature is the hex encoded
+//! HMAC-SHA512 of the raw request body, sent in the `x-paystack-signature` header.
+//!
+//! These are plain synchronous functions that do not use the HTTP client or need an async runtime,
+//! so they can be called from any web framework, sync or async.
+
+use crate::{CustomerIdentificationResult, PaystackAPIError};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha512;
+
+/// Name of the header Paystack sends the signature of a webhook event in
+pub const PAYSTACK_SIGNATURE_HEADER: &str = "x-paystack-signature";
+
+/// Name of the event sent when a customer was identified
+pub const CUSTOMER_IDENTIFICATION_SUCCESS_EVENT: &str = "customeridentification.success";
+/// Name of the event sent when a customer could not be identified
+pub const CUSTOMER_IDENTIFICATION_FAILED_EVENT: &str = "customeridentification.failed";
+
+type HmacSha512 = Hmac<Sha512>;
+
+/// A webhook event sent by Paystack.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WebhookEvent {
+    /// Name of the event e.g. `charge.success`
+    pub event: String,
+    /// Data of the event. Its shape depends on the event, e.g. a transaction for `charge.success`
+    pub data: serde_json::Value,
+}
+
+impl WebhookEvent {
+    /// Reads the result of a customer identification from a `customeridentification.success`
+    /// or `customeridentification.failed` event.
+    ///
+    /// `CustomersEndpoints::validate_customer` only starts the identification. Keep the code of the
+    /// customer when calling it, and match it with `customer_code` on the result to know when the
+    /// identification is done.
+    ///
+    /// # Returns
+    /// A Result containing the identification result, `None` if the event is not a customer
+    /// identification event, or a webhook error if its data cannot be read
+    ///
+    /// # Example
+    /// ```
+    /// use paystack::{PaystackAPIError, WebhookEvent};
+    ///
+    /// fn on_event(event: &WebhookEvent, pending_customer: &str) -> Result<(), PaystackAPIError> {
+    ///     if let Some(result) = event.customer_identification()? {
+    ///         if result.customer_code == pending_customer && result.identified {
+    ///             // The customer can now be assigned a dedicated virtual account
+    ///         }
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn customer_identification(
+        &self,
+    ) -> Result<Option<CustomerIdentificationResult>, PaystackAPIError> {
+        let identified = match self.event.as_str() {
+            CUSTOMER_IDENTIFICATION_SUCCESS_EVENT => true,
+            CUSTOMER_IDENTIFICATION_FAILED_EVENT => false,
+            _ => return Ok(None),
+        };
+
+        let mut result = CustomerIdentificationResult::deserialize(&self.data)
+            .map_err(|e| PaystackAPIError::Webhook(e.to_string()))?;
+        result.identified = identified;
+        Ok(Some(result))
+    }
+}
+
+/// Checks that a webhook event was signed by Paystack.
+///
+/// The comparison is done in constant time.
+///
+/// # Arguments
+/// * `secret_key` - The Paystack secret key of the integration
+/// * `raw_body` - The body of the request exactly as it was received, before any parsing
+/// * `signature` - The value of the `x-paystack-signature` header
+///
+/// # Returns
+/// `true` if the signature matches the body
+pub fn verify_webhook_signature(secret_key: &str, raw_body: &[u8], signature: &str) -> bool {
+    let Some(signature) = decode_hex(signature.trim()) else {
+        return false;
+    };
+
+    let mut mac = new_mac(secret_key);
+    mac.update(raw_body);
+    mac.verify_slice(&signature).is_ok()
+}
+
+/// Checks that a webhook event was signed by Paystack and deserializes it.
+///
+/// # Arguments
+/// * `secret_key` - The Paystack secret key of the integration
+/// * `raw_body` - The body of the request exactly as it was received, before any parsing
+/// * `signature` - The value of the `x-paystack-signature` header
+///
+/// # Returns
+/// A Result containing the event, or a webhook error if the signature does not match
+/// or the body is not a webhook event
+pub fn parse_webhook_event(
+    secret_key: &str,
+    raw_body: &[u8],
+    signature: &str,
+) -> Result<WebhookEvent, PaystackAPIError> {
+    if !verify_webhook_signature(secret_key, raw_body, signature) {
+        return Err(PaystackAPIError::Webhook(String::from(
+            "the signature does not match the body",
+        )));
+    }
+
+    serde_json::from_slice(raw_body).map_err(|e| PaystackAPIError::Webhook(e.to_string()))
+}
+
+/// Computes the signature Paystack would send for a webhook event.
+/// Use it to send realistic signed events to your webhook handler in tests.
+///
+/// # Arguments
+/// * `secret_key` - The Paystack secret key of the integration
+/// * `raw_body` - The body of the event, exactly as it will be sent
+///
+/// # Returns
+/// The hex encoded HMAC-SHA512 signature to send in the `x-paystack-signature` header
+#[cfg(any(test, feature = "test-util"))]
+pub fn sign_webhook(secret_key: &str, raw_body: &[u8]) -> String {
+    let mut mac = new_mac(secret_key);
+    mac.update(raw_body);
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+fn new_mac(secret_key: &str) -> HmacSha512 {
+    // HMAC accepts keys of any length, so this cannot fail
+    HmacSha512::new_from_slice(secret_key.as_bytes()).expect("HMAC can take a key of any size")
+}
+
+/// Decodes a hex string, returning `None` if it is not valid hex
+fn decode_hex(value: &str) -> Option<Vec<u8>> {
+    if !value.len().is_multiple_of(2) {
+        return None;
+    }
+
+    (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(value.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET_KEY: &str = "sk_test_xxxxxxxxxxxx";
+    const BODY: &[u8] = br#"{"event":"charge.success","data":{"reference":"re4lyvq3s3"}}"#;
+    const SIGNATURE: &str = "fe73fa1418a446367982d183bb6593642fc1a29ffcab0cf40914ca2988daa6547516716967f0bfa8a1a928c3ae1593585c86f09e26fb6272283b1551afed2886";
+
+    #[test]
+    fn can_verify_paystack_signature() {
+        assert!(verify_webhook_signature(SECRET_KEY, BODY, SIGNATURE));
+    }
+
+    #[test]
+    fn rejects_tampered_body_and_invalid_signature() {
+        let tampered = br#"{"event":"charge.success","data":{"reference":"another"}}"#;
+
+        assert!(!verify_webhook_signature(SECRET_KEY, tampered, SIGNATURE));
+        assert!(!verify_webhook_signature("sk_test_other", BODY, SIGNATURE));
+        assert!(!verify_webhook_signature(SECRET_KEY, BODY, "not-hex"));
+        assert!(!verify_webhook_signature(SECRET_KEY, BODY, ""));
+    }
+
+    #[test]
+    fn signed_webhook_round_trips() {
+        let signature = sign_webhook(SECRET_KEY, BODY);
+
+        assert_eq!(signature, SIGNATURE);
+        assert!(verify_webhook_signature(SECRET_KEY, BODY, &signature));
+    }
+
+    // Runs without an async runtime, as webhook handlers in sync frameworks would.
+    #[test]
+    fn can_parse_signed_event_without_runtime() {
+        let event = parse_webhook_event(SECRET_KEY, BODY, SIGNATURE).unwrap();
+
+        assert_eq!(event.event, "charge.success");
+        assert_eq!(event.data["reference"], "re4lyvq3s3");
+    }
+
+    #[test]
+    fn can_read_customer_identification_events() {
+        let success: WebhookEvent = serde_json::from_str(
+            r#"{
+                "event": "customeridentification.success",
+                "data": {
+                    "customer_id": "82796315",
+                    "customer_code": "CUS_XXXXXXXXXXXXXXX",
+                    "email": "email@email.com",
+                    "identification": {
+                        "country": "NG",
+                        "type": "bank_account",
+                        "bvn": "200*****677",
+                        "account_number": "012****789",
+                        "bank_code": "999991"
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+        let failed: WebhookEvent = serde_json::from_str(
+            r#"{
+                "event": "customeridentification.failed",
+                "data": {
+                    "customer_id": 82796315,
+                    "customer_code": "CUS_XXXXXXXXXXXXXXX",
+                    "email": "email@email.com",
+                    "identification": {
+                        "country": "NG",
+                        "type": "bank_account",
+                        "bvn": "200*****677",
+                        "account_number": "012****345",
+                        "bank_code": "999991"
+                    },
+                    "reason": "Account number or BVN is incorrect"
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let success = success.customer_identification().unwrap().unwrap();
+        let failed = failed.customer_identification().unwrap().unwrap();
+
+        assert!(success.identified);
+        assert_eq!(success.customer_code, "CUS_XXXXXXXXXXXXXXX");
+        assert_eq!(success.identification.identification_type, "bank_account");
+        assert_eq!(success.reason, None);
+        assert!(!failed.identified);
+        assert_eq!(
+            failed.reason.as_deref(),
+            Some("Account number or BVN is incorrect")
+        );
+    }
+
+    #[test]
+    fn other_events_are_not_customer_identifications() {
+        let event = parse_webhook_event(SECRET_KEY, BODY, SIGNATURE).unwrap();
+
+        assert_eq!(event.customer_identification().unwrap(), None);
+    }
+
+    #[test]
+    fn malformed_customer_identification_is_rejected() {
+        let event = WebhookEvent {
+            event: CUSTOMER_IDENTIFICATION_SUCCESS_EVENT.to_string(),
+            data: serde_json::json!({ "customer_code": "CUS_XXXXXXXXXXXXXXX" }),
+        };
+
+        assert!(matches!(
+            event.customer_identification(),
+            Err(PaystackAPIError::Webhook(_))
+        ));
+    }
+
+    #[test]
+    fn parse_rejects_invalid_signature() {
+        let event = parse_webhook_event("sk_test_other", BODY, SIGNATURE);
+
+        assert!(matches!(event, Err(PaystackAPIError::Webhook(msg)) if msg.contains("signature")));
+    }
+}
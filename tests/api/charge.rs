@@ -1,7 +1,9 @@
-use crate::helpers::get_paystack_client;
-use paystack::{Channel, ChargeRequestBuilder, Currency};
+use crate::helpers::{get_mock_http_client, get_paystack_client, mock_response, mock_transaction};
+use paystack::{Channel, ChargeRequestBuilder, Currency, TransactionEndpoints, TransactionStatus};
 use rand::Rng;
+use serde_json::json;
 use std::error::Error;
+use std::sync::Arc;
 
 /// Values are hardcoded in this test because of the nature of the test.
 /// The values reflect the values in my integration.
@@ -38,3 +40,40 @@ async fn charge_authorization_succeeds() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn charge_authorization_sends_recurring_charge() -> Result<(), Box<dyn Error>> {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let mut charge_data = mock_transaction(4099260516, "sub-2024-08-001");
+    charge_data["transaction_date"] = json!("2024-08-22T09:15:02.000Z");
+    http.respond_to_url(
+        "https://api.paystack.co/transaction/charge_authorization",
+        mock_response("Charge attempted", charge_data),
+    );
+    let transactions = TransactionEndpoints::new(key, Arc::new(http.clone()));
+
+    let charge = ChargeRequestBuilder::default()
+        .email("demo@test.com".to_string())
+        .amount("20000".to_string())
+        .authorization_code("AUTH_uh8bcl3zbn".to_string())
+        .reference("sub-2024-08-001".to_string())
+        .channel(vec![Channel::Card])
+        .queue(true)
+        .build()?;
+
+    // Act
+    let res = transactions.charge_authorization(charge).await?;
+
+    // Assert
+    let body = &http.requests()[0].body;
+    assert_eq!(body["authorization_code"], "AUTH_uh8bcl3zbn");
+    assert_eq!(body["reference"], "sub-2024-08-001");
+    assert_eq!(body["queue"], true);
+    assert_eq!(body["channels"], json!(["card"]));
+    let data = res.data.unwrap();
+    assert_eq!(data.status, TransactionStatus::Success);
+    assert_eq!(data.reference, "sub-2024-08-001");
+
+    Ok(())
+}
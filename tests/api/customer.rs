@@ -7,11 +7,15 @@ use fake::{
     Fake,
 };
 use paystack::{
-    CreateCustomerRequestBuilder, IdentificationType, RiskAction, UpdateCustomerRequestBuilder,
-    ValidateCustomerRequestBuilder,
+    CreateCustomerRequestBuilder, CustomersEndpoints, IdentificationType, PaystackAPIError,
+    RiskAction, UpdateCustomerRequestBuilder, ValidateCustomerRequestBuilder,
 };
+use serde_json::json;
+use std::sync::Arc;
 
-use crate::helpers::get_paystack_client;
+use crate::helpers::{
+    get_mock_http_client, get_paystack_client, mock_page, mock_response, mock_transaction,
+};
 
 #[tokio::test]
 async fn create_customer_with_valid_data_succeed() {
@@ -284,3 +288,167 @@ async fn can_blacklist_and_whitelist_a_customer() {
 }
 
 // TODO: make test for dea
+
+#[tokio::test]
+async fn summary_totals_customer_spend_per_currency() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let mut usd = mock_transaction(3, "T_usd");
+    usd["currency"] = json!("USD");
+    usd["amount"] = json!(1500);
+    http.respond_with(mock_page(
+        "Transactions retrieved",
+        json!([
+            mock_transaction(1, "T_first"),
+            mock_transaction(2, "T_second"),
+            usd
+        ]),
+        1,
+        1,
+    ));
+    let customers = CustomersEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = customers
+        .summary(181873746, Some("2024-01-01T00:00:00.000Z"), None)
+        .await
+        .expect("unable to summarize customer transactions");
+
+    // Assert
+    let requests = http.requests();
+    assert_eq!(requests[0].url, "https://api.paystack.co/transaction");
+    assert!(requests[0]
+        .query
+        .contains(&("customer".to_string(), "181873746".to_string())));
+    assert!(requests[0]
+        .query
+        .contains(&("status".to_string(), "success".to_string())));
+    let summary = res.data.unwrap();
+    assert_eq!(summary.transaction_count, 3);
+    assert_eq!(summary.total_spend["NGN"], 40000);
+    assert_eq!(summary.total_spend["USD"], 1500);
+}
+
+#[tokio::test]
+async fn summary_fails_when_the_filters_are_ignored() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let mut other_customer = mock_transaction(2, "T_other_customer");
+    other_customer["customer"]["id"] = json!(555);
+    let mut abandoned = mock_transaction(3, "T_abandoned");
+    abandoned["status"] = json!("abandoned");
+    http.respond_with(mock_response(
+        "Transactions retrieved",
+        json!([mock_transaction(1, "T_first"), other_customer]),
+    ))
+    .respond_with(mock_response(
+        "Transactions retrieved",
+        json!([mock_transaction(1, "T_first"), abandoned]),
+    ));
+    let customers = CustomersEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let other_customer_res = customers.summary(181873746, None, None).await;
+    let abandoned_res = customers.summary(181873746, None, None).await;
+
+    // Assert
+    assert!(matches!(
+        other_customer_res,
+        Err(PaystackAPIError::FilterNotHonoured(msg)) if msg.contains("555")
+    ));
+    assert!(matches!(
+        abandoned_res,
+        Err(PaystackAPIError::FilterNotHonoured(msg)) if msg.contains("abandoned")
+    ));
+}
+
+/// A customer as returned by the list customers endpoint
+fn mock_customer(id: u64, email: &str) -> serde_json::Value {
+    json!({
+        "id": id,
+        "integration": 100032,
+        "domain": "test",
+        "email": email,
+        "customer_code": format!("CUS_{id}"),
+        "risk_action": "default"
+    })
+}
+
+#[tokio::test]
+async fn find_by_email_domain_scans_every_page() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    // A first page shorter than requested, as if Paystack capped the page size,
+    // so only the page count tells that there is a second page
+    http.respond_with(mock_page(
+        "Customers retrieved",
+        json!([
+            mock_customer(1, "ada@acme.com"),
+            mock_customer(2, "grace@ACME.com"),
+            mock_customer(3, "mallory@notacme.com")
+        ]),
+        1,
+        2,
+    ));
+    http.respond_with(mock_page(
+        "Customers retrieved",
+        json!([
+            mock_customer(101, "linus@acme.com"),
+            mock_customer(102, "acme.com@example.com")
+        ]),
+        2,
+        2,
+    ));
+    let customers = CustomersEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = customers
+        .find_by_email_domain("@acme.com")
+        .await
+        .expect("unable to find customers by email domain");
+
+    // Assert
+    let requests = http.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[1]
+        .query
+        .contains(&("page".to_string(), "2".to_string())));
+    let emails: Vec<_> = res.data.unwrap().into_iter().map(|c| c.email).collect();
+    assert_eq!(
+        emails,
+        vec!["ada@acme.com", "grace@ACME.com", "linus@acme.com"]
+    );
+}
+
+#[tokio::test]
+async fn is_identified_checks_the_identified_flag() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let mut identified = mock_customer(1, "ada@acme.com");
+    identified["identified"] = json!(true);
+    let mut pending = mock_customer(2, "grace@acme.com");
+    pending["identified"] = json!(false);
+    http.respond_to_url(
+        "https://api.paystack.co/customer/CUS_1",
+        mock_response("Customer retrieved", identified),
+    );
+    http.respond_to_url(
+        "https://api.paystack.co/customer/CUS_2",
+        mock_response("Customer retrieved", pending),
+    );
+    let customers = CustomersEndpoints::new(key, Arc::new(http));
+
+    // Act
+    let identified = customers
+        .is_identified("CUS_1")
+        .await
+        .expect("unable to check the customer");
+    let pending = customers
+        .is_identified("CUS_2")
+        .await
+        .expect("unable to check the customer");
+
+    // Assert
+    assert_eq!(identified.data, Some(true));
+    assert_eq!(pending.data, Some(false));
+}
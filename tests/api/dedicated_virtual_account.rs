@@ -1,3 +1,321 @@
+use crate::helpers::{get_mock_http_client, mock_response};
+use paystack::{
+    DedicatedVirtualAccountEndpoints, DedicatedVirtualAccountRequestBuilder, PaystackAPIError,
+    SplitDedicatedAccountTransactionRequestBuilder,
+};
+use serde_json::{json, Value};
+use std::{sync::Arc, time::Duration};
+
+/// A dedicated virtual account payload with the given split configuration
+fn dedicated_account_payload(split_config: Value) -> Value {
+    json!({
+        "status": true,
+        "message": "Assigned Managed Account Successfully Created",
+        "data": {
+            "bank": { "name": "Test Bank", "id": 20, "slug": "test-bank" },
+            "account_name": "PAYSTACK/John Doe",
+            "account_number": "1234567890",
+            "assigned": true,
+            "currency": "NGN",
+            "metadata": null,
+            "active": true,
+            "id": 22495,
+            "created_at": "2021-11-03T11:18:13.000Z",
+            "updated_at": "2021-11-03T11:18:13.000Z",
+            "assignment": {
+                "integration": 463433,
+                "assignee_id": 17328797,
+                "assignee_type": "Customer",
+                "expired": false,
+                "account_type": "PAY-WITH-TRANSFER-RECURRING",
+                "assigned_at": "2021-11-03T11:18:13.176Z"
+            },
+            "split_config": split_config,
+            "customer": {
+                "id": 17328797,
+                "first_name": "John",
+                "last_name": "Doe",
+                "email": "john@example.com",
+                "customer_code": "CUS_ka4xutrdslg7cyf",
+                "phone": "+2348100000000",
+                "risk_action": "default"
+            }
+        }
+    })
+}
+
+/// The bank providers available for dedicated virtual accounts
+fn bank_providers_payload() -> Value {
+    mock_response(
+        "Dedicated account providers retrieved",
+        json!([
+            { "provider_slug": "access-bank", "bank_id": 1, "bank_name": "Access Bank", "id": 6 },
+            { "provider_slug": "wema-bank", "bank_id": 20, "bank_name": "Wema Bank", "id": 5 }
+        ]),
+    )
+}
+
 // TODO: need authorization in the paystack developer account to implement this test
 #[tokio::test]
 async fn can_create_dedicated_virtual_account() {}
+
+#[tokio::test]
+async fn can_add_split_to_dedicated_virtual_account() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(dedicated_account_payload(
+        json!({ "split_code": "SPL_e7jnRLtzla" }),
+    ));
+    let dedicated_virtual_account =
+        DedicatedVirtualAccountEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let body = SplitDedicatedAccountTransactionRequestBuilder::default()
+        .customer("CUS_ka4xutrdslg7cyf".to_string())
+        .split_code("SPL_e7jnRLtzla".to_string())
+        .preferred_bank("test-bank".to_string())
+        .build()
+        .expect("unable to build split request");
+    let res = dedicated_virtual_account
+        .split_dedicated_account_transaction(body)
+        .await
+        .expect("unable to split dedicated account transaction");
+
+    // Assert
+    let requests = http.requests();
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(
+        requests[0].url,
+        "https://api.paystack.co/dedicated_account/split"
+    );
+    assert_eq!(requests[0].body["split_code"], "SPL_e7jnRLtzla");
+    assert!(res.status);
+    let split_config = res.data.unwrap().split_config.unwrap();
+    assert_eq!(split_config.split_code, "SPL_e7jnRLtzla");
+}
+
+#[tokio::test]
+async fn can_remove_split_from_dedicated_virtual_account() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(dedicated_account_payload(json!({})));
+    let dedicated_virtual_account =
+        DedicatedVirtualAccountEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = dedicated_virtual_account
+        .remove_split_from_dedicated_account("1234567890".to_string())
+        .await
+        .expect("unable to remove split from dedicated account");
+
+    // Assert
+    let requests = http.requests();
+    assert_eq!(requests[0].method, "DELETE");
+    assert_eq!(
+        requests[0].url,
+        "https://api.paystack.co/dedicated_account/split"
+    );
+    assert_eq!(requests[0].body["account_number"], "1234567890");
+    assert!(res.status);
+    assert!(res.data.unwrap().split_config.is_none());
+}
+
+#[tokio::test]
+async fn create_dedicated_virtual_account_accepts_supported_preferred_bank() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(bank_providers_payload())
+        .respond_with(dedicated_account_payload(json!({})))
+        .respond_with(dedicated_account_payload(json!({})));
+    let dedicated_virtual_account =
+        DedicatedVirtualAccountEndpoints::new(key, Arc::new(http.clone()));
+    let body = DedicatedVirtualAccountRequestBuilder::default()
+        .customer("CUS_ka4xutrdslg7cyf".to_string())
+        .preferred_bank("wema-bank".to_string())
+        .build()
+        .expect("unable to build dedicated virtual account request");
+
+    // Act
+    let res = dedicated_virtual_account
+        .create_dedicated_virtual_account(body.clone())
+        .await
+        .expect("unable to create dedicated virtual account");
+    dedicated_virtual_account
+        .create_dedicated_virtual_account(body)
+        .await
+        .expect("unable to create dedicated virtual account");
+
+    // Assert
+    let requests = http.requests();
+    // the providers are fetched once and cached for the second request
+    assert_eq!(requests.len(), 3);
+    assert_eq!(
+        requests[0].url,
+        "https://api.paystack.co/dedicated_account/available_providers"
+    );
+    assert_eq!(requests[1].method, "POST");
+    assert_eq!(requests[1].body["preferred_bank"], "wema-bank");
+    assert_eq!(requests[2].method, "POST");
+    assert!(res.status);
+}
+
+#[tokio::test]
+async fn create_dedicated_virtual_account_rejects_unsupported_preferred_bank() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(bank_providers_payload());
+    let dedicated_virtual_account =
+        DedicatedVirtualAccountEndpoints::new(key, Arc::new(http.clone()));
+    let body = DedicatedVirtualAccountRequestBuilder::default()
+        .customer("CUS_ka4xutrdslg7cyf".to_string())
+        .preferred_bank("test-bank".to_string())
+        .build()
+        .expect("unable to build dedicated virtual account request");
+
+    // Act
+    let res = dedicated_virtual_account
+        .create_dedicated_virtual_account(body)
+        .await;
+
+    // Assert
+    match res {
+        Err(PaystackAPIError::Validation(msg)) => {
+            assert!(msg.contains("test-bank"), "unexpected error: {msg}");
+            assert!(
+                msg.contains("access-bank, wema-bank"),
+                "unexpected error: {msg}"
+            );
+        }
+        other => panic!("expected a validation error, got {other:?}"),
+    }
+    // only the providers were fetched, the account was not created
+    assert_eq!(http.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn bank_providers_are_fetched_again_after_cache_ttl() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(bank_providers_payload())
+        .respond_with(bank_providers_payload());
+    let dedicated_virtual_account =
+        DedicatedVirtualAccountEndpoints::new(key, Arc::new(http.clone()))
+            .with_provider_cache_ttl(Duration::ZERO);
+
+    // Act
+    dedicated_virtual_account
+        .providers()
+        .await
+        .expect("unable to get bank providers");
+    let providers = dedicated_virtual_account
+        .providers()
+        .await
+        .expect("unable to get bank providers");
+
+    // Assert
+    assert_eq!(http.requests().len(), 2);
+    assert_eq!(providers.len(), 2);
+    assert!(dedicated_virtual_account.cached_providers().is_none());
+}
+
+#[tokio::test]
+async fn refresh_providers_fills_the_cache() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(bank_providers_payload());
+    let dedicated_virtual_account =
+        DedicatedVirtualAccountEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let before = dedicated_virtual_account.cached_providers();
+    dedicated_virtual_account
+        .refresh_providers()
+        .await
+        .expect("unable to refresh bank providers");
+
+    // Assert
+    assert!(before.is_none());
+    let slugs: Vec<_> = dedicated_virtual_account
+        .cached_providers()
+        .expect("the providers were not cached")
+        .into_iter()
+        .map(|provider| provider.provider_slug)
+        .collect();
+    assert_eq!(slugs, vec!["access-bank", "wema-bank"]);
+}
+
+#[tokio::test]
+async fn unsuccessful_or_empty_bank_providers_are_not_cached() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(json!({ "status": false, "message": "Providers unavailable" }))
+        .respond_with(mock_response(
+            "Dedicated account providers retrieved",
+            json!([]),
+        ))
+        .respond_with(bank_providers_payload());
+    let dedicated_virtual_account =
+        DedicatedVirtualAccountEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let unsuccessful = dedicated_virtual_account.refresh_providers().await;
+    let empty = dedicated_virtual_account.refresh_providers().await;
+    let cached_after_failures = dedicated_virtual_account.cached_providers();
+    let providers = dedicated_virtual_account
+        .providers()
+        .await
+        .expect("unable to get bank providers");
+
+    // Assert
+    assert!(matches!(
+        unsuccessful,
+        Err(PaystackAPIError::DedicatedVirtualAccount(msg)) if msg.contains("Providers unavailable")
+    ));
+    assert!(matches!(
+        empty,
+        Err(PaystackAPIError::DedicatedVirtualAccount(_))
+    ));
+    assert!(cached_after_failures.is_none());
+    assert_eq!(providers.len(), 2);
+    assert_eq!(http.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn preferred_bank_missing_from_the_cache_refreshes_the_providers() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(mock_response(
+        "Dedicated account providers retrieved",
+        json!([
+            { "provider_slug": "access-bank", "bank_id": 1, "bank_name": "Access Bank", "id": 6 }
+        ]),
+    ))
+    .respond_with(bank_providers_payload())
+    .respond_with(dedicated_account_payload(json!({})));
+    let dedicated_virtual_account =
+        DedicatedVirtualAccountEndpoints::new(key, Arc::new(http.clone()));
+    let body = DedicatedVirtualAccountRequestBuilder::default()
+        .customer("CUS_ka4xutrdslg7cyf".to_string())
+        .preferred_bank("wema-bank".to_string())
+        .build()
+        .expect("unable to build dedicated virtual account request");
+
+    // Act
+    dedicated_virtual_account
+        .refresh_providers()
+        .await
+        .expect("unable to refresh bank providers");
+    let res = dedicated_virtual_account
+        .create_dedicated_virtual_account(body)
+        .await
+        .expect("unable to create dedicated virtual account");
+
+    // Assert
+    let requests = http.requests();
+    assert_eq!(requests.len(), 3);
+    assert!(requests[1]
+        .url
+        .ends_with("/dedicated_account/available_providers"));
+    assert_eq!(requests[2].method, "POST");
+    assert!(res.status);
+}
@@ -1,7 +1,13 @@
+use async_trait::async_trait;
 use dotenv::dotenv;
+use paystack::http::base::Query;
 use paystack::http::reqwest::ReqwestClient;
-use paystack::PaystackClient;
+use paystack::{HttpClient, PaystackClient};
+use serde_json::{json, Value};
+use std::collections::{HashMap, VecDeque};
 use std::env;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 /// A function to get the bank information for the Paystack API
 pub fn get_bank_account_number_and_code() -> (String, String, String) {
@@ -21,16 +27,285 @@ pub fn get_base_url() -> String {
     env::var("BASE_URL").unwrap_or(String::from("https://api.paystack.co"))
 }
 
-/// A function to get the API key
-pub fn get_api_key() -> String {
+/// A function to get an instance of the paystack client for testing
+pub fn get_paystack_client() -> PaystackClient<ReqwestClient> {
     dotenv().ok();
 
-    env::var("PAYSTACK_API_KEY").expect("Unable to read the paystack API key from .env file.")
+    PaystackClient::from_env().expect("Unable to create the paystack client from the .env file.")
 }
 
-/// A function to get an instance of the paystack client for testing
-pub fn get_paystack_client() -> PaystackClient<ReqwestClient> {
-    let api_key = get_api_key();
+/// A request captured by the `MockHttpClient`
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: &'static str,
+    pub url: String,
+    pub api_key: String,
+    pub query: Vec<(String, String)>,
+    pub body: Value,
+}
+
+/// Responses queued in the `MockHttpClient`, either a body or an error message
+type MockResponses = VecDeque<Result<String, String>>;
+
+/// An HTTP client that replays queued responses in order and records every request it receives.
+/// It allows endpoint logic to be tested without reaching the Paystack API.
+#[derive(Debug, Clone, Default)]
+pub struct MockHttpClient {
+    responses: Arc<Mutex<MockResponses>>,
+    url_responses: Arc<Mutex<HashMap<String, MockResponses>>>,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+    delay: Arc<Mutex<Duration>>,
+}
+
+impl MockHttpClient {
+    /// Queue a successful response body
+    pub fn respond_with(&self, body: Value) -> &Self {
+        self.responses
+            .lock()
+            .unwrap()
+            .push_back(Ok(body.to_string()));
+        self
+    }
+
+    /// Queue a failed request with the given error message
+    pub fn fail_with(&self, error: &str) -> &Self {
+        self.responses
+            .lock()
+            .unwrap()
+            .push_back(Err(error.to_string()));
+        self
+    }
+
+    /// Queue a successful response body for requests to the given URL.
+    /// These take priority over responses queued with `respond_with`, so the order requests are made in does not matter.
+    pub fn respond_to_url(&self, url: &str, body: Value) -> &Self {
+        self.url_responses
+            .lock()
+            .unwrap()
+            .entry(url.to_string())
+            .or_default()
+            .push_back(Ok(body.to_string()));
+        self
+    }
+
+    /// Queue a response body that is not JSON, e.g. a CSV file, for requests to the given URL
+    pub fn respond_to_url_with_text(&self, url: &str, body: &str) -> &Self {
+        self.url_responses
+            .lock()
+            .unwrap()
+            .entry(url.to_string())
+            .or_default()
+            .push_back(Ok(body.to_string()));
+        self
+    }
+
+    /// Queue a failed request to the given URL with the given error message
+    pub fn fail_on_url(&self, url: &str, error: &str) -> &Self {
+        self.url_responses
+            .lock()
+            .unwrap()
+            .entry(url.to_string())
+            .or_default()
+            .push_back(Err(error.to_string()));
+        self
+    }
+
+    /// Wait the given time before answering each request, to simulate a slow API
+    pub fn delay_responses(&self, delay: Duration) -> &Self {
+        *self.delay.lock().unwrap() = delay;
+        self
+    }
+
+    /// All the requests received so far
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    async fn wait(&self) {
+        let delay = *self.delay.lock().unwrap();
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    fn record(
+        &self,
+        method: &'static str,
+        url: &str,
+        api_key: &str,
+        query: Option<&Query>,
+        body: &Value,
+    ) -> Result<String, String> {
+        let query = query
+            .map(|q| {
+                q.iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        self.requests.lock().unwrap().push(RecordedRequest {
+            method,
+            url: url.to_string(),
+            api_key: api_key.to_string(),
+            query,
+            body: body.clone(),
+        });
+        let url_response = self
+            .url_responses
+            .lock()
+            .unwrap()
+            .get_mut(url)
+            .and_then(VecDeque::pop_front);
+        url_response
+            .or_else(|| self.responses.lock().unwrap().pop_front())
+            .unwrap_or_else(|| Err(format!("no mock response queued for {method} {url}")))
+    }
+}
+
+#[async_trait]
+impl HttpClient for MockHttpClient {
+    type Error = String;
+
+    async fn get(
+        &self,
+        url: &str,
+        api_key: &str,
+        query: Option<&Query>,
+    ) -> Result<String, Self::Error> {
+        self.wait().await;
+        self.record("GET", url, api_key, query, &Value::Null)
+    }
+
+    async fn post(&self, url: &str, api_key: &str, body: &Value) -> Result<String, Self::Error> {
+        self.wait().await;
+        self.record("POST", url, api_key, None, body)
+    }
+
+    async fn put(&self, url: &str, api_key: &str, body: &Value) -> Result<String, Self::Error> {
+        self.wait().await;
+        self.record("PUT", url, api_key, None, body)
+    }
+
+    async fn delete(&self, url: &str, api_key: &str, body: &Value) -> Result<String, Self::Error> {
+        self.wait().await;
+        self.record("DELETE", url, api_key, None, body)
+    }
+
+    /// Recorded with the `DOWNLOAD` method, so tests can tell downloads from API requests
+    async fn download(&self, url: &str) -> Result<String, Self::Error> {
+        self.wait().await;
+        self.record("DOWNLOAD", url, "", None, &Value::Null)
+    }
+}
+
+/// A function to get the mock client and the shared API key used to create endpoints in tests
+pub fn get_mock_http_client() -> (Arc<String>, MockHttpClient) {
+    (
+        Arc::new(String::from("sk_test_mock")),
+        MockHttpClient::default(),
+    )
+}
+
+/// A function to get a transaction object as returned by the Paystack API
+pub fn mock_transaction(id: u64, reference: &str) -> Value {
+    json!({
+        "id": id,
+        "domain": "test",
+        "status": "success",
+        "reference": reference,
+        "amount": 20000,
+        "message": null,
+        "gateway_response": "Successful",
+        "paid_at": "2024-08-22T09:15:02.000Z",
+        "created_at": "2024-08-22T09:14:24.000Z",
+        "channel": "card",
+        "currency": "NGN",
+        "ip_address": "127.0.0.1",
+        "metadata": "",
+        "fees": 300,
+        "customer": {
+            "id": 181873746,
+            "first_name": null,
+            "last_name": null,
+            "email": "demo@test.com",
+            "customer_code": "CUS_1rkzaqsv4rrhqo6",
+            "phone": null,
+            "metadata": null,
+            "risk_action": "default",
+            "international_format_phone": null
+        },
+        "authorization": {
+            "authorization_code": "AUTH_uh8bcl3zbn",
+            "bin": "408408",
+            "last4": "4081",
+            "exp_month": "12",
+            "exp_year": "2030",
+            "channel": "card",
+            "card_type": "visa ",
+            "bank": "TEST BANK",
+            "country_code": "NG",
+            "brand": "visa",
+            "reusable": true,
+            "signature": "SIG_yEXu7dLBeqG0kU7g95Ke",
+            "account_name": null
+        },
+        "plan": null
+    })
+}
+
+/// A function to wrap data in the Paystack API response envelope
+pub fn mock_response(message: &str, data: Value) -> Value {
+    json!({
+        "status": true,
+        "message": message,
+        "data": data
+    })
+}
+
+/// A function to wrap one page of a list in the Paystack API response envelope, with its meta
+pub fn mock_page(message: &str, data: Value, page: u32, page_count: u32) -> Value {
+    json!({
+        "status": true,
+        "message": message,
+        "data": data,
+        "meta": { "perPage": 100, "page": page, "pageCount": page_count }
+    })
+}
 
-    PaystackClient::<ReqwestClient>::new(api_key)
+/// A function to get a subscription object as returned by the list subscriptions endpoint
+pub fn mock_subscription(id: u64, code: &str, status: &str) -> Value {
+    json!({
+        "customer": { "id": 1173, "email": "customer@email.com", "customer_code": "CUS_xnxdt6s1zg1f4nx" },
+        "plan": { "id": 28, "name": "Monthly retainer", "plan_code": "PLN_gx2wn530m0i3w3m" },
+        "integration": 100032,
+        "domain": "test",
+        "start": 1459296064,
+        "status": status,
+        "quantity": 1,
+        "amount": 50000,
+        "subscription_code": code,
+        "email_token": format!("token_{id}"),
+        "authorization": {
+            "authorization_code": "AUTH_8dfhjjdt",
+            "bin": "408408",
+            "last4": "4081",
+            "exp_month": "12",
+            "exp_year": "2030",
+            "channel": "card",
+            "card_type": "visa",
+            "bank": "TEST BANK",
+            "country_code": "NG",
+            "brand": "visa",
+            "reusable": true,
+            "signature": "SIG_yEXu7dLBeqG0kU7g95Ke",
+            "account_name": null
+        },
+        "easy_cron_id": null,
+        "cron_expression": "0 0 28 * *",
+        "next_payment_date": "2016-05-19T07:00:00.000Z",
+        "open_invoice": null,
+        "id": id,
+        "createdAt": "2016-03-30T00:01:04.000Z",
+        "updatedAt": "2016-03-30T00:22:58.000Z"
+    })
 }
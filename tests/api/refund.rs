@@ -1,5 +1,10 @@
-use crate::helpers::get_paystack_client;
-use paystack::CreateRefundRequestBuilder;
+use crate::helpers::{get_mock_http_client, get_paystack_client, mock_page, mock_response};
+use paystack::{
+    CreateRefundRequestBuilder, Currency, DryRunClient, PaystackAPIError, PaystackClient,
+    RefundEndpoints,
+};
+use serde_json::json;
+use std::sync::Arc;
 
 #[tokio::test]
 async fn list_refunds_succeeds() {
@@ -66,3 +71,305 @@ async fn fetch_refund_fails_for_nonexistent_id() {
         }
     }
 }
+
+#[tokio::test]
+async fn create_refund_sends_request_when_currencies_match() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(mock_response(
+        "Refund has been queued for processing",
+        json!({
+            "id": 3018284,
+            "integration": 412829,
+            "domain": "test",
+            "transaction": 1641,
+            "amount": 10000,
+            "deducted_amount": 0,
+            "currency": "USD",
+            "channel": null,
+            "fully_deducted": false,
+            "refunded_by": "customer@email.com",
+            "expected_at": "2021-12-16T09:21:17.016Z",
+            "customer_note": "Refund for transaction T685312322670591",
+            "merchant_note": "Refund for transaction T685312322670591 by customer@email.com",
+            "status": "pending",
+            "createdAt": "2021-12-07T09:21:17.122Z",
+            "updatedAt": "2021-12-07T09:21:17.122Z"
+        }),
+    ));
+    let refund = RefundEndpoints::new(key, Arc::new(http.clone()));
+
+    let body = CreateRefundRequestBuilder::default()
+        .transaction("T685312322670591".to_string())
+        .amount(10000)
+        .currency("USD".to_string())
+        .transaction_currency("usd".to_string())
+        .build()
+        .unwrap();
+
+    // Act
+    let res = refund
+        .create_refund(body)
+        .await
+        .expect("unable to create refund");
+
+    // Assert
+    let requests = http.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].body["currency"], "USD");
+    assert!(requests[0].body.get("transaction_currency").is_none());
+    assert_eq!(res.data.unwrap().currency, "USD");
+}
+
+#[tokio::test]
+async fn create_refund_rejects_mismatched_currency() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let refund = RefundEndpoints::new(key, Arc::new(http.clone()));
+
+    let body = CreateRefundRequestBuilder::default()
+        .transaction("T685312322670591".to_string())
+        .currency("NGN".to_string())
+        .transaction_currency("USD".to_string())
+        .build()
+        .unwrap();
+
+    // Act
+    let res = refund.create_refund(body).await;
+
+    // Assert
+    match res {
+        Err(PaystackAPIError::Validation(msg)) => {
+            assert!(
+                msg.contains("NGN") && msg.contains("USD"),
+                "unexpected error: {msg}"
+            )
+        }
+        other => panic!("expected a validation error, got {other:?}"),
+    }
+    assert!(http.requests().is_empty());
+}
+
+#[tokio::test]
+async fn dry_run_prepares_create_refund_request() {
+    // Arrange
+    let dry_run = DryRunClient::default();
+    let client = PaystackClient::with_http("sk_test_xxxxxxxxxxxx".to_string(), dry_run.clone());
+
+    let body = CreateRefundRequestBuilder::default()
+        .transaction("T685312322670591".to_string())
+        .amount(10000)
+        .currency("NGN".to_string())
+        .build()
+        .unwrap();
+
+    // Act
+    let res = client
+        .refund
+        .create_refund(body)
+        .await
+        .expect("dry run should not fail");
+
+    // Assert
+    let request = dry_run.last_request().expect("no request was prepared");
+    assert_eq!(request.method, "POST");
+    assert_eq!(request.url, "https://api.paystack.co/refund");
+    assert_eq!(
+        request.body,
+        Some(json!({
+            "transaction": "T685312322670591",
+            "amount": 10000,
+            "currency": "NGN",
+            "customer_note": null,
+            "merchant_note": null
+        }))
+    );
+    assert!(request.headers.contains(&(
+        "Authorization".to_string(),
+        "Bearer sk_test_****".to_string()
+    )));
+    assert!(res.status);
+    assert!(res.data.is_none());
+}
+
+#[tokio::test]
+async fn create_refund_uses_client_default_currency() {
+    // Arrange
+    let dry_run = DryRunClient::default();
+    let client = PaystackClient::with_http("sk_test_xxxxxxxxxxxx".to_string(), dry_run.clone())
+        .with_default_currency(Currency::GHS);
+
+    let without_currency = CreateRefundRequestBuilder::default()
+        .transaction("T685312322670591".to_string())
+        .build()
+        .unwrap();
+    let with_currency = CreateRefundRequestBuilder::default()
+        .transaction("T685312322670591".to_string())
+        .currency("USD".to_string())
+        .build()
+        .unwrap();
+
+    // Act
+    client
+        .refund
+        .create_refund(without_currency)
+        .await
+        .expect("dry run should not fail");
+    client
+        .refund
+        .create_refund(with_currency)
+        .await
+        .expect("dry run should not fail");
+
+    // Assert
+    let requests = dry_run.requests();
+    assert_eq!(requests[0].body.as_ref().unwrap()["currency"], "GHS");
+    assert_eq!(requests[1].body.as_ref().unwrap()["currency"], "USD");
+}
+
+#[tokio::test]
+async fn create_refund_prefers_transaction_currency_over_client_default() {
+    // Arrange
+    let dry_run = DryRunClient::default();
+    let client = PaystackClient::with_http("sk_test_xxxxxxxxxxxx".to_string(), dry_run.clone())
+        .with_default_currency(Currency::NGN);
+
+    let body = CreateRefundRequestBuilder::default()
+        .transaction("T685312322670591".to_string())
+        .transaction_currency("USD".to_string())
+        .build()
+        .unwrap();
+
+    // Act
+    client
+        .refund
+        .create_refund(body)
+        .await
+        .expect("a refund in the transaction currency should be sent");
+
+    // Assert
+    let requests = dry_run.requests();
+    assert_eq!(requests[0].body.as_ref().unwrap()["currency"], "USD");
+}
+
+/// A refund as returned by the list refunds endpoint
+fn mock_refund(id: u64, amount: u64, status: &str) -> serde_json::Value {
+    json!({
+        "id": id,
+        "integration": 412829,
+        "domain": "test",
+        "transaction": 1641,
+        "amount": amount,
+        "currency": "NGN",
+        "status": status,
+        "created_at": "2021-12-07T09:21:17.000Z"
+    })
+}
+
+#[tokio::test]
+async fn refund_exists_finds_matching_refund() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(mock_page(
+        "Refunds retrieved",
+        json!([
+            mock_refund(1, 10000, "failed"),
+            mock_refund(2, 5000, "processed"),
+            mock_refund(3, 10000, "pending")
+        ]),
+        1,
+        1,
+    ));
+    let refund = RefundEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = refund
+        .refund_exists("T685312322670591", 10000)
+        .await
+        .expect("unable to check refunds");
+
+    // Assert
+    let requests = http.requests();
+    assert!(requests[0]
+        .query
+        .contains(&("transaction".to_string(), "T685312322670591".to_string())));
+    assert_eq!(res.data, Some(true));
+}
+
+#[tokio::test]
+async fn refund_exists_ignores_failed_and_other_amounts() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(mock_page(
+        "Refunds retrieved",
+        json!([
+            mock_refund(1, 10000, "failed"),
+            mock_refund(2, 5000, "processed")
+        ]),
+        1,
+        1,
+    ));
+    let refund = RefundEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = refund
+        .refund_exists("T685312322670591", 10000)
+        .await
+        .expect("unable to check refunds");
+
+    // Assert
+    assert_eq!(http.requests().len(), 1);
+    assert_eq!(res.data, Some(false));
+}
+
+#[tokio::test]
+async fn refund_exists_fails_when_the_transaction_filter_is_ignored() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let mut other_transaction = mock_refund(2, 10000, "processed");
+    other_transaction["transaction"] = json!(9999);
+    http.respond_with(mock_response(
+        "Refunds retrieved",
+        json!([mock_refund(1, 5000, "processed"), other_transaction]),
+    ));
+    let refund = RefundEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = refund.refund_exists("1641", 10000).await;
+
+    // Assert
+    match res {
+        Err(PaystackAPIError::FilterNotHonoured(msg)) => assert!(msg.contains("9999")),
+        other => panic!("expected FilterNotHonoured, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn count_refunds_returns_total_without_parsing_records() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(json!({
+        "status": true,
+        "message": "Refunds retrieved",
+        // Not valid refunds, a count must not deserialize the records
+        "data": [{ "unexpected": "record" }],
+        "meta": { "total": 1234, "skipped": 0, "perPage": 1, "page": 1, "pageCount": 1234 }
+    }));
+    let refund = RefundEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = refund
+        .count_refunds(Some("T685312322670591"), None)
+        .await
+        .expect("unable to count refunds");
+
+    // Assert
+    let requests = http.requests();
+    assert!(requests[0]
+        .query
+        .contains(&("perPage".to_string(), "1".to_string())));
+    assert!(requests[0]
+        .query
+        .contains(&("transaction".to_string(), "T685312322670591".to_string())));
+    assert_eq!(res.data, Some(1234));
+}
@@ -2,9 +2,16 @@ use fake::{
     faker::{company::zh_tw::CompanyName, lorem::en::Sentence},
     Fake,
 };
-use paystack::CreateSubaccountRequestBuilder;
+use paystack::{
+    CreateSubaccountRequestBuilder, ListSubaccountsRequestBuilder, PaystackAPIError,
+    SettlementSchedule, SubaccountEndpoints,
+};
+use serde_json::json;
+use std::sync::Arc;
 
-use crate::helpers::{get_bank_account_number_and_code, get_paystack_client};
+use crate::helpers::{
+    get_bank_account_number_and_code, get_mock_http_client, get_paystack_client, mock_response,
+};
 
 #[tokio::test]
 async fn create_a_subaccount() {
@@ -133,3 +140,202 @@ async fn update_subaccount() {
     assert_ne!(updated_data.business_name, sub_account.business_name);
     assert_eq!(updated_data.business_name, "New business name");
 }
+
+fn mock_subaccount(id: u32, subaccount_code: &str) -> serde_json::Value {
+    json!({
+        "integration": 100973,
+        "domain": "test",
+        "subaccount_code": subaccount_code,
+        "business_name": "Sunshine Studios",
+        "description": "Sunshine Studios",
+        "primary_contact_name": null,
+        "primary_contact_email": null,
+        "primary_contact_phone": null,
+        "metadata": null,
+        "percentage_charge": 18.2,
+        "is_verified": false,
+        "settlement_bank": "Access Bank",
+        "account_number": "0193274682",
+        "settlement_schedule": "AUTO",
+        "active": true,
+        "migrate": false,
+        "id": id,
+        "createdAt": "2016-10-05T12:22:04.000Z",
+        "updatedAt": "2016-10-21T02:19:47.000Z"
+    })
+}
+
+#[tokio::test]
+async fn create_subaccount_returns_the_subaccount_code() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(mock_response(
+        "Subaccount created",
+        mock_subaccount(55, "ACCT_4hl4xenwpjy5wb"),
+    ));
+    let subaccount = SubaccountEndpoints::new(key, Arc::new(http.clone()));
+    let body = CreateSubaccountRequestBuilder::default()
+        .business_name("Sunshine Studios".to_string())
+        .settlement_bank("044".to_string())
+        .account_number("0193274682".to_string())
+        .percentage_charge(18.2)
+        .primary_contact_email("sunshine@studios.com".to_string())
+        .build()
+        .unwrap();
+
+    // Act
+    let res = subaccount
+        .create_subaccount(body)
+        .await
+        .expect("unable to create subaccount");
+
+    // Assert
+    let request = &http.requests()[0];
+    assert_eq!(request.method, "POST");
+    assert_eq!(request.body["settlement_bank"], "044");
+    assert_eq!(
+        request.body["primary_contact_email"],
+        "sunshine@studios.com"
+    );
+    assert_eq!(res.data.unwrap().subaccount_code, "ACCT_4hl4xenwpjy5wb");
+}
+
+#[tokio::test]
+async fn create_subaccount_without_settlement_details_is_rejected() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let subaccount = SubaccountEndpoints::new(key, Arc::new(http.clone()));
+    let body = CreateSubaccountRequestBuilder::default()
+        .business_name("Sunshine Studios".to_string())
+        .percentage_charge(18.2)
+        .build()
+        .unwrap();
+
+    // Act
+    let res = subaccount.create_subaccount(body).await;
+
+    // Assert
+    assert!(
+        matches!(res, Err(PaystackAPIError::Validation(msg)) if msg.contains("settlement_bank"))
+    );
+    assert!(http.requests().is_empty());
+}
+
+#[tokio::test]
+async fn list_subaccounts_with_filter_sends_date_range_and_reads_meta() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let mut response = mock_response(
+        "Subaccounts retrieved",
+        json!([
+            mock_subaccount(55, "ACCT_4hl4xenwpjy5wb"),
+            mock_subaccount(56, "ACCT_eg4sob4590pq9vb")
+        ]),
+    );
+    response["meta"] =
+        json!({"total": 12, "skipped": 10, "perPage": 10, "page": 2, "pageCount": 2});
+    http.respond_with(response);
+    let subaccount = SubaccountEndpoints::new(key, Arc::new(http.clone()));
+    let request = ListSubaccountsRequestBuilder::default()
+        .per_page(10)
+        .page(2)
+        .from("2024-08-01T00:00:00.000Z".to_string())
+        .to("2024-08-31T23:59:59.000Z".to_string())
+        .build()
+        .unwrap();
+
+    // Act
+    let res = subaccount
+        .list_subaccounts_with_filter(request)
+        .await
+        .expect("unable to list subaccounts");
+
+    // Assert
+    let query = &http.requests()[0].query;
+    let expected: Vec<(String, String)> = [
+        ("perPage", "10"),
+        ("page", "2"),
+        ("from", "2024-08-01T00:00:00.000Z"),
+        ("to", "2024-08-31T23:59:59.000Z"),
+    ]
+    .iter()
+    .map(|(k, v)| (k.to_string(), v.to_string()))
+    .collect();
+    assert_eq!(query, &expected);
+    assert_eq!(res.data.unwrap().len(), 2);
+    let meta = res.meta.unwrap();
+    assert_eq!(meta.total, Some(12));
+    assert_eq!(meta.page_count, Some(2));
+}
+
+#[tokio::test]
+async fn fetch_subaccount_reads_settlement_details_and_object_metadata() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let mut response_subaccount = mock_subaccount(55, "ACCT_4hl4xenwpjy5wb");
+    response_subaccount["metadata"] = json!({"vendor_id": 42});
+    response_subaccount["settlement_schedule"] = json!("WEEKLY");
+    response_subaccount["active"] = json!(0);
+    http.respond_with(mock_response("Subaccount retrieved", response_subaccount));
+    let subaccount = SubaccountEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = subaccount
+        .fetch_subaccount("ACCT_4hl4xenwpjy5wb".to_string())
+        .await
+        .expect("unable to fetch subaccount");
+
+    // Assert
+    assert!(http.requests()[0]
+        .url
+        .ends_with("/subaccount/ACCT_4hl4xenwpjy5wb"));
+    let data = res.data.unwrap();
+    assert_eq!(data.id, 55);
+    assert_eq!(data.settlement_bank, "Access Bank");
+    assert_eq!(data.account_number, "0193274682");
+    assert_eq!(data.settlement_schedule, Some(SettlementSchedule::Weekly));
+    assert_eq!(data.active, Some(false));
+    assert_eq!(data.metadata.as_deref(), Some(r#"{"vendor_id":42}"#));
+}
+
+#[tokio::test]
+async fn update_subaccount_sends_only_the_changed_fields() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let mut updated_subaccount = mock_subaccount(55, "ACCT_4hl4xenwpjy5wb");
+    updated_subaccount["account_number"] = json!("0123456789");
+    updated_subaccount["active"] = json!(false);
+    http.respond_with(mock_response("Subaccount updated", updated_subaccount));
+    let subaccount = SubaccountEndpoints::new(key, Arc::new(http.clone()));
+    let update_request = CreateSubaccountRequestBuilder::default()
+        .settlement_bank("058".to_string())
+        .account_number("0123456789".to_string())
+        .percentage_charge(12.5)
+        .metadata(r#"{"vendor_id":42}"#.to_string())
+        .active(false)
+        .build()
+        .unwrap();
+
+    // Act
+    let res = subaccount
+        .update_subaccount("ACCT_4hl4xenwpjy5wb".to_string(), update_request)
+        .await
+        .expect("unable to update subaccount");
+
+    // Assert
+    let request = &http.requests()[0];
+    assert_eq!(request.method, "PUT");
+    assert_eq!(
+        request.body,
+        json!({
+            "settlement_bank": "058",
+            "account_number": "0123456789",
+            "percentage_charge": 12.5,
+            "metadata": "{\"vendor_id\":42}",
+            "active": false
+        })
+    );
+    let data = res.data.unwrap();
+    assert_eq!(data.account_number, "0123456789");
+    assert_eq!(data.active, Some(false));
+}
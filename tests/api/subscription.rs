@@ -1,7 +1,14 @@
-use crate::helpers::get_paystack_client;
+use crate::helpers::{
+    get_mock_http_client, get_paystack_client, mock_page, mock_response, mock_subscription,
+};
 use fake::faker::internet::en::SafeEmail;
 use fake::Fake;
-use paystack::{CreateCustomerRequestBuilder, CreateSubscriptionRequestBuilder};
+use paystack::{
+    CreateCustomerRequestBuilder, CreateSubscriptionRequestBuilder, SubscriptionEndpoints,
+    SubscriptionStatus,
+};
+use serde_json::json;
+use std::sync::Arc;
 
 #[tokio::test]
 async fn create_subscription_valid() {
@@ -39,3 +46,345 @@ async fn create_subscription_valid() {
     assert!(res.status);
     assert_eq!("Subscription successfully created", res.message);
 }
+
+#[tokio::test]
+async fn can_disable_all_chargeable_subscriptions_for_customer() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(mock_page(
+        "Subscriptions retrieved",
+        json!([
+            mock_subscription(1, "SUB_active_one", "active"),
+            mock_subscription(2, "SUB_cancelled", "cancelled"),
+            mock_subscription(3, "SUB_active_two", "active"),
+            mock_subscription(4, "SUB_attention", "attention"),
+        ]),
+        1,
+        1,
+    ))
+    .respond_with(json!({ "status": true, "message": "Subscription disabled successfully" }))
+    .respond_with(json!({ "status": true, "message": "Subscription disabled successfully" }))
+    .respond_with(json!({ "status": true, "message": "Subscription disabled successfully" }));
+    let subscription = SubscriptionEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = subscription
+        .disable_all_for_customer(1173)
+        .await
+        .expect("unable to disable subscriptions");
+
+    // Assert
+    let requests = http.requests();
+    assert_eq!(requests.len(), 4);
+    assert_eq!(requests[0].method, "GET");
+    assert!(requests[0].url.contains("customer=1173"));
+    assert_eq!(
+        requests[1].url,
+        "https://api.paystack.co/subscription/disable"
+    );
+    assert_eq!(requests[1].body["code"], "SUB_active_one");
+    assert_eq!(requests[1].body["token"], "token_1");
+    assert_eq!(requests[2].body["code"], "SUB_active_two");
+    assert_eq!(requests[2].body["token"], "token_3");
+    assert_eq!(requests[3].body["code"], "SUB_attention");
+    assert!(res.status);
+    assert_eq!(res.message, "Disabled 3 subscriptions");
+    assert_eq!(
+        res.data.unwrap(),
+        vec![
+            "SUB_active_one".to_string(),
+            "SUB_active_two".to_string(),
+            "SUB_attention".to_string()
+        ]
+    );
+}
+
+#[tokio::test]
+async fn disable_all_for_customer_continues_past_failures() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(mock_page(
+        "Subscriptions retrieved",
+        json!([
+            mock_subscription(1, "SUB_active_one", "active"),
+            mock_subscription(2, "SUB_active_two", "active"),
+        ]),
+        1,
+        1,
+    ))
+    .fail_with("connection reset")
+    .respond_with(json!({ "status": true, "message": "Subscription disabled successfully" }));
+    let subscription = SubscriptionEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = subscription
+        .disable_all_for_customer(1173)
+        .await
+        .expect("unable to disable subscriptions");
+
+    // Assert
+    assert_eq!(http.requests().len(), 3);
+    assert!(!res.status);
+    assert!(res.message.contains("SUB_active_one"));
+    assert_eq!(res.data.unwrap(), vec!["SUB_active_two".to_string()]);
+}
+
+#[tokio::test]
+async fn can_disable_subscription_with_only_its_code() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(mock_response(
+        "Subscription retrieved successfully",
+        mock_subscription(7, "SUB_vsyqdmlzble3uii", "active"),
+    ))
+    .respond_with(json!({ "status": true, "message": "Subscription disabled successfully" }));
+    let subscription = SubscriptionEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = subscription
+        .disable("SUB_vsyqdmlzble3uii".to_string())
+        .await
+        .expect("unable to disable subscription");
+
+    // Assert
+    let requests = http.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(
+        requests[0].url,
+        "https://api.paystack.co/subscription/SUB_vsyqdmlzble3uii"
+    );
+    assert_eq!(requests[1].method, "POST");
+    assert_eq!(
+        requests[1].url,
+        "https://api.paystack.co/subscription/disable"
+    );
+    assert_eq!(requests[1].body["code"], "SUB_vsyqdmlzble3uii");
+    assert_eq!(requests[1].body["token"], "token_7");
+    assert!(res.status);
+}
+
+#[tokio::test]
+async fn can_enable_subscription_with_only_its_code() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(mock_response(
+        "Subscription retrieved successfully",
+        mock_subscription(7, "SUB_vsyqdmlzble3uii", "non-renewing"),
+    ))
+    .respond_with(json!({ "status": true, "message": "Subscription enabled successfully" }));
+    let subscription = SubscriptionEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = subscription
+        .enable("SUB_vsyqdmlzble3uii".to_string())
+        .await
+        .expect("unable to enable subscription");
+
+    // Assert
+    let requests = http.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(
+        requests[1].url,
+        "https://api.paystack.co/subscription/enable"
+    );
+    assert_eq!(requests[1].body["token"], "token_7");
+    assert!(res.status);
+}
+
+#[tokio::test]
+async fn disable_does_not_send_action_when_fetch_fails() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.fail_with("subscription not found");
+    let subscription = SubscriptionEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = subscription.disable("SUB_missing".to_string()).await;
+
+    // Assert
+    assert!(res.is_err());
+    assert_eq!(http.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn can_pause_and_resume_subscription() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let code = "SUB_vsyqdmlzble3uii";
+    http.respond_with(mock_response(
+        "Subscription retrieved successfully",
+        mock_subscription(7, code, "active"),
+    ))
+    .respond_with(json!({ "status": true, "message": "Subscription disabled successfully" }))
+    .respond_with(mock_response(
+        "Subscription retrieved successfully",
+        mock_subscription(7, code, "non-renewing"),
+    ))
+    .respond_with(mock_response(
+        "Subscription retrieved successfully",
+        mock_subscription(7, code, "non-renewing"),
+    ))
+    .respond_with(json!({ "status": true, "message": "Subscription enabled successfully" }))
+    .respond_with(mock_response(
+        "Subscription retrieved successfully",
+        mock_subscription(7, code, "active"),
+    ));
+    let subscription = SubscriptionEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let paused = subscription
+        .pause_subscription(code.to_string())
+        .await
+        .expect("unable to pause subscription");
+    let paused_status = subscription
+        .fetch_subscription(code.to_string())
+        .await
+        .expect("unable to fetch subscription")
+        .data
+        .unwrap()
+        .status;
+    let resumed = subscription
+        .resume_subscription(code.to_string())
+        .await
+        .expect("unable to resume subscription");
+    let resumed_status = subscription
+        .fetch_subscription(code.to_string())
+        .await
+        .expect("unable to fetch subscription")
+        .data
+        .unwrap()
+        .status;
+
+    // Assert
+    let requests = http.requests();
+    assert_eq!(
+        requests[1].url,
+        "https://api.paystack.co/subscription/disable"
+    );
+    assert_eq!(requests[1].body["token"], "token_7");
+    assert_eq!(
+        requests[4].url,
+        "https://api.paystack.co/subscription/enable"
+    );
+    assert_eq!(requests[4].body["token"], "token_7");
+    assert!(paused.status);
+    assert!(resumed.status);
+    assert_eq!(paused_status, SubscriptionStatus::NonRenewing);
+    assert_eq!(resumed_status, SubscriptionStatus::Active);
+}
+
+#[tokio::test]
+async fn send_management_email_succeeds() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(json!({ "status": true, "message": "Email successfully sent" }));
+    let subscription = SubscriptionEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = subscription
+        .send_management_email("SUB_vsyqdmlzble3uii".to_string())
+        .await
+        .expect("unable to send the management email");
+
+    // Assert
+    let requests = http.requests();
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(
+        requests[0].url,
+        "https://api.paystack.co/subscription/SUB_vsyqdmlzble3uii/manage/email"
+    );
+    assert!(res.status);
+}
+
+#[tokio::test]
+async fn manage_generates_and_emails_link() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(mock_response(
+        "Link generated",
+        json!({ "link": "https://paystack.com/manage/subscriptions/qlgwhpyq1ts2nsw?subscription_token=uqmnm4tw8yb0gqi" }),
+    ))
+    .respond_with(json!({ "status": true, "message": "Email successfully sent" }));
+    let subscription = SubscriptionEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = subscription
+        .manage("SUB_vsyqdmlzble3uii".to_string())
+        .await
+        .expect("unable to manage subscription");
+
+    // Assert
+    let requests = http.requests();
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(
+        requests[0].url,
+        "https://api.paystack.co/subscription/SUB_vsyqdmlzble3uii/manage/link"
+    );
+    let links = res.data.unwrap();
+    assert!(links
+        .link
+        .starts_with("https://paystack.com/manage/subscriptions/"));
+    assert!(links.emailed);
+}
+
+#[tokio::test]
+async fn manage_returns_link_when_email_fails() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(mock_response(
+        "Link generated",
+        json!({ "link": "https://paystack.com/manage/subscriptions/qlgwhpyq1ts2nsw" }),
+    ))
+    .fail_with("status code: 500 Internal Server Error");
+    let subscription = SubscriptionEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = subscription
+        .manage("SUB_vsyqdmlzble3uii".to_string())
+        .await
+        .expect("unable to manage subscription");
+
+    // Assert
+    let links = res.data.unwrap();
+    assert_eq!(
+        links.link,
+        "https://paystack.com/manage/subscriptions/qlgwhpyq1ts2nsw"
+    );
+    assert!(!links.emailed);
+}
+
+#[tokio::test]
+async fn cancel_subscription_disables_and_reports_new_status() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let code = "SUB_vsyqdmlzble3uii";
+    http.respond_with(mock_response(
+        "Subscription retrieved successfully",
+        mock_subscription(7, code, "active"),
+    ))
+    .respond_with(json!({ "status": true, "message": "Subscription disabled successfully" }))
+    .respond_with(mock_response(
+        "Subscription retrieved successfully",
+        mock_subscription(7, code, "non-renewing"),
+    ));
+    let subscription = SubscriptionEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = subscription
+        .cancel_subscription(code.to_string())
+        .await
+        .expect("unable to cancel subscription");
+
+    // Assert
+    let requests = http.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(
+        requests[1].url,
+        "https://api.paystack.co/subscription/disable"
+    );
+    assert_eq!(requests[1].body["token"], "token_7");
+    assert_eq!(res.message, "Subscription disabled successfully");
+    assert_eq!(res.data, Some(SubscriptionStatus::NonRenewing));
+}
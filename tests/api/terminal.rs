@@ -1,3 +1,368 @@
+use crate::helpers::{get_mock_http_client, mock_response};
+use paystack::{
+    EventRequest, EventRequestBuilder, EventRequestData, EventRequestDataBuilder, EventType,
+    ListTerminalsRequestBuilder, PaystackAPIError, TerminalAction, TerminalEndpoints,
+};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
 // TODO: to conduct the test, you need access to a paystack terminal which I do not have
 #[tokio::test]
 async fn terminal_send_event_succeed() {}
+
+#[tokio::test]
+async fn send_event_pushes_invoice_to_terminal() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(mock_response(
+        "Event sent to Terminal",
+        json!({ "id": "616d721e8ac4fb4e07a2e3a4" }),
+    ));
+    let terminal = TerminalEndpoints::new(key, Arc::new(http.clone()));
+
+    let event_request = EventRequestBuilder::default()
+        .event_type(EventType::Invoice)
+        .action(TerminalAction::Process)
+        .data(
+            EventRequestDataBuilder::default()
+                .id("7895939".to_string())
+                .reference("4634337895939".to_string())
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .expect("an invoice can be processed");
+
+    // Act
+    let res = terminal
+        .send_event("30".to_string(), event_request)
+        .await
+        .expect("unable to send event");
+
+    // Assert
+    let requests = http.requests();
+    assert!(requests[0].url.ends_with("/terminal/30/event"));
+    assert_eq!(
+        requests[0].body,
+        json!({
+            "type": "invoice",
+            "action": "process",
+            "data": { "id": "7895939", "reference": "4634337895939" }
+        })
+    );
+    assert_eq!(res.data.unwrap().id, "616d721e8ac4fb4e07a2e3a4");
+}
+
+#[tokio::test]
+async fn send_event_pushes_transaction_without_reference() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(mock_response(
+        "Event sent to Terminal",
+        json!({ "id": "616d721e8ac4fb4e07a2e3a5" }),
+    ));
+    let terminal = TerminalEndpoints::new(key, Arc::new(http.clone()));
+
+    let event_request = EventRequestBuilder::default()
+        .event_type(EventType::Transaction)
+        .action(TerminalAction::Print)
+        .data(
+            EventRequestDataBuilder::default()
+                .id("4099260516".to_string())
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .expect("a transaction can be printed");
+
+    // Act
+    let res = terminal
+        .send_event("30".to_string(), event_request)
+        .await
+        .expect("unable to send event");
+
+    // Assert
+    assert_eq!(
+        http.requests()[0].body,
+        json!({
+            "type": "transaction",
+            "action": "print",
+            "data": { "id": "4099260516" }
+        })
+    );
+    assert_eq!(res.data.unwrap().id, "616d721e8ac4fb4e07a2e3a5");
+}
+
+#[tokio::test]
+async fn send_event_rejects_invalid_action_for_event_type() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let terminal = TerminalEndpoints::new(key, Arc::new(http.clone()));
+
+    // Built without the builder, which would reject it
+    let event_request = EventRequest {
+        event_type: EventType::Transaction,
+        action: TerminalAction::View,
+        data: EventRequestData {
+            id: "4099260516".to_string(),
+            reference: None,
+        },
+    };
+
+    // Act
+    let res = terminal.send_event("30".to_string(), event_request).await;
+
+    // Assert
+    match res {
+        Err(PaystackAPIError::Validation(msg)) => {
+            assert!(msg.contains("view"), "unexpected error: {msg}")
+        }
+        other => panic!("expected a validation error, got {other:?}"),
+    }
+    assert!(http.requests().is_empty());
+}
+
+const EVENT_URL: &str = "https://api.paystack.co/terminal/30/event/616d721e8ac4fb4e07a2e3a4";
+const PRESENCE_URL: &str = "https://api.paystack.co/terminal/30/presence";
+
+#[tokio::test]
+async fn fetch_event_status_reports_undelivered_event() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_to_url(
+        EVENT_URL,
+        mock_response("Message Status Retrieved", json!({ "delivered": false })),
+    );
+    let terminal = TerminalEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = terminal
+        .fetch_event_status("30".to_string(), "616d721e8ac4fb4e07a2e3a4".to_string())
+        .await
+        .expect("unable to fetch event status");
+
+    // Assert
+    let requests = http.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "GET");
+    assert!(!res.data.unwrap().delivered);
+}
+
+#[tokio::test]
+async fn fetch_terminal_status_reads_presence() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_to_url(
+        PRESENCE_URL,
+        mock_response(
+            "Terminal status retrieved",
+            json!({ "online": true, "available": false }),
+        ),
+    );
+    let terminal = TerminalEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = terminal
+        .fetch_terminal_status("30".to_string())
+        .await
+        .expect("unable to fetch terminal status");
+
+    // Assert
+    assert_eq!(http.requests()[0].method, "GET");
+    let presence = res.data.unwrap();
+    assert!(presence.online);
+    assert!(!presence.available);
+}
+
+#[tokio::test]
+async fn await_event_polls_until_delivered() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_to_url(
+        EVENT_URL,
+        mock_response("Message Status Retrieved", json!({ "delivered": false })),
+    );
+    http.respond_to_url(
+        EVENT_URL,
+        mock_response("Message Status Retrieved", json!({ "delivered": true })),
+    );
+    http.respond_to_url(
+        PRESENCE_URL,
+        mock_response(
+            "Terminal status retrieved",
+            json!({ "online": true, "available": false }),
+        ),
+    );
+    let terminal = TerminalEndpoints::new(key, Arc::new(http.clone()))
+        .with_event_poll_interval(Duration::from_millis(1));
+
+    // Act
+    let res = terminal
+        .await_event(
+            "30".to_string(),
+            "616d721e8ac4fb4e07a2e3a4".to_string(),
+            Duration::from_secs(5),
+        )
+        .await
+        .expect("the event should be delivered");
+
+    // Assert
+    assert!(res.data.unwrap().delivered);
+    let urls: Vec<_> = http.requests().into_iter().map(|r| r.url).collect();
+    assert_eq!(urls, vec![EVENT_URL, PRESENCE_URL, EVENT_URL]);
+}
+
+#[tokio::test]
+async fn await_event_reports_offline_terminal() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_to_url(
+        EVENT_URL,
+        mock_response("Message Status Retrieved", json!({ "delivered": false })),
+    );
+    http.respond_to_url(
+        PRESENCE_URL,
+        mock_response(
+            "Terminal status retrieved",
+            json!({ "online": false, "available": false }),
+        ),
+    );
+    let terminal = TerminalEndpoints::new(key, Arc::new(http.clone()))
+        .with_event_poll_interval(Duration::from_millis(1));
+
+    // Act
+    let res = terminal
+        .await_event(
+            "30".to_string(),
+            "616d721e8ac4fb4e07a2e3a4".to_string(),
+            Duration::from_secs(5),
+        )
+        .await;
+
+    // Assert
+    match res {
+        Err(PaystackAPIError::Terminal(msg)) => {
+            assert!(msg.contains("offline"), "unexpected error: {msg}")
+        }
+        other => panic!("expected a terminal error, got {other:?}"),
+    }
+    assert_eq!(http.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn await_event_times_out_during_a_slow_poll() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_to_url(
+        EVENT_URL,
+        mock_response("Message Status Retrieved", json!({ "delivered": true })),
+    );
+    http.delay_responses(Duration::from_secs(5));
+    let terminal = TerminalEndpoints::new(key, Arc::new(http.clone()))
+        .with_event_poll_interval(Duration::from_millis(1));
+
+    // Act
+    let started = Instant::now();
+    let res = terminal
+        .await_event(
+            "30".to_string(),
+            "616d721e8ac4fb4e07a2e3a4".to_string(),
+            Duration::from_millis(50),
+        )
+        .await;
+
+    // Assert
+    assert!(started.elapsed() < Duration::from_secs(5));
+    match res {
+        Err(PaystackAPIError::Timeout(msg)) => {
+            assert!(
+                msg.contains("616d721e8ac4fb4e07a2e3a4"),
+                "unexpected error: {msg}"
+            )
+        }
+        other => panic!("expected a timeout error, got {other:?}"),
+    }
+}
+
+fn mock_terminal(id: u64, terminal_id: &str) -> serde_json::Value {
+    json!({
+        "id": id,
+        "serial_number": "033301504601",
+        "device_make": "ACME",
+        "terminal_id": terminal_id,
+        "integration": 100973,
+        "domain": "live",
+        "name": "Front desk",
+        "address": "12 Allen Avenue, Ikeja",
+        "status": "active"
+    })
+}
+
+#[tokio::test]
+async fn list_terminals_with_cursor_returns_next_cursor() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let mut response = mock_response(
+        "Terminals retrieved successfully",
+        json!([mock_terminal(30, "2232WE17"), mock_terminal(31, "2232WE18")]),
+    );
+    response["meta"] = json!({ "next": "dGVybWluYWw6MzE=", "previous": null, "perPage": 2 });
+    http.respond_with(response);
+    let terminal = TerminalEndpoints::new(key, Arc::new(http.clone()));
+    let request = ListTerminalsRequestBuilder::default()
+        .per_page(2)
+        .next("dGVybWluYWw6Mjk=".to_string())
+        .build()
+        .unwrap();
+
+    // Act
+    let res = terminal
+        .list_terminals_with_cursor(request)
+        .await
+        .expect("unable to list terminals");
+
+    // Assert
+    assert_eq!(
+        http.requests()[0].query,
+        vec![
+            ("perPage".to_string(), "2".to_string()),
+            ("next".to_string(), "dGVybWluYWw6Mjk=".to_string())
+        ]
+    );
+    let terminals = res.data.unwrap();
+    assert_eq!(terminals.len(), 2);
+    assert_eq!(terminals[1].terminal_id, "2232WE18");
+    let meta = res.meta.unwrap();
+    assert_eq!(meta.next.as_deref(), Some("dGVybWluYWw6MzE="));
+    assert_eq!(meta.previous, None);
+}
+
+#[tokio::test]
+async fn fetch_terminal_reads_terminal_details() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let mut response_terminal = mock_terminal(30, "2232WE17");
+    response_terminal["device_make"] = json!(null);
+    http.respond_with(mock_response(
+        "Terminal retrieved successfully",
+        response_terminal,
+    ));
+    let terminal = TerminalEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = terminal
+        .fetch_terminal("30".to_string())
+        .await
+        .expect("unable to fetch terminal");
+
+    // Assert
+    assert!(http.requests()[0].url.ends_with("/terminal/30"));
+    let data = res.data.unwrap();
+    assert_eq!(data.serial_number, "033301504601");
+    assert_eq!(data.device_make, None);
+    assert_eq!(data.terminal_id, "2232WE17");
+    assert_eq!(data.integration, 100973);
+    assert_eq!(data.address.as_deref(), Some("12 Allen Avenue, Ikeja"));
+    assert_eq!(data.status, "active");
+}
@@ -1,11 +1,16 @@
-use crate::helpers::get_paystack_client;
+use crate::helpers::{
+    get_mock_http_client, get_paystack_client, mock_page, mock_response, mock_transaction,
+};
 use fake::faker::internet::en::SafeEmail;
 use fake::Fake;
 use paystack::{
-    Channel, Currency, PartialDebitTransactionRequestBuilder, Status, TransactionIdentifier,
-    TransactionRequestBuilder,
+    Channel, Currency, ExportTransactionsRequestBuilder, ListTransactionsRequestBuilder,
+    PartialDebitTransactionRequestBuilder, PaystackAPIError, Status, TimelineEventType,
+    TransactionEndpoints, TransactionIdentifier, TransactionRequestBuilder, TransactionStatus,
 };
 use rand::Rng;
+use serde_json::json;
+use std::sync::Arc;
 
 #[tokio::test]
 async fn initialize_transaction_valid() {
@@ -40,6 +45,107 @@ async fn initialize_transaction_valid() {
     assert_eq!("Authorization URL created", res.message);
 }
 
+#[tokio::test]
+async fn initialize_transaction_sends_channels_and_returns_authorization() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(mock_response(
+        "Authorization URL created",
+        json!({
+            "authorization_url": "https://checkout.paystack.com/0peioxfhpn",
+            "access_code": "0peioxfhpn",
+            "reference": "7PVGX8MEk85tgeEpVDtD"
+        }),
+    ));
+    let transaction = TransactionEndpoints::new(key, Arc::new(http.clone()));
+
+    let body = TransactionRequestBuilder::default()
+        .amount("20000".to_string())
+        .email("customer@email.com".to_string())
+        .currency(Currency::NGN)
+        .reference("7PVGX8MEk85tgeEpVDtD".to_string())
+        .callback_url("https://example.com/callback".to_string())
+        .channel(vec![Channel::Card, Channel::BankTransfer])
+        .metadata(r#"{"order_id": 42}"#.to_string())
+        .build()
+        .unwrap();
+
+    // Act
+    let res = transaction
+        .initialize_transaction(body)
+        .await
+        .expect("unable to initialize transaction");
+
+    // Assert
+    let requests = http.requests();
+    assert_eq!(
+        requests[0].url,
+        "https://api.paystack.co/transaction/initialize"
+    );
+    assert_eq!(
+        requests[0].body["channels"],
+        json!(["card", "bank_transfer"])
+    );
+    assert!(requests[0].body.get("channel").is_none());
+    assert_eq!(
+        requests[0].body["callback_url"],
+        "https://example.com/callback"
+    );
+    let data = res.data.unwrap();
+    assert_eq!(
+        data.authorization_url,
+        "https://checkout.paystack.com/0peioxfhpn"
+    );
+    assert_eq!(data.access_code, "0peioxfhpn");
+    assert_eq!(data.reference, "7PVGX8MEk85tgeEpVDtD");
+}
+
+#[tokio::test]
+async fn verify_transaction_returns_typed_transaction() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let mut transaction = mock_transaction(4099260516, "re4lyvq3s3");
+    transaction["log"] = json!({
+        "start_time": 1724318098,
+        "time_spent": 4,
+        "attempts": 1,
+        "errors": 0,
+        "success": true,
+        "mobile": false,
+        "input": [],
+        "history": [
+            { "type": "action", "message": "Attempted to pay with card", "time": 3 },
+            { "type": "success", "message": "Successfully paid with card", "time": 4 }
+        ]
+    });
+    http.respond_to_url(
+        "https://api.paystack.co/transaction/verify/re4lyvq3s3",
+        mock_response("Verification successful", transaction),
+    );
+    let transactions = TransactionEndpoints::new(key, Arc::new(http));
+
+    // Act
+    let res = transactions
+        .verify_transaction("re4lyvq3s3")
+        .await
+        .expect("unable to verify transaction");
+
+    // Assert
+    let data = res.data.unwrap();
+    assert_eq!(data.status, TransactionStatus::Success);
+    assert_eq!(data.amount, 20000);
+    assert_eq!(data.currency, "NGN");
+    assert_eq!(data.fees, Some(300));
+    assert_eq!(data.customer.email, "demo@test.com");
+    assert_eq!(
+        data.authorization.authorization_code.as_deref(),
+        Some("AUTH_uh8bcl3zbn")
+    );
+    let log = data.log.unwrap();
+    assert_eq!(log.attempts, Some(1));
+    assert_eq!(log.history.unwrap().len(), 2);
+}
+
 #[tokio::test]
 async fn initialize_transaction_fails_when_currency_is_not_supported_by_merchant() {
     // Arrange
@@ -109,7 +215,7 @@ async fn valid_transaction_is_verified() {
     // Assert
     assert!(response.status);
     assert_eq!(response.message, "Verification successful");
-    assert_eq!(response.data.unwrap().status, "abandoned");
+    assert_eq!(response.data.unwrap().status, TransactionStatus::Abandoned);
 }
 
 #[tokio::test]
@@ -163,7 +269,7 @@ async fn fetch_transaction_succeeds() {
     let data = response.data.unwrap();
     let fetched_transaction = client
         .transactions
-        .fetch_transactions(data[0].id)
+        .fetch_transaction(data[0].id)
         .await
         .expect("unable to fetch transaction");
 
@@ -265,6 +371,152 @@ async fn export_transaction_succeeds_with_default_parameters() {
     assert!(!data.path.is_empty());
 }
 
+#[tokio::test]
+async fn export_transactions_with_filter_sends_only_set_filters() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(mock_response(
+        "Export successful",
+        json!({ "path": "https://s3.eu-west-1.amazonaws.com/files.paystack.co/exports/transactions.csv" }),
+    ));
+    let transactions = TransactionEndpoints::new(key, Arc::new(http.clone()));
+
+    let request = ExportTransactionsRequestBuilder::default()
+        .from("2024-08-01T00:00:00.000Z".to_string())
+        .to("2024-08-31T23:59:59.000Z".to_string())
+        .customer(181873746)
+        .settled(true)
+        .settlement(4200)
+        .payment_page(7)
+        .build()
+        .unwrap();
+
+    // Act
+    let res = transactions
+        .export_transactions_with_filter(request)
+        .await
+        .expect("unable to export transactions");
+
+    // Assert
+    let request = &http.requests()[0];
+    assert_eq!(request.url, "https://api.paystack.co/transaction/export");
+    assert_eq!(
+        request.query,
+        [
+            ("from", "2024-08-01T00:00:00.000Z"),
+            ("to", "2024-08-31T23:59:59.000Z"),
+            ("customer", "181873746"),
+            ("settled", "true"),
+            ("settlement", "4200"),
+            ("payment_page", "7"),
+        ]
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+    );
+    assert!(res.data.unwrap().path.ends_with("transactions.csv"));
+}
+
+#[cfg(feature = "csv")]
+#[tokio::test]
+async fn export_and_parse_downloads_and_parses_the_export() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let path = "https://s3.eu-west-1.amazonaws.com/files.paystack.co/exports/transactions.csv";
+    http.respond_to_url(
+        "https://api.paystack.co/transaction/export",
+        mock_response("Export successful", json!({ "path": path })),
+    );
+    http.respond_to_url_with_text(
+        path,
+        "Reference,Amount,Status,Currency,Customer Email\n\
+         T592386937184729,500.00,success,NGN,demo@test.com\n\
+         re4lyvq3s3,200.00,success,NGN,customer@email.com\n",
+    );
+    let transactions = TransactionEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let rows = transactions
+        .export_and_parse(
+            ExportTransactionsRequestBuilder::default()
+                .status(Status::Success)
+                .currency(Currency::NGN)
+                .build()
+                .unwrap(),
+        )
+        .await
+        .expect("unable to export and parse transactions");
+
+    // Assert
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].reference, "T592386937184729");
+    assert_eq!(rows[1].amount, "200.00");
+    assert_eq!(
+        rows[1].customer_email.as_deref(),
+        Some("customer@email.com")
+    );
+
+    let requests = http.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].method, "DOWNLOAD");
+    assert_eq!(requests[1].url, path);
+    assert!(requests[1].api_key.is_empty());
+}
+
+#[cfg(feature = "csv")]
+#[tokio::test]
+async fn export_and_parse_downloads_through_a_wrapper_client() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let path = "https://s3.eu-west-1.amazonaws.com/files.paystack.co/exports/transactions.csv";
+    http.respond_to_url(
+        "https://api.paystack.co/transaction/export",
+        mock_response("Export successful", json!({ "path": path })),
+    );
+    http.respond_to_url_with_text(
+        path,
+        "Reference,Amount,Status,Currency\nT592386937184729,500.00,success,NGN\n",
+    );
+    let wrapper = paystack::CircuitBreakerClient::new(
+        http.clone(),
+        paystack::CircuitBreakerConfig::default(),
+    );
+    let transactions = TransactionEndpoints::new(key, Arc::new(wrapper));
+
+    // Act
+    let rows = transactions
+        .export_and_parse(paystack::ExportTransactionsRequest::default())
+        .await
+        .expect("unable to export and parse transactions");
+
+    // Assert
+    assert_eq!(rows.len(), 1);
+    let requests = http.requests();
+    assert_eq!(requests[0].api_key, "sk_test_mock");
+    assert_eq!(requests[1].method, "DOWNLOAD");
+    assert!(requests[1].api_key.is_empty());
+}
+
+#[cfg(feature = "csv")]
+#[tokio::test]
+async fn export_and_parse_reports_failed_download() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let path = "https://s3.eu-west-1.amazonaws.com/files.paystack.co/exports/transactions.csv";
+    http.respond_to_url(
+        "https://api.paystack.co/transaction/export",
+        mock_response("Export successful", json!({ "path": path })),
+    );
+    http.fail_on_url(path, "status code: 403 Forbidden");
+    let transactions = TransactionEndpoints::new(key, Arc::new(http));
+
+    // Act
+    let res = transactions
+        .export_and_parse(paystack::ExportTransactionsRequest::default())
+        .await;
+
+    // Assert
+    assert!(matches!(res, Err(PaystackAPIError::Transaction(msg)) if msg.contains("403")));
+}
+
 #[tokio::test]
 async fn partial_debit_transaction_passes_or_fails_depending_on_merchant_status() {
     // Arrange
@@ -303,3 +555,741 @@ async fn partial_debit_transaction_passes_or_fails_depending_on_merchant_status(
         }
     }
 }
+
+#[tokio::test]
+async fn fetch_transaction_by_reference_finds_matching_transaction() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(mock_response(
+        "Transactions retrieved",
+        json!([mock_transaction(4099260516, "re4lyvq3s3")]),
+    ));
+    let transactions = TransactionEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = transactions
+        .fetch_transaction_by_reference("re4lyvq3s3")
+        .await
+        .expect("unable to fetch transaction by reference");
+
+    // Assert
+    let requests = http.requests();
+    assert_eq!(requests[0].url, "https://api.paystack.co/transaction");
+    assert!(requests[0]
+        .query
+        .contains(&("reference".to_string(), "re4lyvq3s3".to_string())));
+    let data = res.data.unwrap();
+    assert_eq!(data.id, 4099260516);
+    assert_eq!(data.reference, "re4lyvq3s3");
+}
+
+#[tokio::test]
+async fn fetch_transaction_by_reference_returns_not_found_for_unknown_reference() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(mock_response("Transactions retrieved", json!([])));
+    let transactions = TransactionEndpoints::new(key, Arc::new(http));
+
+    // Act
+    let res = transactions
+        .fetch_transaction_by_reference("unknown-reference")
+        .await;
+
+    // Assert
+    assert!(matches!(res, Err(PaystackAPIError::NotFound(_))));
+}
+
+#[tokio::test]
+async fn fetch_transaction_by_reference_fails_when_the_filter_is_ignored() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(mock_response(
+        "Transactions retrieved",
+        json!([
+            mock_transaction(4099260516, "re4lyvq3s3"),
+            mock_transaction(4099260517, "t8f2kqw9zx"),
+        ]),
+    ));
+    let transactions = TransactionEndpoints::new(key, Arc::new(http));
+
+    // Act
+    let res = transactions
+        .fetch_transaction_by_reference("unknown-reference")
+        .await;
+
+    // Assert
+    match res {
+        Err(PaystackAPIError::FilterNotHonoured(msg)) => {
+            assert!(msg.contains("unknown-reference"));
+            assert!(msg.contains("re4lyvq3s3"));
+        }
+        other => panic!("expected FilterNotHonoured, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn can_verify_several_transactions_concurrently() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_to_url(
+        "https://api.paystack.co/transaction/verify/ref_one",
+        mock_response("Verification successful", mock_transaction(1, "ref_one")),
+    )
+    .fail_on_url(
+        "https://api.paystack.co/transaction/verify/ref_two",
+        "status code: 404 Not Found",
+    )
+    .respond_to_url(
+        "https://api.paystack.co/transaction/verify/ref_three",
+        mock_response("Verification successful", mock_transaction(3, "ref_three")),
+    );
+    let transaction = TransactionEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let results = transaction
+        .verify_transactions(
+            vec![
+                "ref_one".to_string(),
+                "ref_two".to_string(),
+                "ref_three".to_string(),
+                "ref_one".to_string(),
+            ],
+            2,
+        )
+        .await;
+
+    // Assert
+    assert_eq!(http.requests().len(), 3);
+    assert_eq!(results.len(), 3);
+    let one = results["ref_one"].as_ref().expect("ref_one should verify");
+    assert_eq!(one.data.as_ref().unwrap().reference, "ref_one");
+    let three = results["ref_three"]
+        .as_ref()
+        .expect("ref_three should verify");
+    assert_eq!(three.data.as_ref().unwrap().id, 3);
+    let two = results["ref_two"].as_ref().unwrap_err();
+    assert!(two.to_string().contains("404"));
+}
+
+#[tokio::test]
+async fn count_transactions_filters_by_status() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(json!({
+        "status": true,
+        "message": "Transactions retrieved",
+        "data": [mock_transaction(1, "reference")],
+        "meta": { "total": "70000", "perPage": 1, "page": 1 }
+    }));
+    let transaction = TransactionEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = transaction
+        .count_transactions(Some(Status::Failed))
+        .await
+        .expect("unable to count transactions");
+
+    // Assert
+    let requests = http.requests();
+    assert!(requests[0]
+        .query
+        .contains(&("status".to_string(), "failed".to_string())));
+    assert_eq!(res.data, Some(70000));
+}
+
+#[tokio::test]
+async fn find_by_metadata_returns_matching_transactions() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let mut order = mock_transaction(1, "T_order_1234");
+    order["metadata"] = json!({ "order_id": 1234 });
+    let mut encoded_order = mock_transaction(2, "T_order_1234_retry");
+    encoded_order["metadata"] = json!("{\"order_id\":\"1234\"}");
+    let mut other_order = mock_transaction(3, "T_order_5678");
+    other_order["metadata"] = json!({ "order_id": 5678 });
+    http.respond_with(mock_page(
+        "Transactions retrieved",
+        json!([
+            order,
+            encoded_order,
+            other_order,
+            mock_transaction(4, "T_no_metadata")
+        ]),
+        1,
+        1,
+    ));
+    let transaction = TransactionEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = transaction
+        .find_by_metadata(
+            "order_id",
+            "1234",
+            "2024-08-01T00:00:00.000Z",
+            "2024-08-31T23:59:59.000Z",
+        )
+        .await
+        .expect("unable to find transactions by metadata");
+
+    // Assert
+    let requests = http.requests();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0]
+        .query
+        .contains(&("from".to_string(), "2024-08-01T00:00:00.000Z".to_string())));
+    let references: Vec<_> = res
+        .data
+        .unwrap()
+        .into_iter()
+        .map(|transaction| transaction.reference)
+        .collect();
+    assert_eq!(references, vec!["T_order_1234", "T_order_1234_retry"]);
+}
+
+#[tokio::test]
+async fn list_transactions_lenient_keeps_valid_records() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let mut malformed = mock_transaction(2, "T_malformed");
+    malformed["amount"] = json!("20000.00");
+    http.respond_with(mock_response(
+        "Transactions retrieved",
+        json!([
+            mock_transaction(1, "T_first"),
+            malformed,
+            mock_transaction(3, "T_third")
+        ]),
+    ));
+    let transaction = TransactionEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = transaction
+        .list_transactions_lenient(Some(3), None)
+        .await
+        .expect("unable to list transactions");
+
+    // Assert
+    let list = res.data.unwrap();
+    let references: Vec<_> = list.items.iter().map(|t| t.reference.as_str()).collect();
+    assert_eq!(references, vec!["T_first", "T_third"]);
+    assert_eq!(list.errors.len(), 1);
+    assert_eq!(list.errors[0].0, 1);
+    assert!(list.errors[0].1.contains("invalid type"));
+}
+
+#[tokio::test]
+async fn fetch_with_context_bundles_transaction_and_refunds() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_to_url(
+        "https://api.paystack.co/transaction/1641",
+        mock_response("Transaction retrieved", mock_transaction(1641, "T_1641")),
+    )
+    .respond_to_url(
+        "https://api.paystack.co/refund",
+        mock_page(
+            "Refunds retrieved",
+            json!([{
+                "id": 3018284,
+                "integration": 412829,
+                "domain": "test",
+                "transaction": 1641,
+                "amount": 5000,
+                "currency": "NGN",
+                "status": "processed",
+                "created_at": "2021-12-07T09:21:17.000Z"
+            }]),
+            1,
+            1,
+        ),
+    );
+    let transaction = TransactionEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = transaction
+        .fetch_with_context(1641)
+        .await
+        .expect("unable to fetch transaction with context");
+
+    // Assert
+    let refund_request = http
+        .requests()
+        .into_iter()
+        .find(|request| request.url == "https://api.paystack.co/refund")
+        .expect("refunds were not requested");
+    assert!(refund_request
+        .query
+        .contains(&("transaction".to_string(), "1641".to_string())));
+    let context = res.data.unwrap();
+    assert_eq!(context.transaction.reference, "T_1641");
+    assert_eq!(context.refunds.len(), 1);
+    assert_eq!(context.refunds[0].amount, 5000);
+}
+
+#[tokio::test]
+async fn fetch_with_context_pages_through_all_refunds() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let refund = |id: u64| {
+        json!({
+            "id": id,
+            "integration": 412829,
+            "domain": "test",
+            "transaction": 1641,
+            "amount": 100,
+            "currency": "NGN",
+            "status": "processed",
+            "created_at": "2021-12-07T09:21:17.000Z"
+        })
+    };
+    let first_page: Vec<_> = (1..=100).map(refund).collect();
+    http.respond_to_url(
+        "https://api.paystack.co/transaction/1641",
+        mock_response("Transaction retrieved", mock_transaction(1641, "T_1641")),
+    )
+    .respond_to_url(
+        "https://api.paystack.co/refund",
+        mock_page("Refunds retrieved", json!(first_page), 1, 2),
+    )
+    .respond_to_url(
+        "https://api.paystack.co/refund",
+        mock_page("Refunds retrieved", json!([refund(101)]), 2, 2),
+    );
+    let transaction = TransactionEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = transaction
+        .fetch_with_context(1641)
+        .await
+        .expect("unable to fetch transaction with context");
+
+    // Assert
+    let refund_requests: Vec<_> = http
+        .requests()
+        .into_iter()
+        .filter(|request| request.url == "https://api.paystack.co/refund")
+        .collect();
+    assert_eq!(refund_requests.len(), 2);
+    assert!(refund_requests[1]
+        .query
+        .contains(&("page".to_string(), "2".to_string())));
+    let context = res.data.unwrap();
+    assert_eq!(context.refunds.len(), 101);
+    assert_eq!(context.refunds[100].id, 101);
+}
+
+#[tokio::test]
+async fn fetch_with_context_drops_refunds_when_the_transaction_filter_is_ignored() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let refund = |id: u64, transaction: u64| {
+        json!({
+            "id": id,
+            "integration": 412829,
+            "domain": "test",
+            "transaction": transaction,
+            "amount": 5000,
+            "currency": "NGN",
+            "status": "processed",
+            "created_at": "2021-12-07T09:21:17.000Z"
+        })
+    };
+    http.respond_to_url(
+        "https://api.paystack.co/transaction/1641",
+        mock_response("Transaction retrieved", mock_transaction(1641, "T_1641")),
+    )
+    .respond_to_url(
+        "https://api.paystack.co/refund",
+        mock_response(
+            "Refunds retrieved",
+            json!([refund(1, 1641), refund(2, 9999)]),
+        ),
+    );
+    let transaction = TransactionEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = transaction
+        .fetch_with_context(1641)
+        .await
+        .expect("unable to fetch transaction with context");
+
+    // Assert
+    let context = res.data.unwrap();
+    assert_eq!(context.transaction.id, 1641);
+    assert!(context.refunds.is_empty());
+}
+
+#[tokio::test]
+async fn fetch_with_context_leaves_refunds_empty_when_they_fail() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_to_url(
+        "https://api.paystack.co/transaction/1641",
+        mock_response("Transaction retrieved", mock_transaction(1641, "T_1641")),
+    )
+    .fail_on_url(
+        "https://api.paystack.co/refund",
+        "status code: 500 Internal Server Error",
+    );
+    let transaction = TransactionEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = transaction
+        .fetch_with_context(1641)
+        .await
+        .expect("unable to fetch transaction with context");
+
+    // Assert
+    let context = res.data.unwrap();
+    assert_eq!(context.transaction.id, 1641);
+    assert!(context.refunds.is_empty());
+}
+
+#[tokio::test]
+async fn keep_raw_exposes_fields_not_in_the_typed_data() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let mut transaction = mock_transaction(1, "T_raw_1234");
+    transaction["undocumented_field"] = json!("still here");
+    http.respond_with(mock_response("Transaction retrieved", transaction.clone()));
+    http.respond_with(mock_response("Transaction retrieved", transaction));
+    let endpoints = TransactionEndpoints::new(key, Arc::new(http.clone()));
+    let raw_endpoints = endpoints.clone().with_keep_raw(true);
+
+    // Act
+    let default_res = endpoints
+        .fetch_transaction(1)
+        .await
+        .expect("unable to fetch transaction");
+    let raw_res = raw_endpoints
+        .fetch_transaction(1)
+        .await
+        .expect("unable to fetch transaction");
+
+    // Assert
+    assert!(default_res.raw.is_none());
+    let raw = raw_res.raw.expect("the raw body should be kept");
+    assert_eq!(raw["data"]["undocumented_field"], "still here");
+    assert_eq!(raw_res.data.unwrap().reference, "T_raw_1234");
+}
+
+#[tokio::test]
+async fn initialize_transaction_generates_reference_with_prefix() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let authorization = json!({
+        "authorization_url": "https://checkout.paystack.com/0peioxfhpn",
+        "access_code": "0peioxfhpn",
+        "reference": "ORD-generated"
+    });
+    http.respond_with(mock_response(
+        "Authorization URL created",
+        authorization.clone(),
+    ));
+    http.respond_with(mock_response("Authorization URL created", authorization));
+    let transaction = TransactionEndpoints::new(key, Arc::new(http.clone()))
+        .with_reference_prefix("ORD-")
+        .expect("the prefix should be valid");
+
+    let generated = TransactionRequestBuilder::default()
+        .amount("10000".to_string())
+        .email("customer@example.com".to_string())
+        .build()
+        .unwrap();
+    let explicit = TransactionRequestBuilder::default()
+        .amount("10000".to_string())
+        .email("customer@example.com".to_string())
+        .reference("T_explicit_1234".to_string())
+        .build()
+        .unwrap();
+
+    // Act
+    transaction
+        .initialize_transaction(generated)
+        .await
+        .expect("unable to initialize transaction");
+    transaction
+        .initialize_transaction(explicit)
+        .await
+        .expect("unable to initialize transaction");
+
+    // Assert
+    let requests = http.requests();
+    let reference = requests[0].body["reference"].as_str().unwrap();
+    assert!(
+        reference.starts_with("ORD-"),
+        "unexpected reference: {reference}"
+    );
+    assert!(reference.len() > "ORD-".len());
+    assert_eq!(requests[1].body["reference"], "T_explicit_1234");
+}
+
+#[tokio::test]
+async fn reference_prefix_rejects_characters_not_allowed_in_references() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+
+    // Act
+    let res = TransactionEndpoints::new(key, Arc::new(http)).with_reference_prefix("ORD_");
+
+    // Assert
+    assert!(matches!(res, Err(PaystackAPIError::Validation(_))));
+}
+
+#[tokio::test]
+async fn initialize_transaction_uses_default_currency() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let authorization = json!({
+        "authorization_url": "https://checkout.paystack.com/0peioxfhpn",
+        "access_code": "0peioxfhpn",
+        "reference": "T_1234"
+    });
+    http.respond_with(mock_response(
+        "Authorization URL created",
+        authorization.clone(),
+    ));
+    http.respond_with(mock_response("Authorization URL created", authorization));
+    let transaction =
+        TransactionEndpoints::new(key, Arc::new(http.clone())).with_default_currency(Currency::KES);
+
+    let without_currency = TransactionRequestBuilder::default()
+        .amount("10000".to_string())
+        .email("customer@example.com".to_string())
+        .build()
+        .unwrap();
+    let with_currency = TransactionRequestBuilder::default()
+        .amount("10000".to_string())
+        .email("customer@example.com".to_string())
+        .currency(Currency::USD)
+        .build()
+        .unwrap();
+
+    // Act
+    transaction
+        .initialize_transaction(without_currency)
+        .await
+        .expect("unable to initialize transaction");
+    transaction
+        .initialize_transaction(with_currency)
+        .await
+        .expect("unable to initialize transaction");
+
+    // Assert
+    let requests = http.requests();
+    assert_eq!(requests[0].body["currency"], "KES");
+    assert_eq!(requests[1].body["currency"], "USD");
+}
+
+#[tokio::test]
+async fn list_transactions_with_filter_sends_filters_and_returns_meta() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let mut response = mock_response(
+        "Transactions retrieved",
+        json!([mock_transaction(1, "re4lyvq3s3")]),
+    );
+    response["meta"] =
+        json!({ "total": 51, "skipped": 50, "perPage": 50, "page": 2, "pageCount": 2 });
+    http.respond_with(response);
+    let transactions = TransactionEndpoints::new(key, Arc::new(http.clone()));
+
+    let request = ListTransactionsRequestBuilder::default()
+        .per_page(50)
+        .page(2)
+        .customer(181873746)
+        .terminal_id("2232WE17".to_string())
+        .status(Status::Success)
+        .from("2024-08-01T00:00:00.000Z".to_string())
+        .to("2024-08-31T23:59:59.000Z".to_string())
+        .amount(20000)
+        .build()
+        .unwrap();
+
+    // Act
+    let res = transactions
+        .list_transactions_with_filter(request)
+        .await
+        .expect("unable to list transactions");
+
+    // Assert
+    let query = &http.requests()[0].query;
+    let expected = [
+        ("perPage", "50"),
+        ("page", "2"),
+        ("customer", "181873746"),
+        ("terminalid", "2232WE17"),
+        ("status", "success"),
+        ("from", "2024-08-01T00:00:00.000Z"),
+        ("to", "2024-08-31T23:59:59.000Z"),
+        ("amount", "20000"),
+    ];
+    for (key, value) in expected {
+        assert!(
+            query.contains(&(key.to_string(), value.to_string())),
+            "missing {key}={value} in {query:?}"
+        );
+    }
+    assert_eq!(res.data.unwrap()[0].reference, "re4lyvq3s3");
+    assert_eq!(res.meta.unwrap().page, Some(2));
+}
+
+#[tokio::test]
+async fn fetch_transaction_reads_nested_customer_authorization_and_plan() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let plan = json!({
+        "id": 28,
+        "name": "Monthly retainer",
+        "plan_code": "PLN_gx2wn530m0i3w3m",
+        "amount": 20000,
+        "interval": "monthly",
+        "currency": "NGN"
+    });
+    let mut subscription_charge = mock_transaction(4099260516, "re4lyvq3s3");
+    subscription_charge["plan"] = plan.clone();
+    subscription_charge["plan_object"] = plan;
+    let mut one_off = mock_transaction(4099260517, "T592386937184729");
+    one_off["plan"] = json!({});
+    one_off["plan_object"] = json!({});
+    http.respond_to_url(
+        "https://api.paystack.co/transaction/4099260516",
+        mock_response("Transaction retrieved", subscription_charge),
+    );
+    http.respond_to_url(
+        "https://api.paystack.co/transaction/4099260517",
+        mock_response("Transaction retrieved", one_off),
+    );
+    let transactions = TransactionEndpoints::new(key, Arc::new(http));
+
+    // Act
+    let subscription_charge = transactions
+        .fetch_transaction(4099260516)
+        .await
+        .expect("unable to fetch transaction")
+        .data
+        .unwrap();
+    let one_off = transactions
+        .fetch_transaction(4099260517)
+        .await
+        .expect("unable to fetch transaction")
+        .data
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        subscription_charge.customer.customer_code,
+        "CUS_1rkzaqsv4rrhqo6"
+    );
+    assert_eq!(
+        subscription_charge.authorization.last4.as_deref(),
+        Some("4081")
+    );
+    assert_eq!(
+        subscription_charge.plan.as_deref(),
+        Some("PLN_gx2wn530m0i3w3m")
+    );
+    assert_eq!(
+        subscription_charge.plan_object.unwrap().name.as_deref(),
+        Some("Monthly retainer")
+    );
+    assert_eq!(one_off.plan, None);
+    assert!(one_off.plan_object.is_none());
+}
+
+#[tokio::test]
+async fn view_transaction_timeline_returns_typed_history() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_to_url(
+        "https://api.paystack.co/transaction/timeline/re4lyvq3s3",
+        mock_response(
+            "Timeline retrieved",
+            json!({
+                "time_spent": 9,
+                "attempts": 2,
+                "authentication": null,
+                "errors": 1,
+                "success": true,
+                "mobile": false,
+                "input": [],
+                "channel": "card",
+                "history": [
+                    { "type": "input", "message": "Filled these fields: card number, card expiry, card cvv", "time": 7 },
+                    { "type": "error", "message": "Error: Declined", "time": 7 },
+                    { "type": "success", "message": "Successfully paid", "time": 8 },
+                    { "type": "close", "message": "Page closed", "time": 9 }
+                ]
+            }),
+        ),
+    );
+    let transactions = TransactionEndpoints::new(key, Arc::new(http));
+
+    // Act
+    let res = transactions
+        .view_transaction_timeline(TransactionIdentifier::Reference("re4lyvq3s3".to_string()))
+        .await
+        .expect("unable to view transaction timeline");
+
+    // Assert
+    let timeline = res.data.unwrap();
+    assert_eq!(timeline.time_spent, Some(9));
+    assert_eq!(timeline.attempts, Some(2));
+    assert_eq!(timeline.errors, Some(1));
+    let history = timeline.history.unwrap();
+    assert_eq!(history.len(), 4);
+    assert_eq!(history[1].event_type, TimelineEventType::Error);
+    assert_eq!(history[1].message, "Error: Declined");
+    assert_eq!(history[2].time, 8);
+}
+
+#[tokio::test]
+async fn transaction_totals_filters_by_date_range() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(mock_response(
+        "Transaction totals",
+        json!({
+            "total_transactions": 42670,
+            "unique_customers": 8364,
+            "total_volume": 6950000000_u64,
+            "total_volume_by_currency": [
+                { "currency": "NGN", "amount": 6900000000_u64 },
+                { "currency": "USD", "amount": 50000000 }
+            ],
+            "pending_transfers": 24500000,
+            "pending_transfers_by_currency": [
+                { "currency": "NGN", "amount": 24500000 }
+            ]
+        }),
+    ));
+    let transactions = TransactionEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = transactions
+        .transaction_totals(
+            Some("2024-08-01T00:00:00.000Z"),
+            Some("2024-08-31T23:59:59.000Z"),
+        )
+        .await
+        .expect("unable to get transaction totals");
+
+    // Assert
+    let request = &http.requests()[0];
+    assert_eq!(request.url, "https://api.paystack.co/transaction/totals");
+    assert_eq!(
+        request.query,
+        vec![
+            ("from".to_string(), "2024-08-01T00:00:00.000Z".to_string()),
+            ("to".to_string(), "2024-08-31T23:59:59.000Z".to_string())
+        ]
+    );
+    let totals = res.data.unwrap();
+    assert_eq!(totals.total_transactions, Some(42670));
+    assert_eq!(totals.total_volume, Some(6950000000));
+    assert_eq!(
+        totals.total_volume_by_currency.unwrap()[0].amount,
+        6900000000
+    );
+    assert_eq!(totals.pending_transfers, Some(24500000));
+}
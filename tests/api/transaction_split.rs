@@ -1,13 +1,19 @@
-use crate::helpers::{get_bank_account_number_and_code, get_paystack_client};
+use crate::helpers::{
+    get_bank_account_number_and_code, get_mock_http_client, get_paystack_client, mock_page,
+    mock_response,
+};
 use fake::{
     faker::{company::en::CompanyName, lorem::en::Sentence, name::en::FirstName},
     Fake,
 };
 use paystack::{
-    CreateSubaccountRequestBuilder, Currency, DeleteSubAccountBody, PaystackClient, ReqwestClient,
-    SubaccountBody, SubaccountBodyBuilder, TransactionSplitRequest, TransactionSplitRequestBuilder,
-    UpdateTransactionSplitRequestBuilder,
+    CreateSubaccountRequestBuilder, Currency, DeleteSubAccountBody,
+    ListTransactionSplitsRequestBuilder, PaystackAPIError, PaystackClient, ReqwestClient,
+    SubaccountBody, SubaccountBodyBuilder, TransactionSplitEndpoints, TransactionSplitRequest,
+    TransactionSplitRequestBuilder, UpdateTransactionSplitRequestBuilder,
 };
+use serde_json::json;
+use std::sync::Arc;
 
 async fn create_subaccount_body(
     client: &PaystackClient<ReqwestClient>,
@@ -58,7 +64,7 @@ async fn build_transaction_split(
         .name(txn_split_name.clone())
         .split_type(paystack::SplitType::Percentage)
         .currency(paystack::Currency::NGN)
-        .bearer_type(paystack::BearerType::Subaccount)
+        .bearer_type(paystack::Bearer::Subaccount)
         .subaccounts(vec![
             first_subaccount_body.clone(),
             second_subaccount_body.clone(),
@@ -100,7 +106,7 @@ async fn create_transaction_split_fails_with_invalid_data() {
         .split_type(paystack::SplitType::Flat)
         .currency(paystack::Currency::EMPTY)
         .subaccounts(vec![])
-        .bearer_type(paystack::BearerType::Subaccount)
+        .bearer_type(paystack::Bearer::Subaccount)
         .bearer_subaccount("non_existent_subaccount".to_string())
         .build()
         .unwrap();
@@ -199,8 +205,8 @@ async fn update_a_transaction_split_passes_with_valid_data() {
     // create update split body
     let update_split_body = UpdateTransactionSplitRequestBuilder::default()
         .active(false)
-        .bearer_type(paystack::BearerType::Account)
-        .bearer_subaccount(new_subaccount_body)
+        .bearer_type(paystack::Bearer::Account)
+        .bearer_subaccount(new_subaccount_body.subaccount)
         .name(new_split_name.clone())
         .build()
         .unwrap();
@@ -241,7 +247,7 @@ async fn update_a_transaction_split_fails_with_invalid_data() {
     // create update split body
     let update_split_body = UpdateTransactionSplitRequestBuilder::default()
         .active(true)
-        .bearer_type(paystack::BearerType::Subaccount)
+        .bearer_type(paystack::Bearer::Subaccount)
         .name("".to_string())
         .build()
         .unwrap();
@@ -413,3 +419,433 @@ async fn remove_a_subaccount_from_a_transaction_split_fails_with_invalid_data()
         panic!();
     }
 }
+
+fn mock_split(id: u32, subaccount_codes: &[&str]) -> serde_json::Value {
+    let subaccounts: Vec<_> = subaccount_codes
+        .iter()
+        .map(|code| {
+            json!({
+                "subaccount": {
+                    "id": 55,
+                    "subaccount_code": code,
+                    "business_name": "Sunshine Studios",
+                    "settlement_bank": "Access Bank",
+                    "account_number": "0193274682"
+                },
+                "share": 20
+            })
+        })
+        .collect();
+
+    json!({
+        "id": id,
+        "name": format!("Split {id}"),
+        "type": "percentage",
+        "currency": "NGN",
+        "integration": 428626,
+        "domain": "test",
+        "split_code": format!("SPL_{id}"),
+        "active": true,
+        "bearer_type": "subaccount",
+        "bearer_subaccount": 55,
+        "createdAt": "2020-06-30T11:42:29.000Z",
+        "updatedAt": "2020-06-30T11:42:29.000Z",
+        "total_subaccounts": subaccounts.len(),
+        "subaccounts": subaccounts
+    })
+}
+
+#[tokio::test]
+async fn list_splits_for_subaccount_returns_only_its_splits() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(mock_page(
+        "Split retrieved",
+        json!([
+            mock_split(1, &["ACCT_4hl4xenwpjy5wb"]),
+            mock_split(2, &["ACCT_eg4sob4590pq9vb"]),
+            mock_split(3, &["ACCT_eg4sob4590pq9vb", "ACCT_4hl4xenwpjy5wb"])
+        ]),
+        1,
+        1,
+    ));
+    let split = TransactionSplitEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = split
+        .list_splits_for_subaccount("ACCT_4hl4xenwpjy5wb")
+        .await
+        .expect("unable to list splits for subaccount");
+
+    // Assert
+    assert_eq!(http.requests().len(), 1);
+    let split_codes: Vec<_> = res
+        .data
+        .unwrap()
+        .into_iter()
+        .map(|split| split.split_code)
+        .collect();
+    assert_eq!(split_codes, vec!["SPL_1", "SPL_3"]);
+}
+
+#[tokio::test]
+async fn list_transaction_splits_with_filter_sends_only_set_filters() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(mock_response(
+        "Split retrieved",
+        json!([mock_split(
+            1,
+            &["ACCT_4hl4xenwpjy5wb", "ACCT_eg4sob4590pq9vb"]
+        )]),
+    ));
+    let split = TransactionSplitEndpoints::new(key, Arc::new(http.clone()));
+    let request = ListTransactionSplitsRequestBuilder::default()
+        .name("Marketplace split".to_string())
+        .active(true)
+        .sort_by("name".to_string())
+        .per_page(20)
+        .page(2)
+        .from("2024-08-01T00:00:00.000Z".to_string())
+        .build()
+        .unwrap();
+
+    // Act
+    let res = split
+        .list_transaction_splits_with_filter(request)
+        .await
+        .expect("unable to list splits");
+
+    // Assert
+    let query = &http.requests()[0].query;
+    let expected: Vec<(String, String)> = [
+        ("name", "Marketplace split"),
+        ("active", "true"),
+        ("sort_by", "name"),
+        ("perPage", "20"),
+        ("page", "2"),
+        ("from", "2024-08-01T00:00:00.000Z"),
+    ]
+    .iter()
+    .map(|(k, v)| (k.to_string(), v.to_string()))
+    .collect();
+    assert_eq!(query, &expected);
+    let splits = res.data.unwrap();
+    assert_eq!(splits[0].subaccounts.len(), 2);
+    assert_eq!(
+        splits[0].subaccounts[1].subaccount.subaccount_code,
+        "ACCT_eg4sob4590pq9vb"
+    );
+    assert_eq!(splits[0].subaccounts[1].share, 20);
+}
+
+#[tokio::test]
+async fn fetch_transaction_split_returns_subaccounts_and_bearer() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(mock_response(
+        "Split retrieved",
+        mock_split(143, &["ACCT_4hl4xenwpjy5wb", "ACCT_eg4sob4590pq9vb"]),
+    ));
+    let split = TransactionSplitEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = split
+        .fetch_transaction_split("143")
+        .await
+        .expect("unable to fetch split");
+
+    // Assert
+    let request = &http.requests()[0];
+    assert_eq!(request.method, "GET");
+    assert!(request.url.ends_with("/split/143"));
+    let data = res.data.unwrap();
+    assert_eq!(data.split_code, "SPL_143");
+    assert_eq!(data.bearer_type, paystack::Bearer::Subaccount);
+    assert_eq!(data.bearer_subaccount, Some(55));
+    assert_eq!(data.subaccounts.len(), 2);
+    assert_eq!(data.total_share(), 40);
+}
+
+#[tokio::test]
+async fn update_transaction_split_sends_bearer_subaccount_code() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let mut updated_split = mock_split(143, &["ACCT_4hl4xenwpjy5wb"]);
+    updated_split["name"] = json!("Vendor split");
+    updated_split["active"] = json!(false);
+    http.respond_with(mock_response("Split group updated", updated_split));
+    let split = TransactionSplitEndpoints::new(key, Arc::new(http.clone()));
+    let body = UpdateTransactionSplitRequestBuilder::default()
+        .name("Vendor split".to_string())
+        .active(false)
+        .bearer_type(paystack::Bearer::Subaccount)
+        .bearer_subaccount("ACCT_4hl4xenwpjy5wb".to_string())
+        .build()
+        .unwrap();
+
+    // Act
+    let res = split
+        .update_transaction_split("143", body)
+        .await
+        .expect("unable to update split");
+
+    // Assert
+    let request = &http.requests()[0];
+    assert_eq!(request.method, "PUT");
+    assert!(request.url.ends_with("/split/143"));
+    assert_eq!(
+        request.body,
+        json!({
+            "name": "Vendor split",
+            "active": false,
+            "bearer_type": "subaccount",
+            "bearer_subaccount": "ACCT_4hl4xenwpjy5wb"
+        })
+    );
+    let data = res.data.unwrap();
+    assert_eq!(data.name, "Vendor split");
+    assert_eq!(data.active, Some(false));
+}
+
+fn split_request(split_type: paystack::SplitType, shares: &[f32]) -> TransactionSplitRequest {
+    let subaccounts: Vec<_> = shares
+        .iter()
+        .enumerate()
+        .map(|(i, share)| SubaccountBody {
+            subaccount: format!("ACCT_{i}"),
+            share: *share,
+        })
+        .collect();
+
+    TransactionSplitRequestBuilder::default()
+        .name("Marketplace split".to_string())
+        .split_type(split_type)
+        .currency(paystack::Currency::NGN)
+        .bearer_type(paystack::Bearer::Account)
+        .subaccounts(subaccounts)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn create_percentage_split_with_valid_shares_is_sent() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(mock_response(
+        "Split created",
+        mock_split(1, &["ACCT_0", "ACCT_1"]),
+    ));
+    let split = TransactionSplitEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = split
+        .create_transaction_split(split_request(
+            paystack::SplitType::Percentage,
+            &[60.0, 40.0],
+        ))
+        .await;
+
+    // Assert
+    assert!(res.is_ok());
+    assert_eq!(http.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn create_percentage_split_adding_up_to_exactly_100_is_sent() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(mock_response(
+        "Split created",
+        mock_split(1, &["ACCT_0", "ACCT_1", "ACCT_2"]),
+    ));
+    let split = TransactionSplitEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    // These add up to 100.00001 as f32
+    let res = split
+        .create_transaction_split(split_request(
+            paystack::SplitType::Percentage,
+            &[0.3, 64.3, 35.4],
+        ))
+        .await;
+
+    // Assert
+    assert!(res.is_ok());
+    assert_eq!(http.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn create_split_borne_by_main_account_omits_bearer_subaccount() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let mut response_split = mock_split(1, &["ACCT_0"]);
+    response_split["bearer_type"] = json!("account");
+    response_split["bearer_subaccount"] = json!(null);
+    http.respond_with(mock_response("Split created", response_split));
+    let split = TransactionSplitEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = split
+        .create_transaction_split(split_request(paystack::SplitType::Percentage, &[20.0]))
+        .await
+        .expect("unable to create split");
+
+    // Assert
+    let requests = http.requests();
+    assert_eq!(requests[0].body["bearer_type"], "account");
+    assert!(requests[0].body.get("bearer_subaccount").is_none());
+    assert_eq!(requests[0].body["subaccounts"][0]["subaccount"], "ACCT_0");
+    let data = res.data.unwrap();
+    assert_eq!(data.bearer_type, paystack::Bearer::Account);
+    assert_eq!(data.bearer_subaccount, None);
+}
+
+#[tokio::test]
+async fn create_percentage_split_over_100_is_rejected() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let split = TransactionSplitEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = split
+        .create_transaction_split(split_request(
+            paystack::SplitType::Percentage,
+            &[80.0, 30.0],
+        ))
+        .await;
+
+    // Assert
+    assert!(matches!(res, Err(PaystackAPIError::Validation(msg)) if msg.contains("110")));
+    assert!(http.requests().is_empty());
+}
+
+#[tokio::test]
+async fn create_flat_split_with_valid_shares_is_sent() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(mock_response(
+        "Split created",
+        mock_split(1, &["ACCT_0", "ACCT_1"]),
+    ));
+    let split = TransactionSplitEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let valid = split
+        .create_transaction_split(split_request(paystack::SplitType::Flat, &[5000.0, 25000.0]))
+        .await;
+    let fractional = split
+        .create_transaction_split(split_request(paystack::SplitType::Flat, &[50.5]))
+        .await;
+
+    // Assert
+    assert!(valid.is_ok());
+    assert!(matches!(fractional, Err(PaystackAPIError::Validation(_))));
+    assert_eq!(http.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn add_subaccount_over_100_percent_is_rejected() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    // mock_split gives each subaccount a 20% share
+    http.respond_with(mock_response(
+        "Split retrieved",
+        mock_split(1, &["ACCT_0", "ACCT_1"]),
+    ));
+    let split = TransactionSplitEndpoints::new(key, Arc::new(http.clone()));
+    let body = SubaccountBody {
+        subaccount: "ACCT_2".to_string(),
+        share: 70.0,
+    };
+
+    // Act
+    let res = split.add_or_update_subaccount_split("1", body).await;
+
+    // Assert
+    assert!(matches!(res, Err(PaystackAPIError::Validation(msg)) if msg.contains("110")));
+    let requests = http.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "GET");
+}
+
+#[tokio::test]
+async fn add_subaccount_to_split_of_unknown_type_is_rejected() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    let mut unknown = mock_split(1, &["ACCT_0"]);
+    unknown["type"] = json!("tiered");
+    http.respond_with(mock_response("Split retrieved", unknown));
+    let split = TransactionSplitEndpoints::new(key, Arc::new(http.clone()));
+    let body = SubaccountBody {
+        subaccount: "ACCT_1".to_string(),
+        share: 10.0,
+    };
+
+    // Act
+    let res = split.add_or_update_subaccount_split("1", body).await;
+
+    // Assert
+    assert!(matches!(res, Err(PaystackAPIError::TransactionSplit(msg)) if msg.contains("tiered")));
+    assert_eq!(http.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn update_existing_subaccount_share_replaces_its_share() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    // mock_split gives each subaccount a 20% share
+    http.respond_with(mock_response(
+        "Split retrieved",
+        mock_split(1, &["ACCT_0", "ACCT_1"]),
+    ));
+    http.respond_with(mock_response(
+        "Subaccount added",
+        mock_split(1, &["ACCT_0", "ACCT_1"]),
+    ));
+    let split = TransactionSplitEndpoints::new(key, Arc::new(http.clone()));
+    let body = SubaccountBody {
+        subaccount: "ACCT_0".to_string(),
+        share: 75.0,
+    };
+
+    // Act
+    let res = split.add_or_update_subaccount_split("1", body).await;
+
+    // Assert
+    assert!(res.is_ok());
+    let requests = http.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].method, "POST");
+    assert!(requests[1].url.ends_with("/split/1/subaccount/add"));
+    assert_eq!(
+        requests[1].body,
+        json!({"subaccount": "ACCT_0", "share": 75.0})
+    );
+}
+
+#[tokio::test]
+async fn remove_split_subaccount_sends_the_subaccount_code() {
+    // Arrange
+    let (key, http) = get_mock_http_client();
+    http.respond_with(json!({"status": true, "message": "Subaccount removed"}));
+    let split = TransactionSplitEndpoints::new(key, Arc::new(http.clone()));
+
+    // Act
+    let res = split
+        .remove_split_subaccount("143", "ACCT_4hl4xenwpjy5wb")
+        .await
+        .expect("unable to remove subaccount");
+
+    // Assert
+    assert!(res.status);
+    assert_eq!(res.message, "Subaccount removed");
+    let removed = res.data.unwrap();
+    assert_eq!(removed.split_id, "143");
+    assert_eq!(removed.subaccount, "ACCT_4hl4xenwpjy5wb");
+    assert!(removed.removed);
+    let request = &http.requests()[0];
+    assert_eq!(request.method, "POST");
+    assert!(request.url.ends_with("/split/143/subaccount/remove"));
+    assert_eq!(request.body, json!({"subaccount": "ACCT_4hl4xenwpjy5wb"}));
+}